@@ -0,0 +1,81 @@
+//! The members of `ar` archives, in file order, with their long names resolved.
+//!
+//! goblin only exposes members by name, which loses their order and any duplicates, so the headers are
+//! walked here. Both the GNU (`/123` into the `//` table) and BSD (`#1/len`) long name schemes are handled.
+
+use goblin::{archive, error};
+
+const SIZEOF_HEADER: usize = 60;
+const SIZEOF_IDENTIFIER: usize = 16;
+/// The offset of the decimal member size in the header
+const SIZE_OFFSET: usize = 48;
+const SIZEOF_SIZE: usize = 10;
+
+#[derive(Debug)]
+pub struct Member<'a> {
+    pub name: String,
+    /// The file offset of the member's data
+    pub offset: usize,
+    pub data: &'a [u8],
+}
+
+fn size(bytes: &[u8], header: usize) -> error::Result<usize> {
+    let field = bytes.get(header + SIZE_OFFSET..header + SIZE_OFFSET + SIZEOF_SIZE)
+        .ok_or_else(|| error::Error::Malformed(format!("archive member header at {:#x} is truncated", header)))?;
+    ::std::str::from_utf8(field).ok().and_then(|size| size.trim().parse().ok())
+        .ok_or_else(|| error::Error::Malformed(format!("archive member at {:#x} has a bad size {:?}", header, String::from_utf8_lossy(field))))
+}
+
+/// The name at `offset` in the GNU long name table, which ends with `/\n`
+fn long_name(names: &[u8], offset: usize) -> Option<&str> {
+    let name = names.get(offset..)?;
+    let end = name.iter().position(|&c| c == b'\n').unwrap_or(name.len());
+    ::std::str::from_utf8(&name[..end]).ok().map(|name| name.trim_end_matches('/'))
+}
+
+/// Lists the members of the archive in `bytes`, leaving out the symbol index and the long name table
+pub fn members(bytes: &[u8]) -> error::Result<Vec<Member<'_>>> {
+    if !bytes.starts_with(archive::MAGIC) {
+        return Err(error::Error::BadMagic(0));
+    }
+    let mut members = Vec::new();
+    let mut names: &[u8] = &[];
+    let mut header = archive::SIZEOF_MAGIC;
+    while header + SIZEOF_HEADER <= bytes.len() {
+        let identifier = String::from_utf8_lossy(&bytes[header..header + SIZEOF_IDENTIFIER]).trim_end().to_owned();
+        let size = size(bytes, header)?;
+        let mut offset = header + SIZEOF_HEADER;
+        let mut data = bytes.get(offset..offset.saturating_add(size))
+            .ok_or_else(|| error::Error::Malformed(format!("archive member {:?} of size {:#x} at {:#x} is past the end", identifier, size, offset)))?;
+        // members are 2 byte aligned
+        header = offset + size + (size & 1);
+        let name = match identifier.as_str() {
+            "/" | "/SYM64/" | "__.SYMDEF" | "__.SYMDEF SORTED" => continue,
+            "//" => {
+                names = data;
+                continue;
+            },
+            identifier if identifier.starts_with("#1/") => {
+                // the BSD name precedes the data, and is counted in its size
+                let len = identifier[3..].parse::<usize>().map_err(|_| error::Error::Malformed(format!("bad BSD archive name {:?}", identifier)))?;
+                let name = data.get(..len).ok_or_else(|| error::Error::Malformed(format!("BSD archive name of length {} is past the end", len)))?;
+                data = &data[len..];
+                offset += len;
+                let name = String::from_utf8_lossy(name).trim_end_matches('\0').to_owned();
+                if name.starts_with("__.SYMDEF") {
+                    continue;
+                }
+                name
+            },
+            identifier if identifier.starts_with('/') => {
+                match identifier[1..].parse::<usize>().ok().and_then(|index| long_name(names, index)) {
+                    Some(name) => name.to_owned(),
+                    None => identifier.to_owned(),
+                }
+            },
+            identifier => identifier.trim_end_matches('/').to_owned(),
+        };
+        members.push(Member { name, offset, data });
+    }
+    Ok(members)
+}
@@ -0,0 +1,206 @@
+//! Architecture sub-type information: the per-machine meaning of ELF `e_flags`, and Mach-o cpu subtypes.
+//!
+//! Each decoder returns `(label, value)` pairs, with any bits it doesn't know as a final `unknown` entry.
+
+use goblin::elf::header::{EM_ARM, EM_MIPS, EM_PPC64, EM_RISCV};
+use goblin::mach::constants::cputype::{CPU_ARCH_ABI64, CPU_TYPE_X86, CPU_TYPE_X86_64, CPU_TYPE_ARM, CPU_TYPE_ARM64};
+
+pub const EF_ARM_EABIMASK: u32 = 0xff000000;
+pub const EF_ARM_BE8: u32 = 0x00800000;
+pub const EF_ARM_ABI_FLOAT_HARD: u32 = 0x00000400;
+pub const EF_ARM_ABI_FLOAT_SOFT: u32 = 0x00000200;
+
+pub const EF_MIPS_NOREORDER: u32 = 0x00000001;
+pub const EF_MIPS_PIC: u32 = 0x00000002;
+pub const EF_MIPS_CPIC: u32 = 0x00000004;
+pub const EF_MIPS_ABI2: u32 = 0x00000020;
+pub const EF_MIPS_32BITMODE: u32 = 0x00000100;
+pub const EF_MIPS_FP64: u32 = 0x00000200;
+pub const EF_MIPS_NAN2008: u32 = 0x00000400;
+pub const EF_MIPS_ABI: u32 = 0x0000f000;
+pub const EF_MIPS_MACH: u32 = 0x00ff0000;
+pub const EF_MIPS_MICROMIPS: u32 = 0x02000000;
+pub const EF_MIPS_ARCH_ASE_M16: u32 = 0x04000000;
+pub const EF_MIPS_ARCH_ASE_MDMX: u32 = 0x08000000;
+pub const EF_MIPS_ARCH: u32 = 0xf0000000;
+
+pub const EF_PPC64_ABI: u32 = 0x3;
+
+pub const EF_RISCV_RVC: u32 = 0x1;
+pub const EF_RISCV_FLOAT_ABI: u32 = 0x6;
+pub const EF_RISCV_RVE: u32 = 0x8;
+pub const EF_RISCV_TSO: u32 = 0x10;
+
+pub const CPU_SUBTYPE_MASK: u32 = 0xff000000;
+pub const CPU_SUBTYPE_LIB64: u32 = 0x80000000;
+pub const CPU_SUBTYPE_PTRAUTH_ABI: u32 = 0x80000000;
+pub const CPU_ARCH_ABI64_32: u32 = 0x02000000;
+pub const CPU_TYPE_ARM64_32: u32 = CPU_TYPE_ARM | CPU_ARCH_ABI64_32;
+pub const CPU_TYPE_POWERPC: u32 = 18;
+pub const CPU_TYPE_POWERPC64: u32 = CPU_TYPE_POWERPC | CPU_ARCH_ABI64;
+
+const MIPS_FLAGS: [(u32, &str); 9] = [
+    (EF_MIPS_NOREORDER, "noreorder"),
+    (EF_MIPS_PIC, "pic"),
+    (EF_MIPS_CPIC, "cpic"),
+    (EF_MIPS_32BITMODE, "32bitmode"),
+    (EF_MIPS_FP64, "fp64"),
+    (EF_MIPS_NAN2008, "nan2008"),
+    (EF_MIPS_MICROMIPS, "micromips"),
+    (EF_MIPS_ARCH_ASE_M16, "mips16"),
+    (EF_MIPS_ARCH_ASE_MDMX, "mdmx"),
+];
+
+fn mips_abi(e_flags: u32, is_64: bool) -> &'static str {
+    match e_flags & EF_MIPS_ABI {
+        0x1000 => "O32",
+        0x2000 => "O64",
+        0x3000 => "EABI32",
+        0x4000 => "EABI64",
+        0 if e_flags & EF_MIPS_ABI2 != 0 => "N32",
+        0 if is_64 => "N64",
+        0 => "O32 (implied)",
+        _ => "unknown",
+    }
+}
+
+fn mips_isa(e_flags: u32) -> &'static str {
+    match (e_flags & EF_MIPS_ARCH) >> 28 {
+        0x0 => "mips1",
+        0x1 => "mips2",
+        0x2 => "mips3",
+        0x3 => "mips4",
+        0x4 => "mips5",
+        0x5 => "mips32",
+        0x6 => "mips64",
+        0x7 => "mips32r2",
+        0x8 => "mips64r2",
+        0x9 => "mips32r6",
+        0xa => "mips64r6",
+        _ => "unknown",
+    }
+}
+
+fn riscv_float_abi(e_flags: u32) -> &'static str {
+    match e_flags & EF_RISCV_FLOAT_ABI {
+        0x0 => "soft",
+        0x2 => "single",
+        0x4 => "double",
+        _ => "quad",
+    }
+}
+
+/// The names of the bits of `flags` that are in `table`
+fn flag_names(flags: u32, table: &[(u32, &'static str)]) -> String {
+    table.iter().filter(|&&(bit, _)| flags & bit != 0).map(|&(_, name)| name).collect::<Vec<_>>().join(" ")
+}
+
+/// Decodes `e_flags` for `machine`; machines with no flags defined, such as x86, only report unknown bits
+pub fn elf_flags(machine: u16, is_64: bool, e_flags: u32) -> Vec<(&'static str, String)> {
+    let mut info = Vec::new();
+    let known = match machine {
+        EM_ARM => {
+            let version = (e_flags & EF_ARM_EABIMASK) >> 24;
+            info.push(("EABI", if version == 0 { "unknown (GNU)".to_owned() } else { format!("version {}", version) }));
+            if e_flags & EF_ARM_ABI_FLOAT_HARD != 0 {
+                info.push(("float ABI", "hard".to_owned()));
+            } else if e_flags & EF_ARM_ABI_FLOAT_SOFT != 0 {
+                info.push(("float ABI", "soft".to_owned()));
+            }
+            if e_flags & EF_ARM_BE8 != 0 {
+                info.push(("endianness", "BE8".to_owned()));
+            }
+            EF_ARM_EABIMASK | EF_ARM_BE8 | EF_ARM_ABI_FLOAT_HARD | EF_ARM_ABI_FLOAT_SOFT
+        },
+        EM_MIPS => {
+            info.push(("ABI", mips_abi(e_flags, is_64).to_owned()));
+            info.push(("ISA", mips_isa(e_flags).to_owned()));
+            if e_flags & EF_MIPS_MACH != 0 {
+                info.push(("machine", format!("{:#x}", (e_flags & EF_MIPS_MACH) >> 16)));
+            }
+            let flags = flag_names(e_flags, &MIPS_FLAGS);
+            if !flags.is_empty() {
+                info.push(("flags", flags));
+            }
+            MIPS_FLAGS.iter().fold(EF_MIPS_ABI2 | EF_MIPS_ABI | EF_MIPS_MACH | EF_MIPS_ARCH, |known, &(bit, _)| known | bit)
+        },
+        EM_RISCV => {
+            info.push(("float ABI", riscv_float_abi(e_flags).to_owned()));
+            let flags = flag_names(e_flags, &[(EF_RISCV_RVC, "rvc"), (EF_RISCV_RVE, "rve"), (EF_RISCV_TSO, "tso")]);
+            if !flags.is_empty() {
+                info.push(("flags", flags));
+            }
+            EF_RISCV_RVC | EF_RISCV_FLOAT_ABI | EF_RISCV_RVE | EF_RISCV_TSO
+        },
+        EM_PPC64 => {
+            let abi = match e_flags & EF_PPC64_ABI {
+                0 => "unspecified",
+                1 => "ELFv1",
+                2 => "ELFv2",
+                _ => "unknown",
+            };
+            info.push(("ABI", abi.to_owned()));
+            EF_PPC64_ABI
+        },
+        _ => 0,
+    };
+    if e_flags & !known != 0 {
+        info.push(("unknown", format!("{:#x}", e_flags & !known)));
+    }
+    info
+}
+
+fn arm_subtype(subtype: u32) -> &'static str {
+    match subtype {
+        0 => "arm (all)",
+        5 => "armv4t",
+        6 => "armv6",
+        7 => "armv5tej",
+        8 => "xscale",
+        9 => "armv7",
+        10 => "armv7f",
+        11 => "armv7s",
+        12 => "armv7k",
+        13 => "armv8",
+        14 => "armv6m",
+        15 => "armv7m",
+        16 => "armv7em",
+        _ => "unknown",
+    }
+}
+
+/// Decodes the full 32-bit Mach-o `cpusubtype` of a `cputype` binary, including its capability bits
+pub fn mach_subtype(cputype: u32, cpusubtype: u32) -> Vec<(&'static str, String)> {
+    let subtype = cpusubtype & !CPU_SUBTYPE_MASK;
+    let caps = cpusubtype & CPU_SUBTYPE_MASK;
+    let mut info = Vec::new();
+    let name = match cputype {
+        CPU_TYPE_X86 => match subtype { 3 => "i386 (all)", _ => "unknown" },
+        CPU_TYPE_X86_64 => match subtype { 3 => "x86_64 (all)", 8 => "x86_64h", _ => "unknown" },
+        CPU_TYPE_ARM => arm_subtype(subtype),
+        CPU_TYPE_ARM64 => match subtype { 0 => "arm64 (all)", 1 => "arm64v8", 2 => "arm64e", _ => "unknown" },
+        CPU_TYPE_ARM64_32 => match subtype { 0 => "arm64_32 (all)", 1 => "arm64_32v8", _ => "unknown" },
+        CPU_TYPE_POWERPC | CPU_TYPE_POWERPC64 => match subtype { 0 => "ppc (all)", 10 => "ppc7400", 11 => "ppc7450", 100 => "ppc970", _ => "unknown" },
+        _ => "unknown",
+    };
+    info.push(("subtype", format!("{} ({})", name, subtype)));
+    match cputype {
+        CPU_TYPE_ARM64 if subtype == 2 => {
+            // arm64e keeps its pointer authentication ABI version in the capability bits
+            if caps & CPU_SUBTYPE_PTRAUTH_ABI != 0 {
+                info.push(("ptrauth ABI", format!("version {}", (caps >> 24) & 0xf)));
+            } else {
+                info.push(("ptrauth ABI", "unversioned".to_owned()));
+            }
+        },
+        CPU_TYPE_X86_64 | CPU_TYPE_POWERPC64 if caps & CPU_SUBTYPE_LIB64 != 0 => {
+            info.push(("capabilities", "LIB64".to_owned()));
+            if caps & !CPU_SUBTYPE_LIB64 != 0 {
+                info.push(("unknown", format!("{:#x}", caps & !CPU_SUBTYPE_LIB64)));
+            }
+        },
+        _ if caps != 0 => info.push(("capabilities", format!("{:#x}", caps))),
+        _ => (),
+    }
+    info
+}
@@ -0,0 +1,55 @@
+//! LLVM bitcode, as shipped for LTO or embedded with `-fembed-bitcode`.
+//!
+//! Bitcode is either raw, starting with `BC\xC0\xDE`, or in a wrapper that Apple's toolchain adds, which gives the
+//! offset and size of the raw bitcode and the cpu it was built for. Only its presence and size are reported.
+
+use goblin::error;
+use scroll::{self, Pread};
+
+/// The start of raw bitcode
+pub const RAW_MAGIC: &[u8] = b"BC\xc0\xde";
+/// The start of a bitcode wrapper, `0x0B17C0DE` little endian
+pub const WRAPPER_MAGIC: &[u8] = b"\xde\xc0\x17\x0b";
+
+/// The ELF section clang embeds bitcode in
+pub const ELF_SECTION: &str = ".llvmbc";
+/// The Mach-o segment clang embeds bitcode in
+pub const MACH_SEGMENT: &str = "__LLVM";
+/// The bitcode sections of `MACH_SEGMENT`: `__bitcode` in objects and a xar `__bundle` in linked images
+pub const MACH_SECTIONS: [&str; 2] = ["__bitcode", "__bundle"];
+
+#[derive(Debug)]
+pub struct Wrapper {
+    pub version: u32,
+    /// The file offset of the raw bitcode
+    pub offset: u32,
+    pub size: u32,
+    pub cputype: u32,
+}
+
+#[derive(Debug)]
+pub struct Bitcode {
+    /// The wrapper, if the raw bitcode is in one
+    pub wrapper: Option<Wrapper>,
+    pub size: usize,
+}
+
+pub fn is_bitcode(bytes: &[u8]) -> bool {
+    bytes.starts_with(RAW_MAGIC) || bytes.starts_with(WRAPPER_MAGIC)
+}
+
+pub fn parse(bytes: &[u8]) -> error::Result<Bitcode> {
+    if bytes.starts_with(RAW_MAGIC) {
+        return Ok(Bitcode { wrapper: None, size: bytes.len() });
+    }
+    let wrapper = Wrapper {
+        version: bytes.pread_with(4, scroll::LE)?,
+        offset: bytes.pread_with(8, scroll::LE)?,
+        size: bytes.pread_with(12, scroll::LE)?,
+        cputype: bytes.pread_with(16, scroll::LE)?,
+    };
+    if (wrapper.offset as usize).saturating_add(wrapper.size as usize) > bytes.len() {
+        return Err(error::Error::Malformed(format!("the wrapped bitcode at {:#x} of size {:#x} is past the end of the file", wrapper.offset, wrapper.size)));
+    }
+    Ok(Bitcode { size: wrapper.size as usize, wrapper: Some(wrapper) })
+}
@@ -0,0 +1,177 @@
+//! The CLR (COR20) header of managed PE binaries, i.e., .NET assemblies.
+//!
+//! Only the header level is parsed: the COR20 header, the metadata root, and its stream directory.
+
+use goblin::error;
+use goblin::pe;
+use scroll::{self, Pread, Gread};
+
+/// The metadata root signature, `BSJB`
+pub const METADATA_SIGNATURE: u32 = 0x424a5342;
+
+pub const COMIMAGE_FLAGS_ILONLY: u32 = 0x1;
+pub const COMIMAGE_FLAGS_32BITREQUIRED: u32 = 0x2;
+pub const COMIMAGE_FLAGS_IL_LIBRARY: u32 = 0x4;
+pub const COMIMAGE_FLAGS_STRONGNAMESIGNED: u32 = 0x8;
+pub const COMIMAGE_FLAGS_NATIVE_ENTRYPOINT: u32 = 0x10;
+pub const COMIMAGE_FLAGS_TRACKDEBUGDATA: u32 = 0x10000;
+pub const COMIMAGE_FLAGS_32BITPREFERRED: u32 = 0x20000;
+
+pub const COMIMAGE_FLAGS: [u32; 7] = [
+    COMIMAGE_FLAGS_ILONLY,
+    COMIMAGE_FLAGS_32BITREQUIRED,
+    COMIMAGE_FLAGS_IL_LIBRARY,
+    COMIMAGE_FLAGS_STRONGNAMESIGNED,
+    COMIMAGE_FLAGS_NATIVE_ENTRYPOINT,
+    COMIMAGE_FLAGS_TRACKDEBUGDATA,
+    COMIMAGE_FLAGS_32BITPREFERRED,
+];
+
+pub fn flag_to_str(flag: u32) -> &'static str {
+    match flag {
+        COMIMAGE_FLAGS_ILONLY => "ILONLY",
+        COMIMAGE_FLAGS_32BITREQUIRED => "32BITREQUIRED",
+        COMIMAGE_FLAGS_IL_LIBRARY => "IL_LIBRARY",
+        COMIMAGE_FLAGS_STRONGNAMESIGNED => "STRONGNAMESIGNED",
+        COMIMAGE_FLAGS_NATIVE_ENTRYPOINT => "NATIVE_ENTRYPOINT",
+        COMIMAGE_FLAGS_TRACKDEBUGDATA => "TRACKDEBUGDATA",
+        COMIMAGE_FLAGS_32BITPREFERRED => "32BITPREFERRED",
+        _ => "UNKNOWN_FLAG",
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Directory {
+    pub rva: u32,
+    pub size: u32,
+}
+
+#[derive(Debug)]
+pub struct Cor20Header {
+    /// The size of the header
+    pub cb: u32,
+    pub major_runtime_version: u16,
+    pub minor_runtime_version: u16,
+    pub metadata: Directory,
+    pub flags: u32,
+    /// A metadata token, or an RVA if the entry point is native
+    pub entry_point: u32,
+    pub resources: Directory,
+    pub strong_name_signature: Directory,
+    pub code_manager_table: Directory,
+    pub vtable_fixups: Directory,
+    pub export_address_table_jumps: Directory,
+    pub managed_native_header: Directory,
+}
+
+#[derive(Debug)]
+pub struct Stream<'a> {
+    /// Offset from the metadata root
+    pub offset: u32,
+    pub size: u32,
+    pub name: &'a str,
+}
+
+#[derive(Debug)]
+pub struct Metadata<'a> {
+    pub major_version: u16,
+    pub minor_version: u16,
+    /// The runtime the assembly was built against, e.g. `v4.0.30319`
+    pub version: &'a str,
+    pub streams: Vec<Stream<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Clr<'a> {
+    pub header: Cor20Header,
+    pub metadata: error::Result<Metadata<'a>>,
+}
+
+/// The file offset of `rva`, if it is inside one of the PE's sections
+pub fn find_offset(rva: u32, sections: &[pe::section_table::SectionTable]) -> Option<usize> {
+    sections.iter().find(|section| {
+        let size = ::std::cmp::max(section.virtual_size, section.size_of_raw_data);
+        section.virtual_address <= rva && (rva as u64) < section.virtual_address as u64 + size as u64
+    }).map(|section| (rva - section.virtual_address) as usize + section.pointer_to_raw_data as usize)
+}
+
+fn directory(bytes: &[u8], offset: &mut usize) -> error::Result<Directory> {
+    Ok(Directory {
+        rva: bytes.gread_with(offset, scroll::LE)?,
+        size: bytes.gread_with(offset, scroll::LE)?,
+    })
+}
+
+fn parse_header(bytes: &[u8], mut offset: usize) -> error::Result<Cor20Header> {
+    let offset = &mut offset;
+    Ok(Cor20Header {
+        cb: bytes.gread_with(offset, scroll::LE)?,
+        major_runtime_version: bytes.gread_with(offset, scroll::LE)?,
+        minor_runtime_version: bytes.gread_with(offset, scroll::LE)?,
+        metadata: directory(bytes, offset)?,
+        flags: bytes.gread_with(offset, scroll::LE)?,
+        entry_point: bytes.gread_with(offset, scroll::LE)?,
+        resources: directory(bytes, offset)?,
+        strong_name_signature: directory(bytes, offset)?,
+        code_manager_table: directory(bytes, offset)?,
+        vtable_fixups: directory(bytes, offset)?,
+        export_address_table_jumps: directory(bytes, offset)?,
+        managed_native_header: directory(bytes, offset)?,
+    })
+}
+
+/// Rounds `n` up to the next multiple of 4
+fn align4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn parse_metadata<'a>(bytes: &'a [u8], root: usize) -> error::Result<Metadata<'a>> {
+    let signature = bytes.pread_with::<u32>(root, scroll::LE)?;
+    if signature != METADATA_SIGNATURE {
+        return Err(error::Error::BadMagic(signature as u64));
+    }
+    let major_version = bytes.pread_with(root + 4, scroll::LE)?;
+    let minor_version = bytes.pread_with(root + 6, scroll::LE)?;
+    let length = bytes.pread_with::<u32>(root + 12, scroll::LE)? as usize;
+    // the version is NUL padded to `length`
+    let version = bytes.pread::<&str>(root + 16)?;
+    let version = &version[..::std::cmp::min(version.len(), length)];
+    let mut offset = root + 16 + align4(length);
+    // skip the flags
+    offset += 2;
+    let nstreams = bytes.gread_with::<u16>(&mut offset, scroll::LE)?;
+    let mut streams = Vec::with_capacity(nstreams as usize);
+    for _ in 0..nstreams {
+        let stream_offset = bytes.gread_with(&mut offset, scroll::LE)?;
+        let size = bytes.gread_with(&mut offset, scroll::LE)?;
+        let name = bytes.pread::<&str>(offset)?;
+        offset += align4(name.len() + 1);
+        streams.push(Stream { offset: stream_offset, size, name });
+    }
+    Ok(Metadata {
+        major_version,
+        minor_version,
+        version,
+        streams,
+    })
+}
+
+/// Parses the CLR header, if the PE has a CLR runtime header data directory
+pub fn parse<'a>(bytes: &'a [u8], pe: &pe::PE) -> error::Result<Option<Clr<'a>>> {
+    let directory = match pe.header.optional_header {
+        Some(ref optional_header) => match *optional_header.data_directories.get_clr_runtime_header() {
+            Some(directory) => directory,
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+    let offset = find_offset(directory.virtual_address, &pe.sections)
+        .ok_or_else(|| error::Error::Malformed(format!("CLR header rva {:#x} is not in any section", directory.virtual_address)))?;
+    let header = parse_header(bytes, offset)?;
+    // a bad metadata root shouldn't prevent showing the header
+    let metadata = match find_offset(header.metadata.rva, &pe.sections) {
+        Some(root) => parse_metadata(bytes, root),
+        None => Err(error::Error::Malformed(format!("metadata rva {:#x} is not in any section", header.metadata.rva))),
+    };
+    Ok(Some(Clr { header, metadata }))
+}
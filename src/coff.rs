@@ -0,0 +1,316 @@
+//! Standalone COFF object files, the `.obj` files the Windows toolchains produce.
+//!
+//! These have no DOS stub and no optional header, so goblin's PE parser can't read them; the file header is at
+//! offset 0, followed by the section table, with the symbol table and its string table wherever the header points.
+
+use goblin::error;
+use scroll::{self, Pread, Gread};
+
+pub const IMAGE_FILE_MACHINE_I386: u16 = 0x14c;
+pub const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+pub const IMAGE_FILE_MACHINE_ARM: u16 = 0x1c0;
+pub const IMAGE_FILE_MACHINE_ARMNT: u16 = 0x1c4;
+pub const IMAGE_FILE_MACHINE_ARM64: u16 = 0xaa64;
+
+const SIZEOF_FILE_HEADER: usize = 20;
+const SIZEOF_SECTION: usize = 40;
+const SIZEOF_SYMBOL: usize = 18;
+const SIZEOF_RELOCATION: usize = 10;
+
+pub const IMAGE_SYM_UNDEFINED: i16 = 0;
+pub const IMAGE_SYM_ABSOLUTE: i16 = -1;
+pub const IMAGE_SYM_DEBUG: i16 = -2;
+
+pub const IMAGE_SYM_CLASS_EXTERNAL: u8 = 2;
+pub const IMAGE_SYM_CLASS_STATIC: u8 = 3;
+pub const IMAGE_SYM_CLASS_LABEL: u8 = 6;
+pub const IMAGE_SYM_CLASS_FUNCTION: u8 = 101;
+pub const IMAGE_SYM_CLASS_FILE: u8 = 103;
+pub const IMAGE_SYM_CLASS_SECTION: u8 = 104;
+pub const IMAGE_SYM_CLASS_WEAK_EXTERNAL: u8 = 105;
+
+pub const IMAGE_SCN_CNT_CODE: u32 = 0x0000_0020;
+pub const IMAGE_SCN_CNT_INITIALIZED_DATA: u32 = 0x0000_0040;
+pub const IMAGE_SCN_CNT_UNINITIALIZED_DATA: u32 = 0x0000_0080;
+pub const IMAGE_SCN_LNK_INFO: u32 = 0x0000_0200;
+pub const IMAGE_SCN_LNK_REMOVE: u32 = 0x0000_0800;
+pub const IMAGE_SCN_LNK_COMDAT: u32 = 0x0000_1000;
+pub const IMAGE_SCN_ALIGN_MASK: u32 = 0x00f0_0000;
+pub const IMAGE_SCN_LNK_NRELOC_OVFL: u32 = 0x0100_0000;
+pub const IMAGE_SCN_MEM_DISCARDABLE: u32 = 0x0200_0000;
+pub const IMAGE_SCN_MEM_SHARED: u32 = 0x1000_0000;
+pub const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+pub const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+pub const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
+
+/// The section flags worth showing, with their short names
+pub const IMAGE_SCN_FLAGS: [(u32, &str); 12] = [
+    (IMAGE_SCN_CNT_CODE, "CODE"),
+    (IMAGE_SCN_CNT_INITIALIZED_DATA, "DATA"),
+    (IMAGE_SCN_CNT_UNINITIALIZED_DATA, "BSS"),
+    (IMAGE_SCN_LNK_INFO, "INFO"),
+    (IMAGE_SCN_LNK_REMOVE, "REMOVE"),
+    (IMAGE_SCN_LNK_COMDAT, "COMDAT"),
+    (IMAGE_SCN_LNK_NRELOC_OVFL, "NRELOC_OVFL"),
+    (IMAGE_SCN_MEM_DISCARDABLE, "DISCARDABLE"),
+    (IMAGE_SCN_MEM_SHARED, "SHARED"),
+    (IMAGE_SCN_MEM_EXECUTE, "EXECUTE"),
+    (IMAGE_SCN_MEM_READ, "READ"),
+    (IMAGE_SCN_MEM_WRITE, "WRITE"),
+];
+
+pub fn machine_to_str(machine: u16) -> &'static str {
+    match machine {
+        IMAGE_FILE_MACHINE_I386 => "i386",
+        IMAGE_FILE_MACHINE_AMD64 => "x86-64",
+        IMAGE_FILE_MACHINE_ARM => "ARM",
+        IMAGE_FILE_MACHINE_ARMNT => "ARM Thumb-2",
+        IMAGE_FILE_MACHINE_ARM64 => "ARM64",
+        _ => "UNKNOWN",
+    }
+}
+
+pub fn storage_class_to_str(class: u8) -> &'static str {
+    match class {
+        IMAGE_SYM_CLASS_EXTERNAL => "EXTERNAL",
+        IMAGE_SYM_CLASS_STATIC => "STATIC",
+        IMAGE_SYM_CLASS_LABEL => "LABEL",
+        IMAGE_SYM_CLASS_FUNCTION => "FUNCTION",
+        IMAGE_SYM_CLASS_FILE => "FILE",
+        IMAGE_SYM_CLASS_SECTION => "SECTION",
+        IMAGE_SYM_CLASS_WEAK_EXTERNAL => "WEAK_EXTERNAL",
+        _ => "UNKNOWN",
+    }
+}
+
+/// The name of relocation type `typ`, which depends on the machine
+pub fn reloc_to_str(machine: u16, typ: u16) -> &'static str {
+    match machine {
+        IMAGE_FILE_MACHINE_AMD64 => match typ {
+            0x0 => "ABSOLUTE",
+            0x1 => "ADDR64",
+            0x2 => "ADDR32",
+            0x3 => "ADDR32NB",
+            0x4 => "REL32",
+            0x5 => "REL32_1",
+            0x6 => "REL32_2",
+            0x7 => "REL32_3",
+            0x8 => "REL32_4",
+            0x9 => "REL32_5",
+            0xa => "SECTION",
+            0xb => "SECREL",
+            0xc => "SECREL7",
+            0xd => "TOKEN",
+            0xe => "SREL32",
+            0xf => "PAIR",
+            0x10 => "SSPAN32",
+            _ => "UNKNOWN",
+        },
+        IMAGE_FILE_MACHINE_I386 => match typ {
+            0x0 => "ABSOLUTE",
+            0x1 => "DIR16",
+            0x2 => "REL16",
+            0x6 => "DIR32",
+            0x7 => "DIR32NB",
+            0x9 => "SEG12",
+            0xa => "SECTION",
+            0xb => "SECREL",
+            0xc => "TOKEN",
+            0xd => "SECREL7",
+            0x14 => "REL32",
+            _ => "UNKNOWN",
+        },
+        IMAGE_FILE_MACHINE_ARM64 => match typ {
+            0x0 => "ABSOLUTE",
+            0x1 => "ADDR32",
+            0x2 => "ADDR32NB",
+            0x3 => "BRANCH26",
+            0x4 => "PAGEBASE_REL21",
+            0x5 => "REL21",
+            0x6 => "PAGEOFFSET_12A",
+            0x7 => "PAGEOFFSET_12L",
+            0x8 => "SECREL",
+            0x9 => "SECREL_LOW12A",
+            0xa => "SECREL_HIGH12A",
+            0xb => "SECREL_LOW12L",
+            0xc => "TOKEN",
+            0xd => "SECTION",
+            0xe => "ADDR64",
+            0xf => "BRANCH19",
+            0x10 => "BRANCH14",
+            0x11 => "REL32",
+            _ => "UNKNOWN",
+        },
+        _ => "UNKNOWN",
+    }
+}
+
+#[derive(Debug)]
+pub struct FileHeader {
+    pub machine: u16,
+    pub number_of_sections: u16,
+    pub time_date_stamp: u32,
+    pub pointer_to_symbol_table: u32,
+    pub number_of_symbols: u32,
+    pub size_of_optional_header: u16,
+    pub characteristics: u16,
+}
+
+#[derive(Debug)]
+pub struct Relocation {
+    pub virtual_address: u32,
+    /// The index into the symbol table, which counts auxiliary records
+    pub symbol: u32,
+    pub typ: u16,
+}
+
+#[derive(Debug)]
+pub struct Section<'a> {
+    pub name: &'a str,
+    pub size_of_raw_data: u32,
+    pub pointer_to_raw_data: u32,
+    pub characteristics: u32,
+    pub relocations: Vec<Relocation>,
+}
+
+impl<'a> Section<'a> {
+    /// The alignment encoded in the characteristics, or 0 if there is none
+    pub fn alignment(&self) -> u32 {
+        match (self.characteristics & IMAGE_SCN_ALIGN_MASK) >> 20 {
+            0 => 0,
+            n => 1 << (n - 1),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Symbol<'a> {
+    /// The index of the symbol's record in the symbol table
+    pub index: usize,
+    pub name: &'a str,
+    pub value: u32,
+    /// 1-based, or one of the `IMAGE_SYM_*` special values
+    pub section_number: i16,
+    pub typ: u16,
+    pub storage_class: u8,
+}
+
+impl<'a> Symbol<'a> {
+    pub fn is_function(&self) -> bool {
+        // the complex type is in the high nibble of the low byte; 2 is "function returning the base type"
+        (self.typ >> 4) & 0xf == 2
+    }
+}
+
+#[derive(Debug)]
+pub struct Coff<'a> {
+    pub header: FileHeader,
+    pub sections: Vec<Section<'a>>,
+    /// The symbols, without their auxiliary records
+    pub symbols: Vec<Symbol<'a>>,
+}
+
+impl<'a> Coff<'a> {
+    /// The symbol whose record is at `index`, as referred to by relocations
+    pub fn symbol(&self, index: u32) -> Option<&Symbol<'a>> {
+        self.symbols.binary_search_by_key(&(index as usize), |sym| sym.index).ok().map(|i| &self.symbols[i])
+    }
+
+    /// The name of the 1-based section `number`, or of the special section it stands for
+    pub fn section_name(&self, number: i16) -> &'a str {
+        match number {
+            IMAGE_SYM_UNDEFINED => "UNDEF",
+            IMAGE_SYM_ABSOLUTE => "ABS",
+            IMAGE_SYM_DEBUG => "DEBUG",
+            _ => self.sections.get(number as usize - 1).map(|section| section.name).unwrap_or("<bad index>"),
+        }
+    }
+}
+
+/// Whether `bytes` looks like a COFF object for one of the machines bingrep knows: objects have no optional header
+pub fn is_coff(bytes: &[u8]) -> bool {
+    match (bytes.pread_with::<u16>(0, scroll::LE), bytes.pread_with::<u16>(16, scroll::LE)) {
+        (Ok(machine), Ok(0)) => machine_to_str(machine) != "UNKNOWN",
+        _ => false,
+    }
+}
+
+/// The name in an 8 byte name field: either inline and NUL padded, or `/offset` (for sections) or four zeros
+/// then an offset (for symbols) into the string table
+fn name(bytes: &[u8], field: usize, strtab: usize, is_section: bool) -> error::Result<&str> {
+    let raw = bytes.get(field..field + 8).ok_or_else(|| error::Error::Malformed(format!("name at {:#x} is past the end of the file", field)))?;
+    let offset = if is_section && raw[0] == b'/' {
+        ::std::str::from_utf8(&raw[1..]).ok().and_then(|offset| offset.trim_end_matches('\0').parse::<usize>().ok())
+    } else if !is_section && raw[..4] == [0, 0, 0, 0] {
+        Some(bytes.pread_with::<u32>(field + 4, scroll::LE)? as usize)
+    } else {
+        None
+    };
+    match offset {
+        Some(offset) => Ok(bytes.pread(strtab + offset).unwrap_or("<bad index>")),
+        None => {
+            let end = raw.iter().position(|&c| c == 0).unwrap_or(raw.len());
+            Ok(::std::str::from_utf8(&raw[..end]).unwrap_or("<bad name>"))
+        },
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> error::Result<Coff<'_>> {
+    let mut offset = 0;
+    let header = FileHeader {
+        machine: bytes.gread_with(&mut offset, scroll::LE)?,
+        number_of_sections: bytes.gread_with(&mut offset, scroll::LE)?,
+        time_date_stamp: bytes.gread_with(&mut offset, scroll::LE)?,
+        pointer_to_symbol_table: bytes.gread_with(&mut offset, scroll::LE)?,
+        number_of_symbols: bytes.gread_with(&mut offset, scroll::LE)?,
+        size_of_optional_header: bytes.gread_with(&mut offset, scroll::LE)?,
+        characteristics: bytes.gread_with(&mut offset, scroll::LE)?,
+    };
+    let symoff = header.pointer_to_symbol_table as usize;
+    let nsyms = header.number_of_symbols as usize;
+    if nsyms.checked_mul(SIZEOF_SYMBOL).and_then(|size| size.checked_add(symoff)).is_none_or(|end| end > bytes.len()) {
+        return Err(error::Error::Malformed(format!("the symbol table ({} symbols at {:#x}) is past the end of the file", nsyms, symoff)));
+    }
+    // the string table follows the symbols
+    let strtab = symoff + nsyms * SIZEOF_SYMBOL;
+
+    let mut sections = Vec::with_capacity(header.number_of_sections as usize);
+    for i in 0..header.number_of_sections as usize {
+        let section = SIZEOF_FILE_HEADER + header.size_of_optional_header as usize + i * SIZEOF_SECTION;
+        let pointer_to_relocations = bytes.pread_with::<u32>(section + 24, scroll::LE)? as usize;
+        let number_of_relocations = bytes.pread_with::<u16>(section + 32, scroll::LE)? as usize;
+        let mut relocations = Vec::new();
+        for r in 0..number_of_relocations {
+            let reloc = pointer_to_relocations + r * SIZEOF_RELOCATION;
+            relocations.push(Relocation {
+                virtual_address: bytes.pread_with(reloc, scroll::LE)?,
+                symbol: bytes.pread_with(reloc + 4, scroll::LE)?,
+                typ: bytes.pread_with(reloc + 8, scroll::LE)?,
+            });
+        }
+        sections.push(Section {
+            name: name(bytes, section, strtab, true)?,
+            size_of_raw_data: bytes.pread_with(section + 16, scroll::LE)?,
+            pointer_to_raw_data: bytes.pread_with(section + 20, scroll::LE)?,
+            characteristics: bytes.pread_with(section + 36, scroll::LE)?,
+            relocations,
+        });
+    }
+
+    let mut symbols = Vec::new();
+    let mut index = 0;
+    while index < nsyms {
+        let symbol = symoff + index * SIZEOF_SYMBOL;
+        let number_of_aux_symbols: u8 = bytes.pread(symbol + 17)?;
+        symbols.push(Symbol {
+            index,
+            name: name(bytes, symbol, strtab, false)?,
+            value: bytes.pread_with(symbol + 8, scroll::LE)?,
+            section_number: bytes.pread_with(symbol + 12, scroll::LE)?,
+            typ: bytes.pread_with(symbol + 14, scroll::LE)?,
+            storage_class: bytes.pread(symbol + 16)?,
+        });
+        index += 1 + number_of_aux_symbols as usize;
+    }
+    Ok(Coff { header, sections, symbols })
+}
@@ -0,0 +1,88 @@
+//! Support for `SHF_COMPRESSED` ELF sections, e.g. `.debug_*` sections in binaries built with `-gz`.
+//!
+//! The section contents start with an `Elf32_Chdr`/`Elf64_Chdr` describing the compression,
+//! followed by the compressed stream.
+
+use goblin::error;
+use scroll::{self, Pread};
+
+pub const ELFCOMPRESS_ZLIB: u32 = 1;
+pub const ELFCOMPRESS_ZSTD: u32 = 2;
+
+#[cfg(feature = "compression")]
+pub const SIZEOF_CHDR32: usize = 12;
+#[cfg(feature = "compression")]
+pub const SIZEOF_CHDR64: usize = 24;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Chdr {
+    pub ch_type: u32,
+    /// The size of the uncompressed data
+    pub ch_size: u64,
+    pub ch_addralign: u64,
+}
+
+pub fn type_to_str(ch_type: u32) -> &'static str {
+    match ch_type {
+        ELFCOMPRESS_ZLIB => "zlib",
+        ELFCOMPRESS_ZSTD => "zstd",
+        _ => "unknown",
+    }
+}
+
+impl Chdr {
+    /// Parses the compression header at the start of the section contents `bytes`
+    pub fn parse(bytes: &[u8], is_64: bool, little_endian: bool) -> error::Result<Chdr> {
+        let le = scroll::Endian::from(little_endian);
+        if is_64 {
+            // the 64-bit header has a reserved word after the type
+            Ok(Chdr {
+                ch_type: bytes.pread_with(0, le)?,
+                ch_size: bytes.pread_with(8, le)?,
+                ch_addralign: bytes.pread_with(16, le)?,
+            })
+        } else {
+            Ok(Chdr {
+                ch_type: bytes.pread_with(0, le)?,
+                ch_size: bytes.pread_with::<u32>(4, le)? as u64,
+                ch_addralign: bytes.pread_with::<u32>(8, le)? as u64,
+            })
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn size(is_64: bool) -> usize {
+        if is_64 { SIZEOF_CHDR64 } else { SIZEOF_CHDR32 }
+    }
+}
+
+/// Decompresses the section contents `bytes`, including the compression header
+#[cfg(feature = "compression")]
+pub fn decompress(bytes: &[u8], is_64: bool, little_endian: bool) -> error::Result<Vec<u8>> {
+    use std::io::Read;
+    let chdr = Chdr::parse(bytes, is_64, little_endian)?;
+    let stream = &bytes[Chdr::size(is_64)..];
+    // don't trust ch_size for the allocation, a corrupt header could claim anything
+    let mut data = Vec::with_capacity(::std::cmp::min(chdr.ch_size, (stream.len() as u64).saturating_mul(16)) as usize);
+    match chdr.ch_type {
+        ELFCOMPRESS_ZLIB => {
+            ::flate2::read::ZlibDecoder::new(stream).read_to_end(&mut data)?;
+        },
+        ELFCOMPRESS_ZSTD => {
+            let mut stream = stream;
+            let mut decoder = ::ruzstd::StreamingDecoder::new(&mut stream)
+                .map_err(|err| error::Error::Malformed(format!("bad zstd stream: {}", err)))?;
+            decoder.read_to_end(&mut data)?;
+        },
+        typ => return Err(error::Error::Malformed(format!("unknown section compression type {:#x}", typ))),
+    }
+    if data.len() as u64 != chdr.ch_size {
+        return Err(error::Error::Malformed(format!("section decompressed to {:#x} bytes, but its header claims {:#x}", data.len(), chdr.ch_size)));
+    }
+    Ok(data)
+}
+
+#[cfg(not(feature = "compression"))]
+pub fn decompress(_bytes: &[u8], _is_64: bool, _little_endian: bool) -> error::Result<Vec<u8>> {
+    Err(error::Error::Malformed("bingrep was built without the `compression` feature".into()))
+}
@@ -0,0 +1,116 @@
+//! Disassembly of flat code blobs, e.g. shellcode or firmware images, which have no header to
+//! say what they contain.
+
+use std::fmt;
+use goblin::error;
+
+/// An architecture given on the command line with `--arch`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Thumb,
+    Aarch64,
+    Mips,
+    Mipsel,
+    Mips64,
+    Ppc,
+    Ppc64,
+    Riscv32,
+    Riscv64,
+}
+
+pub const ARCHES: [(&str, Arch); 12] = [
+    ("x86", Arch::X86),
+    ("x86_64", Arch::X86_64),
+    ("arm", Arch::Arm),
+    ("thumb", Arch::Thumb),
+    ("aarch64", Arch::Aarch64),
+    ("mips", Arch::Mips),
+    ("mipsel", Arch::Mipsel),
+    ("mips64", Arch::Mips64),
+    ("ppc", Arch::Ppc),
+    ("ppc64", Arch::Ppc64),
+    ("riscv32", Arch::Riscv32),
+    ("riscv64", Arch::Riscv64),
+];
+
+impl Arch {
+    /// Whether addresses on this architecture are 64 bits wide
+    pub fn is_64(self) -> bool {
+        matches!(self, Arch::X86_64 | Arch::Aarch64 | Arch::Mips64 | Arch::Ppc64 | Arch::Riscv64)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseArchError(String);
+
+impl fmt::Display for ParseArchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let names = ARCHES.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+        write!(fmt, "unknown architecture `{}`, expected one of: {}", self.0, names.join(", "))
+    }
+}
+
+impl ::std::error::Error for ParseArchError {
+    fn description(&self) -> &str {
+        "unknown architecture"
+    }
+}
+
+impl ::std::str::FromStr for Arch {
+    type Err = ParseArchError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ARCHES.iter().find(|&&(name, _)| name == s.to_lowercase()).map(|&(_, arch)| arch).ok_or_else(|| ParseArchError(s.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Insn {
+    pub address: u64,
+    pub bytes: Vec<u8>,
+    pub mnemonic: String,
+    pub operands: String,
+}
+
+#[cfg(feature = "disasm")]
+fn capstone(arch: Arch) -> ::capstone::CsResult<::capstone::Capstone> {
+    use capstone::prelude::*;
+    use capstone::Endian;
+    match arch {
+        Arch::X86 => Capstone::new().x86().mode(arch::x86::ArchMode::Mode32).build(),
+        Arch::X86_64 => Capstone::new().x86().mode(arch::x86::ArchMode::Mode64).build(),
+        Arch::Arm => Capstone::new().arm().mode(arch::arm::ArchMode::Arm).build(),
+        Arch::Thumb => Capstone::new().arm().mode(arch::arm::ArchMode::Thumb).build(),
+        Arch::Aarch64 => Capstone::new().arm64().mode(arch::arm64::ArchMode::Arm).build(),
+        Arch::Mips => Capstone::new().mips().mode(arch::mips::ArchMode::Mips32).endian(Endian::Big).build(),
+        Arch::Mipsel => Capstone::new().mips().mode(arch::mips::ArchMode::Mips32).endian(Endian::Little).build(),
+        Arch::Mips64 => Capstone::new().mips().mode(arch::mips::ArchMode::Mips64).endian(Endian::Big).build(),
+        Arch::Ppc => Capstone::new().ppc().mode(arch::ppc::ArchMode::Mode32).endian(Endian::Big).build(),
+        Arch::Ppc64 => Capstone::new().ppc().mode(arch::ppc::ArchMode::Mode64).endian(Endian::Big).build(),
+        Arch::Riscv32 => Capstone::new().riscv().mode(arch::riscv::ArchMode::RiscV32).extra_mode(Some(arch::riscv::ArchExtraMode::RiscVC).into_iter()).build(),
+        Arch::Riscv64 => Capstone::new().riscv().mode(arch::riscv::ArchMode::RiscV64).extra_mode(Some(arch::riscv::ArchExtraMode::RiscVC).into_iter()).build(),
+    }
+}
+
+/// Disassembles all of `bytes` as if loaded at `base`; bytes that don't decode are emitted as `.byte` data
+#[cfg(feature = "disasm")]
+pub fn disassemble(bytes: &[u8], arch: Arch, base: u64) -> error::Result<Vec<Insn>> {
+    let capstone_error = |err: ::capstone::Error| error::Error::Malformed(format!("capstone: {}", err));
+    let mut cs = capstone(arch).map_err(&capstone_error)?;
+    // keep going past data mixed into the code instead of stopping at the first bad instruction
+    cs.set_skipdata(true).map_err(&capstone_error)?;
+    let insns = cs.disasm_all(bytes, base).map_err(&capstone_error)?;
+    Ok(insns.iter().map(|insn| Insn {
+        address: insn.address(),
+        bytes: insn.bytes().to_vec(),
+        mnemonic: insn.mnemonic().unwrap_or("").to_owned(),
+        operands: insn.op_str().unwrap_or("").to_owned(),
+    }).collect())
+}
+
+#[cfg(not(feature = "disasm"))]
+pub fn disassemble(_bytes: &[u8], _arch: Arch, _base: u64) -> error::Result<Vec<Insn>> {
+    Err(error::Error::Malformed("bingrep was built without the `disasm` feature".into()))
+}
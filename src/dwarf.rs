@@ -0,0 +1,55 @@
+//! A summary of DWARF debug info: how many units there are, and which DWARF versions they are.
+//!
+//! Only the unit headers in `.debug_info` and `.debug_types` are read, which is enough to tell whether a binary has
+//! debug info for all of its code or just some objects, without parsing any DIEs.
+
+use goblin::error;
+
+/// Sections with these prefixes hold DWARF; `.zdebug_` is the old GNU compressed form
+pub const SECTION_PREFIXES: [&str; 2] = [".debug_", ".zdebug_"];
+
+#[derive(Debug, Default)]
+pub struct Units {
+    /// Compilation units, including split and partial ones
+    pub compile: usize,
+    /// Type units, from `.debug_types` or DWARF 5 `.debug_info`
+    pub types: usize,
+    /// The DWARF versions of the units, sorted and without duplicates
+    pub versions: Vec<u16>,
+}
+
+pub fn is_dwarf_section(name: &str) -> bool {
+    SECTION_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Counts the units of the `.debug_info` section `debug_info`, and of the `.debug_types` section `debug_types` if there is one
+#[cfg(feature = "dwarf")]
+pub fn units(debug_info: &[u8], debug_types: Option<&[u8]>, little_endian: bool) -> error::Result<Units> {
+    use gimli::{DebugInfo, DebugTypes, RunTimeEndian, UnitType};
+    let dwarf_error = |err: gimli::Error| error::Error::Malformed(format!("DWARF: {}", err));
+    let endian = if little_endian { RunTimeEndian::Little } else { RunTimeEndian::Big };
+    let mut units = Units::default();
+    let mut headers = DebugInfo::new(debug_info, endian).units();
+    while let Some(header) = headers.next().map_err(&dwarf_error)? {
+        match header.type_() {
+            UnitType::Type { .. } | UnitType::SplitType { .. } => units.types += 1,
+            _ => units.compile += 1,
+        }
+        units.versions.push(header.version());
+    }
+    if let Some(debug_types) = debug_types {
+        let mut headers = DebugTypes::new(debug_types, endian).units();
+        while let Some(header) = headers.next().map_err(&dwarf_error)? {
+            units.types += 1;
+            units.versions.push(header.version());
+        }
+    }
+    units.versions.sort();
+    units.versions.dedup();
+    Ok(units)
+}
+
+#[cfg(not(feature = "dwarf"))]
+pub fn units(_debug_info: &[u8], _debug_types: Option<&[u8]>, _little_endian: bool) -> error::Result<Units> {
+    Err(error::Error::Malformed("bingrep was built without the `dwarf` feature".into()))
+}
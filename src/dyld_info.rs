@@ -0,0 +1,170 @@
+//! The rebase and bind opcodes of Mach-o `LC_DYLD_INFO`: the small programs dyld runs to slide and bind the pointers
+//! of an image.
+//!
+//! Each opcode is one byte, the operation in its high nibble and an immediate in its low one, and some are followed
+//! by ULEB128 or SLEB128 operands, or by a symbol name.
+
+use goblin::error;
+use goblin::mach;
+use goblin::mach::bind_opcodes::*;
+use goblin::mach::load_command::{CommandVariant, DyldInfoCommand};
+use scroll::{Pread, Gread, Uleb128, Sleb128};
+
+pub const REBASE_TYPE_POINTER: u8 = 1;
+pub const REBASE_TYPE_TEXT_ABSOLUTE32: u8 = 2;
+pub const REBASE_TYPE_TEXT_PCREL32: u8 = 3;
+pub const REBASE_OPCODE_MASK: u8 = 0xf0;
+pub const REBASE_IMMEDIATE_MASK: u8 = 0x0f;
+pub const REBASE_OPCODE_DONE: u8 = 0x00;
+pub const REBASE_OPCODE_SET_TYPE_IMM: u8 = 0x10;
+pub const REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB: u8 = 0x20;
+pub const REBASE_OPCODE_ADD_ADDR_ULEB: u8 = 0x30;
+pub const REBASE_OPCODE_ADD_ADDR_IMM_SCALED: u8 = 0x40;
+pub const REBASE_OPCODE_DO_REBASE_IMM_TIMES: u8 = 0x50;
+pub const REBASE_OPCODE_DO_REBASE_ULEB_TIMES: u8 = 0x60;
+pub const REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB: u8 = 0x70;
+pub const REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB: u8 = 0x80;
+
+/// Chained fixups in the same stream, on arm64e; its immediate is one of the `BIND_SUBOPCODE_THREADED_*`
+pub const BIND_OPCODE_THREADED: u8 = 0xd0;
+pub const BIND_SUBOPCODE_THREADED_SET_BIND_ORDINAL_TABLE_SIZE_ULEB: u8 = 0x00;
+pub const BIND_SUBOPCODE_THREADED_APPLY: u8 = 0x01;
+pub const BIND_SPECIAL_DYLIB_WEAK_LOOKUP: i64 = -3;
+
+/// The name of a `REBASE_TYPE_*` or `BIND_TYPE_*`, which have the same values
+pub fn type_to_str(typ: u8) -> &'static str {
+    match typ {
+        REBASE_TYPE_POINTER => "pointer",
+        REBASE_TYPE_TEXT_ABSOLUTE32 => "text_absolute32",
+        REBASE_TYPE_TEXT_PCREL32 => "text_pcrel32",
+        _ => "unknown",
+    }
+}
+
+/// The name of a dylib ordinal, the 1-based index of a library, or a special lookup if not positive
+pub fn ordinal_to_str(ordinal: i64) -> String {
+    match ordinal {
+        0 => "self".to_owned(),
+        -1 => "main_executable".to_owned(),
+        -2 => "flat_lookup".to_owned(),
+        BIND_SPECIAL_DYLIB_WEAK_LOOKUP => "weak_lookup".to_owned(),
+        _ => ordinal.to_string(),
+    }
+}
+
+#[derive(Debug)]
+pub enum Operand<'a> {
+    /// An offset or a size
+    Number(u64),
+    /// How many times an opcode repeats
+    Count(u64),
+    Addend(i64),
+    /// A dylib ordinal, see `ordinal_to_str`
+    Ordinal(i64),
+    /// The index of a segment
+    Segment(u8),
+    /// A `REBASE_TYPE_*` or `BIND_TYPE_*`
+    Type(u8),
+    /// `BIND_SYMBOL_FLAGS_*`
+    Flags(u8),
+    Symbol(&'a str),
+}
+
+#[derive(Debug)]
+pub struct Opcode<'a> {
+    /// The offset of the opcode in its stream
+    pub offset: usize,
+    pub name: &'static str,
+    pub operands: Vec<Operand<'a>>,
+}
+
+/// The `LC_DYLD_INFO` or `LC_DYLD_INFO_ONLY` command of `mach`, if it has one
+pub fn command(mach: &mach::MachO) -> Option<DyldInfoCommand> {
+    mach.load_commands.iter().filter_map(|lc| match lc.command {
+        CommandVariant::DyldInfo(command) | CommandVariant::DyldInfoOnly(command) => Some(command),
+        _ => None,
+    }).next()
+}
+
+/// The `size` bytes of opcodes at file offset `offset`
+pub fn stream(bytes: &[u8], offset: u32, size: u32) -> error::Result<&[u8]> {
+    bytes.get(offset as usize..(offset as usize).saturating_add(size as usize))
+        .ok_or_else(|| error::Error::Malformed(format!("dyld info opcodes at {:#x} of size {:#x} are past the end of the file", offset, size)))
+}
+
+/// Decodes the rebase opcodes in `data`, up to the first `REBASE_OPCODE_DONE`
+pub fn rebase(data: &[u8]) -> error::Result<Vec<Opcode<'_>>> {
+    let mut opcodes = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let start = offset;
+        let byte = data.gread::<u8>(&mut offset)?;
+        let immediate = byte & REBASE_IMMEDIATE_MASK;
+        let (name, operands) = match byte & REBASE_OPCODE_MASK {
+            REBASE_OPCODE_DONE => ("done", vec![]),
+            REBASE_OPCODE_SET_TYPE_IMM => ("set_type_imm", vec![Operand::Type(immediate)]),
+            REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB => ("set_segment_and_offset_uleb", vec![Operand::Segment(immediate), Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            REBASE_OPCODE_ADD_ADDR_ULEB => ("add_addr_uleb", vec![Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            REBASE_OPCODE_ADD_ADDR_IMM_SCALED => ("add_addr_imm_scaled", vec![Operand::Count(immediate as u64)]),
+            REBASE_OPCODE_DO_REBASE_IMM_TIMES => ("do_rebase_imm_times", vec![Operand::Count(immediate as u64)]),
+            REBASE_OPCODE_DO_REBASE_ULEB_TIMES => ("do_rebase_uleb_times", vec![Operand::Count(Uleb128::read(&data, &mut offset)?)]),
+            REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB => ("do_rebase_add_addr_uleb", vec![Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB => {
+                let count = Uleb128::read(&data, &mut offset)?;
+                ("do_rebase_uleb_times_skipping_uleb", vec![Operand::Count(count), Operand::Number(Uleb128::read(&data, &mut offset)?)])
+            },
+            _ => return Err(error::Error::Malformed(format!("unknown rebase opcode {:#x} at {:#x}", byte, start))),
+        };
+        opcodes.push(Opcode { offset: start, name, operands });
+        if byte & REBASE_OPCODE_MASK == REBASE_OPCODE_DONE {
+            break;
+        }
+    }
+    Ok(opcodes)
+}
+
+/// Decodes the bind opcodes in `data`; the lazy binds end each symbol with a `BIND_OPCODE_DONE`, so they are decoded
+/// to the end, the others up to the first one
+pub fn bind(data: &[u8], lazy: bool) -> error::Result<Vec<Opcode<'_>>> {
+    let mut opcodes = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let start = offset;
+        let byte = data.gread::<u8>(&mut offset)?;
+        let immediate = byte & BIND_IMMEDIATE_MASK;
+        let (name, operands) = match byte & BIND_OPCODE_MASK {
+            BIND_OPCODE_DONE => ("done", vec![]),
+            BIND_OPCODE_SET_DYLIB_ORDINAL_IMM => ("set_dylib_ordinal_imm", vec![Operand::Ordinal(immediate as i64)]),
+            BIND_OPCODE_SET_DYLIB_ORDINAL_ULEB => ("set_dylib_ordinal_uleb", vec![Operand::Ordinal(Uleb128::read(&data, &mut offset)? as i64)]),
+            // the immediate is the ordinal sign extended from 4 bits, 0 is self
+            BIND_OPCODE_SET_DYLIB_SPECIAL_IMM => ("set_dylib_special_imm", vec![Operand::Ordinal(if immediate == 0 { 0 } else { (immediate | BIND_OPCODE_MASK) as i8 as i64 })]),
+            BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM => {
+                let symbol = data.pread::<&str>(offset)?;
+                offset += symbol.len() + 1;
+                ("set_symbol_trailing_flags_imm", vec![Operand::Flags(immediate), Operand::Symbol(symbol)])
+            },
+            BIND_OPCODE_SET_TYPE_IMM => ("set_type_imm", vec![Operand::Type(immediate)]),
+            BIND_OPCODE_SET_ADDEND_SLEB => ("set_addend_sleb", vec![Operand::Addend(Sleb128::read(&data, &mut offset)?)]),
+            BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB => ("set_segment_and_offset_uleb", vec![Operand::Segment(immediate), Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            BIND_OPCODE_ADD_ADDR_ULEB => ("add_addr_uleb", vec![Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            BIND_OPCODE_DO_BIND => ("do_bind", vec![]),
+            BIND_OPCODE_DO_BIND_ADD_ADDR_ULEB => ("do_bind_add_addr_uleb", vec![Operand::Number(Uleb128::read(&data, &mut offset)?)]),
+            BIND_OPCODE_DO_BIND_ADD_ADDR_IMM_SCALED => ("do_bind_add_addr_imm_scaled", vec![Operand::Count(immediate as u64)]),
+            BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB => {
+                let count = Uleb128::read(&data, &mut offset)?;
+                ("do_bind_uleb_times_skipping_uleb", vec![Operand::Count(count), Operand::Number(Uleb128::read(&data, &mut offset)?)])
+            },
+            BIND_OPCODE_THREADED => match immediate {
+                BIND_SUBOPCODE_THREADED_SET_BIND_ORDINAL_TABLE_SIZE_ULEB => ("threaded_set_bind_ordinal_table_size_uleb", vec![Operand::Count(Uleb128::read(&data, &mut offset)?)]),
+                BIND_SUBOPCODE_THREADED_APPLY => ("threaded_apply", vec![]),
+                _ => return Err(error::Error::Malformed(format!("unknown threaded bind subopcode {:#x} at {:#x}", immediate, start))),
+            },
+            _ => return Err(error::Error::Malformed(format!("unknown bind opcode {:#x} at {:#x}", byte, start))),
+        };
+        opcodes.push(Opcode { offset: start, name, operands });
+        if !lazy && byte & BIND_OPCODE_MASK == BIND_OPCODE_DONE {
+            break;
+        }
+    }
+    Ok(opcodes)
+}
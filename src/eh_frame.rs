@@ -0,0 +1,235 @@
+//! `.eh_frame` and `.eh_frame_hdr`, the unwind tables.
+//!
+//! Every function that can be unwound through has an FDE giving its address range, so even a stripped binary
+//! says where its functions are. Pointers in both sections are written in one of the `DW_EH_PE_*` encodings: a
+//! format in the low nibble, and what it is relative to in the high one.
+
+use std::collections::HashMap;
+use goblin::error;
+use scroll::{self, Pread, Gread, Uleb128, Sleb128};
+
+pub const DW_EH_PE_ABSPTR: u8 = 0x00;
+pub const DW_EH_PE_ULEB128: u8 = 0x01;
+pub const DW_EH_PE_UDATA2: u8 = 0x02;
+pub const DW_EH_PE_UDATA4: u8 = 0x03;
+pub const DW_EH_PE_UDATA8: u8 = 0x04;
+pub const DW_EH_PE_SLEB128: u8 = 0x09;
+pub const DW_EH_PE_SDATA2: u8 = 0x0a;
+pub const DW_EH_PE_SDATA4: u8 = 0x0b;
+pub const DW_EH_PE_SDATA8: u8 = 0x0c;
+pub const DW_EH_PE_PCREL: u8 = 0x10;
+pub const DW_EH_PE_TEXTREL: u8 = 0x20;
+pub const DW_EH_PE_DATAREL: u8 = 0x30;
+pub const DW_EH_PE_FUNCREL: u8 = 0x40;
+pub const DW_EH_PE_ALIGNED: u8 = 0x50;
+/// The pointer is the address of the real pointer
+pub const DW_EH_PE_INDIRECT: u8 = 0x80;
+/// There is no pointer
+pub const DW_EH_PE_OMIT: u8 = 0xff;
+
+/// A pointer encoding, e.g. `pcrel|sdata4`
+pub fn encoding_to_str(encoding: u8) -> String {
+    if encoding == DW_EH_PE_OMIT {
+        return "omit".to_owned();
+    }
+    let format = match encoding & 0x0f {
+        DW_EH_PE_ABSPTR => "absptr",
+        DW_EH_PE_ULEB128 => "uleb128",
+        DW_EH_PE_UDATA2 => "udata2",
+        DW_EH_PE_UDATA4 => "udata4",
+        DW_EH_PE_UDATA8 => "udata8",
+        DW_EH_PE_SLEB128 => "sleb128",
+        DW_EH_PE_SDATA2 => "sdata2",
+        DW_EH_PE_SDATA4 => "sdata4",
+        DW_EH_PE_SDATA8 => "sdata8",
+        _ => "unknown",
+    };
+    let application = match encoding & 0x70 {
+        DW_EH_PE_PCREL => "pcrel|",
+        DW_EH_PE_TEXTREL => "textrel|",
+        DW_EH_PE_DATAREL => "datarel|",
+        DW_EH_PE_FUNCREL => "funcrel|",
+        DW_EH_PE_ALIGNED => "aligned|",
+        _ => "",
+    };
+    let indirect = if encoding & DW_EH_PE_INDIRECT != 0 { "indirect|" } else { "" };
+    format!("{}{}{}", indirect, application, format)
+}
+
+/// Where a section is, for resolving the pointers in it
+#[derive(Debug, Copy, Clone)]
+pub struct Ctx {
+    /// The address of the section
+    pub address: u64,
+    pub is_64: bool,
+    pub le: scroll::Endian,
+}
+
+/// Reads a pointer in `encoding` at `offset` of the section `data`; `None` if it is omitted.
+///
+/// `datarel` pointers are relative to the start of the section, which is what they mean in `.eh_frame_hdr`. An
+/// indirect pointer is the address of the real one, which isn't followed.
+fn read_pointer(data: &[u8], offset: &mut usize, encoding: u8, ctx: Ctx) -> error::Result<Option<u64>> {
+    if encoding == DW_EH_PE_OMIT {
+        return Ok(None);
+    }
+    let pc = ctx.address.wrapping_add(*offset as u64);
+    let value = match encoding & 0x0f {
+        DW_EH_PE_ABSPTR if ctx.is_64 => data.gread_with::<u64>(offset, ctx.le)?,
+        DW_EH_PE_ABSPTR => data.gread_with::<u32>(offset, ctx.le)? as u64,
+        DW_EH_PE_ULEB128 => Uleb128::read(&data, offset)?,
+        DW_EH_PE_UDATA2 => data.gread_with::<u16>(offset, ctx.le)? as u64,
+        DW_EH_PE_UDATA4 => data.gread_with::<u32>(offset, ctx.le)? as u64,
+        DW_EH_PE_UDATA8 => data.gread_with::<u64>(offset, ctx.le)?,
+        DW_EH_PE_SLEB128 => Sleb128::read(&data, offset)? as u64,
+        DW_EH_PE_SDATA2 => data.gread_with::<i16>(offset, ctx.le)? as i64 as u64,
+        DW_EH_PE_SDATA4 => data.gread_with::<i32>(offset, ctx.le)? as i64 as u64,
+        DW_EH_PE_SDATA8 => data.gread_with::<i64>(offset, ctx.le)? as u64,
+        _ => return Err(error::Error::Malformed(format!("unsupported pointer encoding {:#x}", encoding))),
+    };
+    match encoding & 0x70 {
+        DW_EH_PE_ABSPTR => Ok(Some(value)),
+        DW_EH_PE_PCREL => Ok(Some(pc.wrapping_add(value))),
+        DW_EH_PE_DATAREL => Ok(Some(ctx.address.wrapping_add(value))),
+        _ => Err(error::Error::Malformed(format!("unsupported pointer encoding {}", encoding_to_str(encoding)))),
+    }
+}
+
+/// The header of `.eh_frame_hdr`, which points at `.eh_frame` and may have a table to binary search its FDEs
+#[derive(Debug)]
+pub struct Hdr {
+    pub version: u8,
+    pub eh_frame_ptr_enc: u8,
+    pub fde_count_enc: u8,
+    pub table_enc: u8,
+    pub eh_frame_ptr: Option<u64>,
+    pub fde_count: Option<u64>,
+}
+
+impl Hdr {
+    /// Whether the FDEs can be binary searched, which needs their count
+    pub fn has_table(&self) -> bool {
+        self.table_enc != DW_EH_PE_OMIT && self.fde_count.is_some()
+    }
+}
+
+pub fn parse_hdr(data: &[u8], ctx: Ctx) -> error::Result<Hdr> {
+    let mut offset = 0;
+    let version = data.gread::<u8>(&mut offset)?;
+    if version != 1 {
+        return Err(error::Error::Malformed(format!("unknown .eh_frame_hdr version {}", version)));
+    }
+    let eh_frame_ptr_enc = data.gread::<u8>(&mut offset)?;
+    let fde_count_enc = data.gread::<u8>(&mut offset)?;
+    let table_enc = data.gread::<u8>(&mut offset)?;
+    let eh_frame_ptr = read_pointer(data, &mut offset, eh_frame_ptr_enc, ctx)?;
+    let fde_count = read_pointer(data, &mut offset, fde_count_enc, ctx)?;
+    Ok(Hdr {
+        version,
+        eh_frame_ptr_enc,
+        fde_count_enc,
+        table_enc,
+        eh_frame_ptr,
+        fde_count,
+    })
+}
+
+/// The code an FDE covers
+#[derive(Debug)]
+pub struct Fde {
+    pub start: u64,
+    pub length: u64,
+}
+
+#[derive(Debug)]
+pub struct EhFrame {
+    pub cies: usize,
+    pub fdes: Vec<Fde>,
+}
+
+impl EhFrame {
+    /// The lowest start and highest end of the FDEs
+    pub fn pc_range(&self) -> Option<(u64, u64)> {
+        let start = self.fdes.iter().map(|fde| fde.start).min()?;
+        let end = self.fdes.iter().map(|fde| fde.start.wrapping_add(fde.length)).max()?;
+        Some((start, end))
+    }
+}
+
+/// The encoding of the FDE pointers of the CIE at `offset`, the only part of it needed to read its FDEs
+fn fde_encoding(entry: &[u8], offset: &mut usize, ctx: Ctx) -> error::Result<u8> {
+    let version = entry.gread::<u8>(offset)?;
+    let augmentation = entry.pread::<&str>(*offset)?;
+    *offset += augmentation.len() + 1;
+    // gcc 2's eh data pointer
+    if augmentation.contains("eh") {
+        *offset += if ctx.is_64 { 8 } else { 4 };
+    }
+    // the code and data alignment factors, and the return address register
+    Uleb128::read(&entry, offset)?;
+    Sleb128::read(&entry, offset)?;
+    if version == 1 {
+        *offset += 1;
+    } else {
+        Uleb128::read(&entry, offset)?;
+    }
+    let mut encoding = DW_EH_PE_ABSPTR;
+    if augmentation.starts_with('z') {
+        Uleb128::read(&entry, offset)?;
+        for c in augmentation.chars().skip(1) {
+            match c {
+                'L' => { entry.gread::<u8>(offset)?; },
+                'P' => {
+                    let personality = entry.gread::<u8>(offset)?;
+                    read_pointer(entry, offset, personality, ctx)?;
+                },
+                'R' => encoding = entry.gread::<u8>(offset)?,
+                // the augmentation data of anything else can't be skipped, but 'R' usually comes before it
+                _ => break,
+            }
+        }
+    }
+    Ok(encoding)
+}
+
+pub fn parse(data: &[u8], ctx: Ctx) -> error::Result<EhFrame> {
+    let mut frame = EhFrame { cies: 0, fdes: Vec::new() };
+    // the FDE pointer encoding of each CIE, by its offset
+    let mut encodings = HashMap::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let start = offset;
+        let mut length = data.gread_with::<u32>(&mut offset, ctx.le)? as u64;
+        // a zero length terminates the section
+        if length == 0 {
+            break;
+        }
+        let mut id_size = 4;
+        if length == 0xffff_ffff {
+            length = data.gread_with::<u64>(&mut offset, ctx.le)?;
+            id_size = 8;
+        }
+        let end = match (offset as u64).checked_add(length) {
+            Some(end) if end <= data.len() as u64 => end as usize,
+            _ => return Err(error::Error::Malformed(format!("the .eh_frame entry at {:#x} of length {:#x} is past the end of the section", start, length))),
+        };
+        // reads can't run past the entry
+        let entry = &data[..end];
+        let id_offset = offset;
+        let id = if id_size == 4 { entry.gread_with::<u32>(&mut offset, ctx.le)? as u64 } else { entry.gread_with::<u64>(&mut offset, ctx.le)? };
+        if id == 0 {
+            encodings.insert(start, fde_encoding(entry, &mut offset, ctx)?);
+            frame.cies += 1;
+        } else {
+            // the id of an FDE is how far back its CIE is
+            let cie = (id_offset as u64).checked_sub(id).and_then(|cie| encodings.get(&(cie as usize)))
+                .ok_or_else(|| error::Error::Malformed(format!("the .eh_frame FDE at {:#x} has no CIE", start)))?;
+            let fde_start = read_pointer(entry, &mut offset, *cie, ctx)?.unwrap_or(0);
+            // the length has the same format, but is never relative to anything
+            let fde_length = read_pointer(entry, &mut offset, *cie & 0x0f, ctx)?.unwrap_or(0);
+            frame.fdes.push(Fde { start: fde_start, length: fde_length });
+        }
+        offset = end;
+    }
+    Ok(frame)
+}
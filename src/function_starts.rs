@@ -0,0 +1,39 @@
+//! Mach-o `LC_FUNCTION_STARTS`: the start of every function, even in stripped binaries.
+//!
+//! The data is a list of ULEB128 deltas, the first from the start of `__TEXT` and each following one from the
+//! previous function, ended by a zero.
+
+use goblin::error;
+use goblin::mach;
+use goblin::mach::load_command::CommandVariant;
+use scroll::Uleb128;
+
+/// The addresses of the functions listed by `LC_FUNCTION_STARTS`, or `None` if `mach` has no such command
+pub fn parse(bytes: &[u8], mach: &mach::MachO) -> error::Result<Option<Vec<u64>>> {
+    let command = mach.load_commands.iter().filter_map(|lc| match lc.command {
+        CommandVariant::FunctionStarts(command) => Some(command),
+        _ => None,
+    }).next();
+    let command = match command {
+        Some(command) => command,
+        None => return Ok(None),
+    };
+    let start = command.dataoff as usize;
+    let data = bytes.get(start..start.saturating_add(command.datasize as usize))
+        .ok_or_else(|| error::Error::Malformed(format!("LC_FUNCTION_STARTS data at {:#x} of size {:#x} is past the end of the file", command.dataoff, command.datasize)))?;
+    let text = mach.segments.iter().find(|segment| segment.name().ok() == Some("__TEXT"))
+        .ok_or_else(|| error::Error::Malformed("LC_FUNCTION_STARTS needs a __TEXT segment to start from".into()))?;
+    let mut address = text.vmaddr;
+    let mut functions = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let delta = Uleb128::read(&data, &mut offset)?;
+        // the rest is padding to the pointer size
+        if delta == 0 {
+            break;
+        }
+        address = address.wrapping_add(delta);
+        functions.push(address);
+    }
+    Ok(Some(functions))
+}
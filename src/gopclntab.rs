@@ -0,0 +1,191 @@
+//! Parser for the Go runtime's embedded symbol and line number table (`.gopclntab`).
+//!
+//! Go binaries carry their own function table which survives stripping, so for Go we can
+//! still recover function names, entry points and source files when the ELF/Mach-O symbol tables are gone.
+
+use goblin::error;
+use scroll::{self, Pread};
+
+/// ELF section name of the pclntab
+pub const ELF_SECTION: &str = ".gopclntab";
+/// Mach-O section name of the pclntab (inside `__TEXT`)
+pub const MACH_SECTION: &str = "__gopclntab";
+
+pub const GO12_MAGIC: u32 = 0xfffffffb;
+pub const GO116_MAGIC: u32 = 0xfffffffa;
+pub const GO118_MAGIC: u32 = 0xfffffff0;
+pub const GO120_MAGIC: u32 = 0xfffffff1;
+
+/// The size of the fixed pclntab header preceding the version specific fields
+const SIZEOF_HEADER: usize = 8;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Version {
+    Go12,
+    Go116,
+    Go118,
+    Go120,
+}
+
+impl Version {
+    pub fn from_magic(magic: u32) -> Option<Version> {
+        match magic {
+            GO12_MAGIC => Some(Version::Go12),
+            GO116_MAGIC => Some(Version::Go116),
+            GO118_MAGIC => Some(Version::Go118),
+            GO120_MAGIC => Some(Version::Go120),
+            _ => None,
+        }
+    }
+    /// The range of Go releases which emit this pclntab layout
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Version::Go12 => "go1.2-1.15",
+            Version::Go116 => "go1.16-1.17",
+            Version::Go118 => "go1.18-1.19",
+            Version::Go120 => "go1.20+",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Func<'a> {
+    pub entry: u64,
+    pub name: &'a str,
+}
+
+#[derive(Debug)]
+pub struct Pclntab<'a> {
+    pub version: Version,
+    pub funcs: Vec<Func<'a>>,
+    pub files: Vec<&'a str>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    ptr_size: usize,
+    le: scroll::Endian,
+}
+
+impl<'a> Reader<'a> {
+    fn u32(&self, offset: usize) -> error::Result<u32> {
+        Ok(self.bytes.pread_with::<u32>(offset, self.le)?)
+    }
+    fn uintptr(&self, offset: usize) -> error::Result<u64> {
+        if self.ptr_size == 8 {
+            Ok(self.bytes.pread_with::<u64>(offset, self.le)?)
+        } else {
+            Ok(self.bytes.pread_with::<u32>(offset, self.le)? as u64)
+        }
+    }
+    /// The `n`th pointer sized word following the fixed header
+    fn word(&self, n: usize) -> error::Result<usize> {
+        Ok(self.uintptr(SIZEOF_HEADER + n * self.ptr_size)? as usize)
+    }
+    fn str(&self, offset: usize) -> error::Result<&'a str> {
+        Ok(self.bytes.pread::<&str>(offset)?)
+    }
+    /// Guards against allocating for absurd counts in corrupt tables
+    fn count(&self, count: usize, entsize: usize) -> error::Result<usize> {
+        if count.saturating_mul(entsize) > self.bytes.len() {
+            Err(error::Error::Malformed(format!("pclntab count {} exceeds table size {:#x}", count, self.bytes.len())))
+        } else {
+            Ok(count)
+        }
+    }
+}
+
+/// Parse the pclntab contained in `bytes`, which is the entire contents of the pclntab section
+pub fn parse<'a>(bytes: &'a [u8], little_endian: bool) -> error::Result<Pclntab<'a>> {
+    let le = scroll::Endian::from(little_endian);
+    let magic = bytes.pread_with::<u32>(0, le)?;
+    let version = match Version::from_magic(magic) {
+        Some(version) => version,
+        None => return Err(error::Error::BadMagic(magic as u64)),
+    };
+    let ptr_size = bytes.pread::<u8>(7)?;
+    if ptr_size != 4 && ptr_size != 8 {
+        return Err(error::Error::Malformed(format!("pclntab has invalid pointer size {}", ptr_size)));
+    }
+    let reader = Reader { bytes, ptr_size: ptr_size as usize, le };
+    let (funcs, files) = match version {
+        Version::Go12 => parse_go12(&reader)?,
+        Version::Go116 => parse_go116(&reader)?,
+        Version::Go118 | Version::Go120 => parse_go118(&reader)?,
+    };
+    Ok(Pclntab {
+        version,
+        funcs,
+        files,
+    })
+}
+
+fn parse_go12<'a>(reader: &Reader<'a>) -> error::Result<(Vec<Func<'a>>, Vec<&'a str>)> {
+    let ptr_size = reader.ptr_size;
+    let nfunc = reader.count(reader.word(0)?, 2 * ptr_size)?;
+    let functab = SIZEOF_HEADER + ptr_size;
+    let mut funcs = Vec::with_capacity(nfunc);
+    for i in 0..nfunc {
+        let entry = reader.uintptr(functab + i * 2 * ptr_size)?;
+        let funcoff = reader.uintptr(functab + i * 2 * ptr_size + ptr_size)? as usize;
+        let nameoff = reader.u32(funcoff + ptr_size)? as usize;
+        funcs.push(Func { entry, name: reader.str(nameoff)? });
+    }
+    // the functab is terminated by the end pc, followed by the offset of the file table
+    let filetab = reader.u32(functab + nfunc * 2 * ptr_size + ptr_size)? as usize;
+    let nfiles = reader.count(reader.u32(filetab)? as usize, 4)?;
+    let mut files = Vec::with_capacity(nfiles);
+    for i in 1..nfiles {
+        let fileoff = reader.u32(filetab + i * 4)? as usize;
+        files.push(reader.str(fileoff)?);
+    }
+    Ok((funcs, files))
+}
+
+/// Reads `nfiles` consecutive NUL terminated filenames starting at `offset`
+fn parse_filetab<'a>(reader: &Reader<'a>, mut offset: usize, nfiles: usize) -> error::Result<Vec<&'a str>> {
+    let mut files = Vec::with_capacity(nfiles);
+    for _ in 0..nfiles {
+        let file = reader.str(offset)?;
+        offset += file.len() + 1;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+fn parse_go116<'a>(reader: &Reader<'a>) -> error::Result<(Vec<Func<'a>>, Vec<&'a str>)> {
+    let ptr_size = reader.ptr_size;
+    let nfunc = reader.count(reader.word(0)?, 2 * ptr_size)?;
+    let nfiles = reader.count(reader.word(1)?, 1)?;
+    let funcnametab = reader.word(2)?;
+    let filetab = reader.word(4)?;
+    let functab = reader.word(6)?;
+    let mut funcs = Vec::with_capacity(nfunc);
+    for i in 0..nfunc {
+        let entry = reader.uintptr(functab + i * 2 * ptr_size)?;
+        let funcoff = reader.uintptr(functab + i * 2 * ptr_size + ptr_size)? as usize;
+        let nameoff = reader.u32(functab + funcoff + ptr_size)? as usize;
+        funcs.push(Func { entry, name: reader.str(funcnametab + nameoff)? });
+    }
+    let files = parse_filetab(reader, filetab, nfiles)?;
+    Ok((funcs, files))
+}
+
+fn parse_go118<'a>(reader: &Reader<'a>) -> error::Result<(Vec<Func<'a>>, Vec<&'a str>)> {
+    let nfunc = reader.count(reader.word(0)?, 8)?;
+    let nfiles = reader.count(reader.word(1)?, 1)?;
+    let text_start = reader.word(2)? as u64;
+    let funcnametab = reader.word(3)?;
+    let filetab = reader.word(5)?;
+    let functab = reader.word(7)?;
+    let mut funcs = Vec::with_capacity(nfunc);
+    for i in 0..nfunc {
+        // the functab entries are 32-bit offsets relative to the start of the text
+        let entryoff = reader.u32(functab + i * 8)? as u64;
+        let funcoff = reader.u32(functab + i * 8 + 4)? as usize;
+        let nameoff = reader.u32(functab + funcoff + 4)? as usize;
+        funcs.push(Func { entry: text_start + entryoff, name: reader.str(funcnametab + nameoff)? });
+    }
+    let files = parse_filetab(reader, filetab, nfiles)?;
+    Ok((funcs, files))
+}
@@ -0,0 +1,6706 @@
+extern crate goblin;
+extern crate colored;
+extern crate structopt;
+#[macro_use]
+extern crate structopt_derive;
+extern crate rustc_demangle;
+extern crate cpp_demangle;
+extern crate scroll;
+#[macro_use]
+extern crate prettytable;
+extern crate term;
+extern crate notify;
+extern crate wasmparser;
+extern crate md5;
+#[cfg(feature = "compression")]
+extern crate flate2;
+#[cfg(feature = "compression")]
+extern crate ruzstd;
+#[cfg(feature = "disasm")]
+extern crate capstone;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "dwarf")]
+extern crate gimli;
+
+mod gopclntab;
+mod wasm;
+mod compressed;
+mod clr;
+mod pe_import;
+mod resource;
+mod pe_debug;
+mod rich;
+mod ar;
+mod nlist;
+mod note;
+mod disasm;
+mod arch;
+mod version;
+mod theme;
+mod thread;
+mod coff;
+mod function_starts;
+mod bitcode;
+mod eh_frame;
+mod dyld_info;
+mod dwarf;
+#[cfg(test)]
+mod tests;
+
+use scroll::Pread;
+use prettytable::{format, Table};
+use prettytable::row::Row;
+use prettytable::cell::Cell;
+
+use goblin::{error, Hint, pe, elf, mach, archive, container};
+use std::path::Path;
+use std::fs::File;
+use std::io::{Read, Write};
+
+use colored::Colorize;
+use structopt::StructOpt;
+
+/// An integer given on the command line, either in hex with a `0x` prefix, or in decimal
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Number(u64);
+
+impl ::std::str::FromStr for Number {
+    type Err = ::std::num::ParseIntError;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        if s.starts_with("0x") || s.starts_with("0X") {
+            Ok(Number(u64::from_str_radix(&s[2..], 16)?))
+        } else {
+            Ok(Number(s.parse()?))
+        }
+    }
+}
+
+/// Dynamic linkers shipped by common Linux, Android, and BSD systems
+const KNOWN_INTERPRETERS: [&str; 40] = [
+    "/lib64/ld-linux-x86-64.so.2",
+    "/libx32/ld-linux-x32.so.2",
+    "/lib/ld-linux.so.2",
+    "/lib/ld-linux.so.3",
+    "/lib/ld-linux-armhf.so.3",
+    "/lib/ld-linux-aarch64.so.1",
+    "/lib/ld-linux-aarch64_be.so.1",
+    "/lib/ld-linux-riscv64-lp64d.so.1",
+    "/lib/ld-linux-riscv64-lp64.so.1",
+    "/lib/ld-linux-riscv32-ilp32d.so.1",
+    "/lib64/ld-linux-loongarch-lp64d.so.1",
+    "/lib/ld.so.1",
+    "/lib32/ld.so.1",
+    "/lib64/ld.so.1",
+    "/lib/ld64.so.1",
+    "/lib64/ld64.so.1",
+    "/lib64/ld64.so.2",
+    "/lib/ld-musl-x86_64.so.1",
+    "/lib/ld-musl-i386.so.1",
+    "/lib/ld-musl-aarch64.so.1",
+    "/lib/ld-musl-arm.so.1",
+    "/lib/ld-musl-armhf.so.1",
+    "/lib/ld-musl-riscv64.so.1",
+    "/lib/ld-musl-powerpc64le.so.1",
+    "/lib/ld-musl-s390x.so.1",
+    "/lib/ld-musl-mips.so.1",
+    "/lib/ld-musl-mipsel.so.1",
+    "/lib/ld-musl-loongarch64.so.1",
+    "/system/bin/linker",
+    "/system/bin/linker64",
+    "/apex/com.android.runtime/bin/linker",
+    "/apex/com.android.runtime/bin/linker64",
+    "/libexec/ld-elf.so.1",
+    "/libexec/ld-elf32.so.1",
+    "/usr/libexec/ld-elf.so.1",
+    "/usr/libexec/ld.elf_so",
+    "/usr/libexec/ld.so",
+    "/usr/lib/ld.so.1",
+    "/usr/lib/amd64/ld.so.1",
+    "/usr/lib/64/ld.so.1",
+];
+
+/// The interpreters `--check-interp` accepts, or `None` if it wasn't asked for
+fn known_interpreters (opt: &Opt) -> error::Result<Option<Vec<String>>> {
+    match opt.interp_whitelist {
+        Some(ref whitelist) => {
+            let mut contents = String::new();
+            File::open(whitelist)?.read_to_string(&mut contents)?;
+            Ok(Some(contents.lines().map(|line| line.trim()).filter(|line| !line.is_empty() && !line.starts_with('#')).map(|line| line.to_owned()).collect()))
+        },
+        None if opt.check_interp => Ok(Some(KNOWN_INTERPRETERS.iter().map(|&interpreter| interpreter.to_owned()).collect())),
+        None => Ok(None),
+    }
+}
+
+/// The C library whose dynamic linker `interpreter` is, by its file name
+fn interpreter_libc (interpreter: &str) -> Option<&'static str> {
+    let name = interpreter.rsplit('/').next().unwrap_or(interpreter);
+    if name.starts_with("ld-musl-") {
+        Some("musl")
+    } else if name.starts_with("ld-linux") || name.starts_with("ld64.so") {
+        Some("glibc")
+    } else if name.starts_with("ld-uClibc") {
+        Some("uClibc")
+    } else if interpreter.starts_with("/system/bin/linker") {
+        Some("bionic")
+    } else {
+        None
+    }
+}
+
+/// Why `interpreter` isn't a path a dynamic linker would be installed at, if it isn't
+fn interpreter_warning (interpreter: &str) -> Option<&'static str> {
+    if !interpreter.starts_with('/') {
+        // resolved against the working directory at exec time
+        Some("relative path")
+    } else if interpreter.split('/').any(|component| component == "..") {
+        Some("path escapes with ..")
+    } else if ["/tmp/", "/var/tmp/", "/dev/shm/", "/home/", "/root/"].iter().any(|dir| interpreter.starts_with(dir)) {
+        Some("in a user-writable directory")
+    } else {
+        None
+    }
+}
+
+const EI_VERSION: usize = 6;
+const EI_OSABI: usize = 7;
+const EI_ABIVERSION: usize = 8;
+/// The start of the reserved bytes at the end of e_ident, which should be zero
+const EI_PAD: usize = 9;
+
+/// The OS or ABI extensions an ELF is built for, from `e_ident[EI_OSABI]`
+fn osabi_to_str (osabi: u8) -> &'static str {
+    match osabi {
+        0 => "SysV",
+        1 => "HP-UX",
+        2 => "NetBSD",
+        3 => "GNU/Linux",
+        4 => "GNU/Hurd",
+        6 => "Solaris",
+        7 => "AIX",
+        8 => "IRIX",
+        9 => "FreeBSD",
+        10 => "Tru64",
+        11 => "Novell Modesto",
+        12 => "OpenBSD",
+        13 => "OpenVMS",
+        14 => "NonStop Kernel",
+        15 => "AROS",
+        16 => "FenixOS",
+        17 => "CloudABI",
+        18 => "OpenVOS",
+        64 => "ARM EABI",
+        97 => "ARM",
+        255 => "standalone",
+        _ => "unknown",
+    }
+}
+
+/// Shared object to load before the one with this entry, whose symbols take precedence
+const DT_AUXILIARY: u64 = 0x7ffffffd;
+/// Shared object this one is a filter for
+const DT_FILTER: u64 = 0x7fffffff;
+
+// the dynamic tags goblin doesn't know, from the gABI and glibc's elf.h
+const DT_SYMTAB_SHNDX: u64 = 34;
+const DT_RELRSZ: u64 = 35;
+const DT_RELR: u64 = 36;
+const DT_RELRENT: u64 = 37;
+const DT_GNU_PRELINKED: u64 = 0x6ffffdf5;
+const DT_GNU_CONFLICTSZ: u64 = 0x6ffffdf6;
+const DT_GNU_LIBLISTSZ: u64 = 0x6ffffdf7;
+const DT_CHECKSUM: u64 = 0x6ffffdf8;
+const DT_PLTPADSZ: u64 = 0x6ffffdf9;
+const DT_MOVEENT: u64 = 0x6ffffdfa;
+const DT_MOVESZ: u64 = 0x6ffffdfb;
+const DT_FEATURE_1: u64 = 0x6ffffdfc;
+const DT_POSFLAG_1: u64 = 0x6ffffdfd;
+const DT_SYMINSZ: u64 = 0x6ffffdfe;
+const DT_SYMINENT: u64 = 0x6ffffdff;
+const DT_TLSDESC_PLT: u64 = 0x6ffffef6;
+const DT_TLSDESC_GOT: u64 = 0x6ffffef7;
+const DT_GNU_CONFLICT: u64 = 0x6ffffef8;
+const DT_GNU_LIBLIST: u64 = 0x6ffffef9;
+const DT_CONFIG: u64 = 0x6ffffefa;
+const DT_DEPAUDIT: u64 = 0x6ffffefb;
+const DT_AUDIT: u64 = 0x6ffffefc;
+const DT_PLTPAD: u64 = 0x6ffffefd;
+const DT_MOVETAB: u64 = 0x6ffffefe;
+const DT_SYMINFO: u64 = 0x6ffffeff;
+
+/// The name of a dynamic tag, including the ones goblin doesn't know; tags nobody knows are shown in hex
+fn tag_to_str (tag: u64) -> String {
+    let name = match tag {
+        DT_AUXILIARY => "DT_AUXILIARY",
+        DT_FILTER => "DT_FILTER",
+        DT_SYMTAB_SHNDX => "DT_SYMTAB_SHNDX",
+        DT_RELRSZ => "DT_RELRSZ",
+        DT_RELR => "DT_RELR",
+        DT_RELRENT => "DT_RELRENT",
+        DT_GNU_PRELINKED => "DT_GNU_PRELINKED",
+        DT_GNU_CONFLICTSZ => "DT_GNU_CONFLICTSZ",
+        DT_GNU_LIBLISTSZ => "DT_GNU_LIBLISTSZ",
+        DT_CHECKSUM => "DT_CHECKSUM",
+        DT_PLTPADSZ => "DT_PLTPADSZ",
+        DT_MOVEENT => "DT_MOVEENT",
+        DT_MOVESZ => "DT_MOVESZ",
+        DT_FEATURE_1 => "DT_FEATURE_1",
+        DT_POSFLAG_1 => "DT_POSFLAG_1",
+        DT_SYMINSZ => "DT_SYMINSZ",
+        DT_SYMINENT => "DT_SYMINENT",
+        DT_TLSDESC_PLT => "DT_TLSDESC_PLT",
+        DT_TLSDESC_GOT => "DT_TLSDESC_GOT",
+        DT_GNU_CONFLICT => "DT_GNU_CONFLICT",
+        DT_GNU_LIBLIST => "DT_GNU_LIBLIST",
+        DT_CONFIG => "DT_CONFIG",
+        DT_DEPAUDIT => "DT_DEPAUDIT",
+        DT_AUDIT => "DT_AUDIT",
+        DT_PLTPAD => "DT_PLTPAD",
+        DT_MOVETAB => "DT_MOVETAB",
+        DT_SYMINFO => "DT_SYMINFO",
+        _ => elf::dyn::tag_to_str(tag),
+    };
+    if name == "UNKNOWN_TAG" { format!("DT_{:#x}", tag) } else { name.to_owned() }
+}
+
+const DF_1_STUB: u64 = 0x04000000;
+const DF_1_PIE: u64 = 0x08000000;
+const DF_1_KMOD: u64 = 0x10000000;
+const DF_1_WEAKFILTER: u64 = 0x20000000;
+const DF_1_NOCOMMON: u64 = 0x40000000;
+
+const DF_FLAGS: [(u64, &str); 5] = [
+    (elf::dyn::DF_ORIGIN, "ORIGIN"),
+    (elf::dyn::DF_SYMBOLIC, "SYMBOLIC"),
+    (elf::dyn::DF_TEXTREL, "TEXTREL"),
+    (elf::dyn::DF_BIND_NOW, "BIND_NOW"),
+    (elf::dyn::DF_STATIC_TLS, "STATIC_TLS"),
+];
+
+const DF_1_FLAGS: [(u64, &str); 31] = [
+    (elf::dyn::DF_1_NOW, "NOW"),
+    (elf::dyn::DF_1_GLOBAL, "GLOBAL"),
+    (elf::dyn::DF_1_GROUP, "GROUP"),
+    (elf::dyn::DF_1_NODELETE, "NODELETE"),
+    (elf::dyn::DF_1_LOADFLTR, "LOADFLTR"),
+    (elf::dyn::DF_1_INITFIRST, "INITFIRST"),
+    (elf::dyn::DF_1_NOOPEN, "NOOPEN"),
+    (elf::dyn::DF_1_ORIGIN, "ORIGIN"),
+    (elf::dyn::DF_1_DIRECT, "DIRECT"),
+    (elf::dyn::DF_1_TRANS, "TRANS"),
+    (elf::dyn::DF_1_INTERPOSE, "INTERPOSE"),
+    (elf::dyn::DF_1_NODEFLIB, "NODEFLIB"),
+    (elf::dyn::DF_1_NODUMP, "NODUMP"),
+    (elf::dyn::DF_1_CONFALT, "CONFALT"),
+    (elf::dyn::DF_1_ENDFILTEE, "ENDFILTEE"),
+    (elf::dyn::DF_1_DISPRELDNE, "DISPRELDNE"),
+    (elf::dyn::DF_1_DISPRELPND, "DISPRELPND"),
+    (elf::dyn::DF_1_NODIRECT, "NODIRECT"),
+    (elf::dyn::DF_1_IGNMULDEF, "IGNMULDEF"),
+    (elf::dyn::DF_1_NOKSYMS, "NOKSYMS"),
+    (elf::dyn::DF_1_NOHDR, "NOHDR"),
+    (elf::dyn::DF_1_EDITED, "EDITED"),
+    (elf::dyn::DF_1_NORELOC, "NORELOC"),
+    (elf::dyn::DF_1_SYMINTPOSE, "SYMINTPOSE"),
+    (elf::dyn::DF_1_GLOBAUDIT, "GLOBAUDIT"),
+    (elf::dyn::DF_1_SINGLETON, "SINGLETON"),
+    (DF_1_STUB, "STUB"),
+    (DF_1_PIE, "PIE"),
+    (DF_1_KMOD, "KMOD"),
+    (DF_1_WEAKFILTER, "WEAKFILTER"),
+    (DF_1_NOCOMMON, "NOCOMMON"),
+];
+
+/// How the value of a dynamic entry is shown
+#[derive(Copy, Clone)]
+enum DynValue {
+    /// An offset into the dynamic string table
+    Str,
+    Count,
+    Addr,
+    Size,
+    /// Another dynamic tag
+    Tag,
+    /// A flag word, with the names of its bits
+    Flags(&'static [(u64, &'static str)]),
+}
+
+/// The tags whose values mean more than a number; everything else is shown in hex
+const DYN_VALUES: [(u64, DynValue); 61] = [
+    (elf::dyn::DT_NEEDED, DynValue::Str),
+    (elf::dyn::DT_SONAME, DynValue::Str),
+    (elf::dyn::DT_RPATH, DynValue::Str),
+    (elf::dyn::DT_RUNPATH, DynValue::Str),
+    (DT_AUXILIARY, DynValue::Str),
+    (DT_FILTER, DynValue::Str),
+    (DT_CONFIG, DynValue::Str),
+    (DT_DEPAUDIT, DynValue::Str),
+    (DT_AUDIT, DynValue::Str),
+    (elf::dyn::DT_RELACOUNT, DynValue::Count),
+    (elf::dyn::DT_RELCOUNT, DynValue::Count),
+    (elf::dyn::DT_VERNEEDNUM, DynValue::Count),
+    (elf::dyn::DT_VERDEFNUM, DynValue::Count),
+    (elf::dyn::DT_PLTGOT, DynValue::Addr),
+    (elf::dyn::DT_HASH, DynValue::Addr),
+    (elf::dyn::DT_GNU_HASH, DynValue::Addr),
+    (elf::dyn::DT_STRTAB, DynValue::Addr),
+    (elf::dyn::DT_SYMTAB, DynValue::Addr),
+    (elf::dyn::DT_RELA, DynValue::Addr),
+    (elf::dyn::DT_REL, DynValue::Addr),
+    (elf::dyn::DT_JMPREL, DynValue::Addr),
+    (elf::dyn::DT_INIT, DynValue::Addr),
+    (elf::dyn::DT_FINI, DynValue::Addr),
+    (elf::dyn::DT_INIT_ARRAY, DynValue::Addr),
+    (elf::dyn::DT_FINI_ARRAY, DynValue::Addr),
+    (elf::dyn::DT_PREINIT_ARRAY, DynValue::Addr),
+    (elf::dyn::DT_DEBUG, DynValue::Addr),
+    (elf::dyn::DT_VERSYM, DynValue::Addr),
+    (elf::dyn::DT_VERDEF, DynValue::Addr),
+    (elf::dyn::DT_VERNEED, DynValue::Addr),
+    (DT_SYMTAB_SHNDX, DynValue::Addr),
+    (DT_RELR, DynValue::Addr),
+    (DT_TLSDESC_PLT, DynValue::Addr),
+    (DT_TLSDESC_GOT, DynValue::Addr),
+    (DT_GNU_CONFLICT, DynValue::Addr),
+    (DT_GNU_LIBLIST, DynValue::Addr),
+    (DT_PLTPAD, DynValue::Addr),
+    (DT_MOVETAB, DynValue::Addr),
+    (DT_SYMINFO, DynValue::Addr),
+    (elf::dyn::DT_PLTRELSZ, DynValue::Size),
+    (elf::dyn::DT_RELASZ, DynValue::Size),
+    (elf::dyn::DT_RELAENT, DynValue::Size),
+    (elf::dyn::DT_RELSZ, DynValue::Size),
+    (elf::dyn::DT_RELENT, DynValue::Size),
+    (elf::dyn::DT_STRSZ, DynValue::Size),
+    (elf::dyn::DT_SYMENT, DynValue::Size),
+    (elf::dyn::DT_INIT_ARRAYSZ, DynValue::Size),
+    (elf::dyn::DT_FINI_ARRAYSZ, DynValue::Size),
+    (elf::dyn::DT_PREINIT_ARRAYSZ, DynValue::Size),
+    (DT_RELRSZ, DynValue::Size),
+    (DT_RELRENT, DynValue::Size),
+    (DT_GNU_CONFLICTSZ, DynValue::Size),
+    (DT_GNU_LIBLISTSZ, DynValue::Size),
+    (DT_PLTPADSZ, DynValue::Size),
+    (DT_MOVEENT, DynValue::Size),
+    (DT_MOVESZ, DynValue::Size),
+    (DT_SYMINSZ, DynValue::Size),
+    (DT_SYMINENT, DynValue::Size),
+    // the kind of relocations in DT_JMPREL
+    (elf::dyn::DT_PLTREL, DynValue::Tag),
+    (elf::dyn::DT_FLAGS, DynValue::Flags(&DF_FLAGS)),
+    (elf::dyn::DT_FLAGS_1, DynValue::Flags(&DF_1_FLAGS)),
+];
+
+fn dyn_value (tag: u64) -> Option<DynValue> {
+    DYN_VALUES.iter().find(|&&(t, _)| t == tag).map(|&(_, kind)| kind)
+}
+
+/// The raw value of a DT_FLAGS/DT_FLAGS_1 entry, followed by the names of its bits in `table`, and any unknown bits
+fn dyn_flag_names (val: u64, table: &[(u64, &'static str)]) -> String {
+    let mut flags = table.iter().filter(|&&(bit, _)| val & bit != 0).map(|&(_, name)| name.to_owned()).collect::<Vec<_>>();
+    let unknown = table.iter().fold(val, |val, &(bit, _)| val & !bit);
+    if unknown != 0 {
+        flags.push(format!("UNKNOWN({:#x})", unknown));
+    }
+    flags.join(" ")
+}
+
+fn dyn_flags (val: u64, table: &[(u64, &'static str)]) -> String {
+    let names = dyn_flag_names(val, table);
+    if names.is_empty() { format!("{:#x}", val) } else { format!("{:#x} {}", val, names) }
+}
+
+/// Set in the flag word of an SHT_GROUP section whose group the linker keeps only one copy of
+const GRP_COMDAT: u32 = 0x1;
+
+const STV_DEFAULT: u8 = 0;
+const STV_INTERNAL: u8 = 1;
+const STV_HIDDEN: u8 = 2;
+const STV_PROTECTED: u8 = 3;
+
+fn visibility_to_str (st_other: u8) -> &'static str {
+    match st_other & 0x3 {
+        STV_DEFAULT => "DEFAULT",
+        STV_INTERNAL => "INTERNAL",
+        STV_HIDDEN => "HIDDEN",
+        _ => "PROTECTED",
+    }
+}
+
+/// A symbol visibility given on the command line, e.g. `hidden`
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Visibility(u8);
+
+#[derive(Debug)]
+struct ParseVisibilityError(String);
+
+impl ::std::fmt::Display for ParseVisibilityError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "unknown visibility {:?}, expected default, internal, hidden, or protected", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseVisibilityError {
+    fn description(&self) -> &str {
+        "unknown visibility, expected default, internal, hidden, or protected"
+    }
+}
+
+impl ::std::str::FromStr for Visibility {
+    type Err = ParseVisibilityError;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Visibility(STV_DEFAULT)),
+            "internal" => Ok(Visibility(STV_INTERNAL)),
+            "hidden" => Ok(Visibility(STV_HIDDEN)),
+            "protected" => Ok(Visibility(STV_PROTECTED)),
+            _ => Err(ParseVisibilityError(s.to_owned())),
+        }
+    }
+}
+
+/// The order to list symbols in, given on the command line
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum SortSyms {
+    Addr,
+    Name,
+    /// Largest first
+    Size,
+}
+
+#[derive(Debug)]
+struct ParseSortSymsError(String);
+
+impl ::std::fmt::Display for ParseSortSymsError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "unknown symbol order {:?}, expected addr, name, or size", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseSortSymsError {
+    fn description(&self) -> &str {
+        "unknown symbol order, expected addr, name, or size"
+    }
+}
+
+impl ::std::str::FromStr for SortSyms {
+    type Err = ParseSortSymsError;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "addr" => Ok(SortSyms::Addr),
+            "name" => Ok(SortSyms::Name),
+            "size" => Ok(SortSyms::Size),
+            _ => Err(ParseSortSymsError(s.to_owned())),
+        }
+    }
+}
+
+/// The order to list sections in, given on the command line
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum SortSections {
+    Offset,
+    Addr,
+    /// Largest first
+    Size,
+    Name,
+}
+
+#[derive(Debug)]
+struct ParseSortSectionsError(String);
+
+impl ::std::fmt::Display for ParseSortSectionsError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "unknown section order {:?}, expected offset, addr, size, or name", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseSortSectionsError {
+    fn description(&self) -> &str {
+        "unknown section order, expected offset, addr, size, or name"
+    }
+}
+
+impl ::std::str::FromStr for SortSections {
+    type Err = ParseSortSectionsError;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "offset" => Ok(SortSections::Offset),
+            "addr" => Ok(SortSections::Addr),
+            "size" => Ok(SortSections::Size),
+            "name" => Ok(SortSections::Name),
+            _ => Err(ParseSortSectionsError(s.to_owned())),
+        }
+    }
+}
+
+/// A byte pattern given on the command line, e.g. `55 48 89 e5 ?? ??`, where `??` matches any byte
+#[derive(Debug, Clone, PartialEq)]
+struct Signature(Vec<Option<u8>>);
+
+#[derive(Debug)]
+struct ParseSignatureError(String);
+
+impl ::std::fmt::Display for ParseSignatureError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "bad byte signature {:?}, expected hex bytes like `55 48 89 e5`, with `??` for any byte and at least one byte that isn't", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseSignatureError {
+    fn description(&self) -> &str {
+        "bad byte signature, expected hex bytes like `55 48 89 e5`, with `??` for any byte"
+    }
+}
+
+impl ::std::str::FromStr for Signature {
+    type Err = ParseSignatureError;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        let digits = s.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>();
+        if digits.len() % 2 != 0 {
+            return Err(ParseSignatureError(s.to_owned()));
+        }
+        let pattern = digits.chunks(2).map(|pair| match (pair[0], pair[1]) {
+            ('?', '?') => Ok(None),
+            (hi, lo) => match (hi.to_digit(16), lo.to_digit(16)) {
+                (Some(hi), Some(lo)) => Ok(Some((hi << 4 | lo) as u8)),
+                _ => Err(ParseSignatureError(s.to_owned())),
+            },
+        }).collect::<::std::result::Result<Vec<_>, _>>()?;
+        // only wildcards would match everywhere
+        if pattern.iter().all(|byte| byte.is_none()) {
+            return Err(ParseSignatureError(s.to_owned()));
+        }
+        Ok(Signature(pattern))
+    }
+}
+
+impl ::std::fmt::Display for Signature {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.0.iter().map(|byte| match *byte {
+            Some(byte) => format!("{:02x}", byte),
+            None => "??".to_owned(),
+        }).collect::<Vec<_>>();
+        write!(fmt, "{}", bytes.join(" "))
+    }
+}
+
+impl Signature {
+    /// The offsets of every match in `bytes`
+    fn find_all (&self, bytes: &[u8]) -> Vec<usize> {
+        bytes.windows(self.0.len()).enumerate()
+            .filter(|&(_, window)| window.iter().zip(&self.0).all(|(&byte, pattern)| pattern.is_none_or(|pattern| byte == pattern)))
+            .map(|(i, _)| i).collect()
+    }
+}
+
+#[derive(StructOpt, Debug, Clone)]
+#[structopt(name = "bingrep", about = "bingrep - grepping through binaries since 2017")]
+struct Args {
+
+    /// A flag, true if used in the command line.
+    #[structopt(short = "d", long = "debug", help = "Print debug version of parse results")]
+    debug: bool,
+
+    /// Whether to use pretty tables
+    #[structopt(short = "p", long = "pretty", help = "Use pretty tables")]
+    pretty: bool,
+
+    /// Force coloring
+    #[structopt(long = "color", help = "Color")]
+    color: bool,
+
+    ///
+    #[structopt(short = "s", long = "search", help = "Search for string")]
+    search: Option<String>,
+
+    /// Only print the offsets of the search matches
+    #[structopt(long = "offsets-only", help = "With --search, print only the file offset of each match in hex, one per line, for scripts; exits with 4 if there are none")]
+    offsets_only: bool,
+
+    /// Compare the symbols of two binaries
+    #[structopt(long = "diff-syms", help = "Compare the defined ELF or Mach-o symbols of the input with those of a second binary, listing those added, removed, or resized, largest change first")]
+    diff_syms: bool,
+
+    /// Drop Rust symbol hashes before comparing
+    #[structopt(long = "ignore-hash", help = "With --diff-syms, compare Rust symbols without their `::h` hash suffix, so a rebuild that only changes hashes shows no difference; implies --demangle")]
+    ignore_hash: bool,
+
+    /// Compare the headers of two binaries
+    #[structopt(long = "diff", help = "Compare the ELF header, program headers, sections by name, dynamic entries, and libraries of the input with those of a second binary, printing only what differs; exits with 6 if anything does")]
+    diff: bool,
+
+    /// A flag, true if used in the command line.
+    #[structopt(short = "D", long = "demangle", help = "Apply Rust/C++ demangling")]
+    demangle: bool,
+
+    /// Only print suspicious header layouts
+    #[structopt(long = "anomalies", help = "Report overlapping or suspicious section/segment layouts")]
+    anomalies: bool,
+
+    /// Only print the unaccounted for byte ranges
+    #[structopt(long = "section-gaps", help = "Report the byte ranges of an ELF or PE that no section, or for ELF no PT_LOAD segment, covers")]
+    section_gaps: bool,
+
+    /// Only print the padding between sections
+    #[structopt(long = "alignment-waste", help = "Report the padding between consecutive ELF sections in the file, the largest gaps first highlighted, and its total share of the file size")]
+    alignment_waste: bool,
+
+    /// Only print a summary of the DWARF debug info
+    #[structopt(long = "dwarf", help = "Report the sizes of the ELF's DWARF sections, and how many compilation and type units it has of which DWARF versions")]
+    dwarf: bool,
+
+    /// Scan for embedded binaries
+    #[structopt(long = "carve", help = "Scan the input for embedded ELF/PE/Mach-o/archive binaries")]
+    carve: bool,
+
+    /// Directory to write carved binaries into
+    #[structopt(long = "carve-extract", help = "Write each carved binary into the given directory (implies --carve)")]
+    carve_extract: Option<String>,
+
+    /// Don't truncate names in the plain output
+    #[structopt(long = "full-names", help = "Don't truncate section, segment, or symbol names to fit their column")]
+    full_names: bool,
+
+    /// Print the section table as CSV
+    #[structopt(long = "csv-sections", help = "Print only the section table, as uncolored CSV with a header row")]
+    csv_sections: bool,
+
+    /// Don't decompress SHF_COMPRESSED sections
+    #[structopt(long = "raw-compressed", help = "Read the raw compressed bytes of SHF_COMPRESSED sections instead of decompressing them")]
+    raw_compressed: bool,
+
+    /// Write out the contents of a section
+    #[structopt(long = "extract", help = "Write only the contents of this ELF section, e.g. .debug_info, decompressing it if it is SHF_COMPRESSED unless --raw-compressed is passed")]
+    extract: Option<String>,
+
+    /// Load symbols from the file named by .gnu_debuglink
+    #[structopt(long = "follow-debuglink", help = "Find the separate debug file named by .gnu_debuglink and show its symbols")]
+    follow_debuglink: bool,
+
+    /// Print function names, one per line
+    #[structopt(long = "functions-only", help = "Print only the names of defined functions, one per line, without colors or addresses")]
+    functions_only: bool,
+
+    /// Extract the printable strings
+    #[structopt(long = "strings", help = "Print the printable ASCII runs of the file, and for PE also the UTF-16LE ones, with their file offset and the section containing them, like strings -t x")]
+    strings: bool,
+
+    /// The shortest string --strings prints
+    #[structopt(long = "min", default_value = "4", help = "With --strings, skip runs shorter than this many characters")]
+    min: usize,
+
+    /// Print the exported symbols, one per line
+    #[structopt(long = "export-list", help = "Print only the raw names of the defined, visible global and weak dynamic ELF symbols, or Mach-o exports, one per line, as for an .exp file or ld64's -exported_symbols_list")]
+    export_list: bool,
+
+    /// Print a linker version script of the exported symbols
+    #[structopt(long = "version-script", alias = "emit-version-script", help = "Print a GNU ld version script exporting the defined, visible global and weak dynamic ELF symbols by their raw mangled names, grouped in the versions the library already defines, or a Mach-o -exported_symbols_list")]
+    version_script: bool,
+
+    /// Print only counts
+    #[structopt(long = "summary", help = "Print only counts: ELF or Mach-o sections, segments, and symbols by kind, relocations by type, and how many libraries, exports, and imports")]
+    summary: bool,
+
+    /// Print a linker map of the sections and their symbols
+    #[structopt(long = "emit-map", help = "Print an `ld -Map` style report of the allocated ELF sections in address order, each with its symbols by address and size and the slack no symbol accounts for, as uncolored text for diffing builds")]
+    emit_map: bool,
+
+    /// Where to find the separate debug info
+    #[structopt(long = "debug-info", help = "Load symbols from separate debug info found by build-id: `auto` for /usr/lib/debug, a debug directory, or a file")]
+    debug_info: Option<String>,
+
+    /// The root of the system the binary is for
+    #[structopt(long = "sysroot", help = "Resolve the ELF's libraries as if this directory were the root of the system, for binaries built for another one")]
+    sysroot: Option<String>,
+
+    /// Where to look for libraries instead of the default directories
+    #[structopt(long = "library-path", help = "Resolve the ELF's libraries in these directories, separated by `:`, instead of the default /lib, /usr/lib, etc. (after the rpaths)")]
+    library_path: Option<String>,
+
+    /// Find which library defines each import
+    #[structopt(long = "resolve-imports", help = "Look up each ELF import in the exported dynamic symbols of the libraries it needs, like `ldd -r` without running the binary")]
+    resolve_imports: bool,
+
+    /// The order to list symbols in
+    #[structopt(long = "sort-syms", help = "List ELF symbols by addr, name, or size (largest first) instead of in symbol table order")]
+    sort_syms: Option<SortSyms>,
+
+    /// The order to list sections in
+    #[structopt(long = "sort-sections", help = "List ELF section headers, and the Mach-o sections of each segment, by offset, addr, size (largest first), or name instead of in file order; indices stay the original ones")]
+    sort_sections: Option<SortSections>,
+
+    /// Hide smaller entries
+    #[structopt(long = "min-size", help = "Hide ELF symbols, sections, and program headers smaller than this many bytes, e.g. 1024 or 0x400")]
+    min_size: Option<Number>,
+
+    /// Hide larger entries
+    #[structopt(long = "max-size", help = "Hide ELF symbols, sections, and program headers larger than this many bytes")]
+    max_size: Option<Number>,
+
+    /// Sum the sizes of the listed entries
+    #[structopt(long = "total-size", help = "Print the total size of the listed ELF symbols, sections, and program headers below each table")]
+    total_size: bool,
+
+    /// Only show symbols with this visibility
+    #[structopt(long = "only-visibility", help = "Only list ELF symbols with the given visibility: default, internal, hidden, or protected")]
+    only_visibility: Option<Visibility>,
+
+    /// Zero pad addresses
+    #[structopt(long = "pad-addresses", help = "Zero pad addresses and offsets to the word size, 16 hex digits for 64-bit binaries and 8 for 32-bit ones, so columns line up across builds")]
+    pad_addresses: bool,
+
+    /// Find functions by their bytes
+    #[structopt(long = "find-function", help = "Print only where this hex byte pattern, e.g. `55 48 89 e5 ?? ??` with `??` for any byte, occurs in the executable sections of an ELF, Mach-o, or PE, with the nearest symbol")]
+    find_function: Option<Signature>,
+
+    /// How much of each section --find-function scans
+    #[structopt(long = "max-scan-size", help = "Only scan the first this many bytes of each section for --find-function, e.g. 0x100000 (default 64 MiB)")]
+    max_scan_size: Option<Number>,
+
+    /// Describe a file offset
+    #[structopt(long = "at", help = "Print what lives at this ELF file offset: the segments and sections containing it, its address, the nearest symbol, and any relocation of it")]
+    at: Option<Number>,
+
+    /// Show the first bytes of each section
+    #[structopt(long = "preview", help = "Show the first bytes of each ELF section as hex and ASCII, to tell strings from code, zeros, or compressed data at a glance")]
+    preview: bool,
+
+    /// List the FDEs in .eh_frame
+    #[structopt(long = "eh-frame", help = "List the start and length of each FDE in the ELF .eh_frame, with the function containing it, to find the functions of a stripped binary")]
+    eh_frame: bool,
+
+    /// Decode the Mach-o bind opcodes
+    #[structopt(long = "bind-opcodes", help = "List the bind, weak bind, and lazy bind opcodes of a Mach-o LC_DYLD_INFO as pseudo-assembly, with segments by name")]
+    bind_opcodes: bool,
+
+    /// Decode the Mach-o rebase opcodes
+    #[structopt(long = "rebase-opcodes", help = "List the rebase opcodes of a Mach-o LC_DYLD_INFO as pseudo-assembly, with segments by name")]
+    rebase_opcodes: bool,
+
+    /// Where in the file the binary starts
+    #[structopt(long = "offset", help = "Parse the binary starting at this file offset, e.g. 0x12000")]
+    offset: Option<Number>,
+
+    /// Decode the architecture sub-type
+    #[structopt(long = "arch-info", help = "Decode ELF e_flags and Mach-o cpu subtypes into the ABI, ISA level, float ABI, etc.")]
+    arch_info: bool,
+
+    /// Flag unusual dynamic linkers
+    #[structopt(long = "check-interp", help = "Flag an ELF interpreter that isn't a known Linux, Android, or BSD dynamic linker")]
+    check_interp: bool,
+
+    /// Check the ELF against the glibc it would run with
+    #[structopt(long = "check-abi", help = "Flag an ELF that needs a newer glibc than the libc.so.6 it resolves to, in --sysroot if given")]
+    check_abi: bool,
+
+    /// Check that an ELF is a position independent executable
+    #[structopt(long = "pie-check", help = "Print whether the ELF is a position independent executable and exit with 0 if it is, 7 if not, for CI scripts")]
+    pie_check: bool,
+
+    /// The dynamic linkers to accept instead of the built-in ones
+    #[structopt(long = "interp-whitelist", help = "Check the ELF interpreter against the paths in this file, one per line, instead of the built-in list (implies --check-interp)")]
+    interp_whitelist: Option<String>,
+
+    /// The color theme
+    #[structopt(long = "theme", default_value = "default", help = "Color the output with a built-in theme (default, light, or solarized), or a file of `role = color` lines; BINGREP_COLORS overrides its roles")]
+    theme: theme::Theme,
+
+    /// Don't color section headers
+    #[structopt(long = "no-color-headers", help = "Print section headers like `Syms(12)` without colors")]
+    no_color_headers: bool,
+
+    /// Show the GOT slots
+    #[structopt(long = "got", help = "Print only the ELF GOT slots, with their initial values and the relocations and symbols that fill them")]
+    got: bool,
+
+    /// Only show relocations of these types
+    #[structopt(long = "reloc-type", number_of_values = 1, help = "Only list ELF relocations of this type, e.g. R_X86_64_GLOB_DAT; can be given more than once")]
+    reloc_type: Vec<String>,
+
+    /// Only show relocations against matching symbols
+    #[structopt(long = "reloc-sym", help = "Only list ELF relocations whose symbol matches this pattern, where `*` matches anything and `?` any character")]
+    reloc_sym: Option<String>,
+
+    /// List the relocations against a symbol
+    #[structopt(long = "xrefs", help = "List every ELF relocation against symbols matching this pattern, where `*` matches anything and `?` any character")]
+    xrefs: Option<String>,
+
+    /// List the relocations pointing at an address
+    #[structopt(long = "xrefs-addr", help = "List every ELF relocation whose target, the symbol's value plus the addend, is this address, e.g. 0x4010")]
+    xrefs_addr: Option<Number>,
+
+    /// Show file offsets next to addresses
+    #[structopt(long = "both-addrs", help = "Show the file offset of each ELF symbol next to its address, through its section or else the PT_LOAD mapping it, e.g. for patching")]
+    both_addrs: bool,
+
+    /// Hide relative relocations
+    #[structopt(long = "no-relative", help = "Hide the R_*_RELATIVE relocations of position independent binaries")]
+    no_relative: bool,
+
+    /// Leave out empty listings
+    #[structopt(long = "skip-empty", help = "Leave out every listing with nothing in it, like the Dynamic Relas(0) of an object file, headers included")]
+    skip_empty: bool,
+
+    /// Collapse runs of the same relocation
+    #[structopt(long = "fold", help = "Collapse each run of consecutive relocations of the same type without a symbol, like the R_*_RELATIVE ones of a PIE, into one line with its count")]
+    fold: bool,
+
+    /// Show every relocation, even with --fold
+    #[structopt(long = "no-fold", help = "List every relocation on its own line, overriding --fold")]
+    no_fold: bool,
+
+    /// Treat the input as a flat code blob
+    #[structopt(long = "raw", help = "Disassemble the whole input as headerless code, e.g. shellcode; requires --arch")]
+    raw: bool,
+
+    /// The architecture of a --raw blob
+    #[structopt(long = "arch", help = "The architecture to disassemble --raw input as: x86, x86_64, arm, thumb, aarch64, mips, mipsel, mips64, ppc, ppc64, riscv32, or riscv64")]
+    arch: Option<disasm::Arch>,
+
+    /// The address a --raw blob is loaded at
+    #[structopt(long = "base", help = "The address the --raw input is loaded at, e.g. 0x400000 (default 0)")]
+    base: Option<Number>,
+
+    /// Where to write the output
+    #[structopt(short = "o", long = "output", alias = "output-file", help = "Write the output to this file instead of stdout, or to stdout for `-`; colors are off in a file unless --color is given")]
+    output: Option<String>,
+
+    /// Re-run whenever the input changes
+    #[structopt(short = "w", long = "watch", help = "Watch the binary and re-print the analysis when it changes; not with --offsets-only, --diff, --pie-check, or --diff-syms")]
+    watch: bool,
+
+    /// How deep to show archive members
+    #[structopt(long = "archive-depth", default_value = "1", help = "How far to recurse into archive members: 0 lists their names, 1 also shows the symbols of each object, 2 also lists the members of nested archives, etc.")]
+    archive_depth: usize,
+
+    /// Print hashes of the binary's structure
+    #[structopt(long = "hash", help = "Print fingerprint hashes used to cluster binaries, e.g. the richhash of a PE's Rich header")]
+    hash: bool,
+
+    /// Print the imphash of a PE
+    #[structopt(long = "imphash", help = "Print the imphash of a PE's imports, as pefile computes it; --hash prints it too")]
+    imphash: bool,
+
+    /// Print extra detail
+    #[structopt(short = "v", long = "verbose", help = "Print extra detail, e.g. the XML of a PE manifest")]
+    verbose: bool,
+
+    /// Needed parameter, the first on the command line.
+    #[structopt(help = "Binary file")]
+    input: String,
+
+    /// The second binary of --diff-syms
+    #[structopt(help = "The new binary, for --diff-syms and --diff")]
+    other: Option<String>,
+}
+
+impl Args {
+    /// The file `--output` names; `-` means stdout
+    fn output_file(&self) -> Option<&str> {
+        match self.output {
+            Some(ref output) if output != "-" => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Whether a listing of `count` entries is printed, which `--skip-empty` decides when there are none
+    fn shows(&self, count: usize) -> bool {
+        count != 0 || !self.skip_empty
+    }
+
+    /// Whether runs of relocations are collapsed, `--fold` without `--no-fold`
+    fn fold(&self) -> bool {
+        self.fold && !self.no_fold
+    }
+}
+
+/// The command line, and what displaying the binary it names has worked out so far
+#[derive(Debug, Clone)]
+struct Opt {
+    args: Args,
+    /// Demangled names by their mangled name, since binaries repeat names in their symbol tables, relocations, and
+    /// sections, and C++ template instantiations are slow to demangle; a mutex, since `parallel` renders rows on every core
+    demangled: ::std::sync::Arc<::std::sync::Mutex<::std::collections::HashMap<String, String>>>,
+    /// Whether to look for the libraries an ELF needs on this system; the fuzz target has none of its own to look on
+    find_libraries: bool,
+}
+
+impl Opt {
+    fn new(args: Args) -> Self {
+        Opt { args, demangled: Default::default(), find_libraries: true }
+    }
+}
+
+impl ::std::ops::Deref for Opt {
+    type Target = Args;
+    fn deref(&self) -> &Args {
+        &self.args
+    }
+}
+
+impl ::std::ops::DerefMut for Opt {
+    fn deref_mut(&mut self) -> &mut Args {
+        &mut self.args
+    }
+}
+
+fn new_table(title: Row) -> Table {
+    let sep = format::LineSeparator::new('-', '|', ' ', ' ');
+
+    let format = format::FormatBuilder::new()
+        .column_separator(' ')
+        .borders(' ')
+        .separators(&[], sep)
+        .padding(1, 1)
+        .build();
+
+    let mut phdr_table = Table::new();
+    phdr_table.set_titles(title);
+    phdr_table.set_format(format);
+    phdr_table
+}
+
+/// Whether tables should be colored; they always are when forced, otherwise only when printing to a terminal
+fn colorize_tables (opt: &Opt) -> bool {
+    use std::io::IsTerminal;
+    opt.color || (opt.output_file().is_none() && ::std::io::stdout().is_terminal())
+}
+
+/// Renders `table` into the formatter, so that it ends up wherever the rest of the output goes
+fn fmt_table (fmt: &mut ::std::fmt::Formatter, opt: &Opt, table: &Table) -> ::std::fmt::Result {
+    if !opt.shows(table.len()) {
+        return Ok(());
+    }
+    if colorize_tables(opt) {
+        use term::Terminal;
+        if let Some(mut term) = term::TerminfoTerminal::new(Vec::new()) {
+            table.print_term(&mut term).map_err(|_| ::std::fmt::Error)?;
+            return write!(fmt, "{}", String::from_utf8_lossy(&term.into_inner()));
+        }
+    }
+    write!(fmt, "{}", table)
+}
+
+fn string_cell (opt: &Opt, s: &str) -> Cell {
+    if s.is_empty() {
+        Cell::new(&"")
+    } else {
+        // uppercase is the bright variant
+        Cell::new(&symbol_name(opt, s)).style_spec(&format!("F{}b", theme::spec(opt.theme.string).to_ascii_uppercase()))
+    }
+}
+
+/// How the addresses of one binary are laid out: in columns as wide as its words, so 32-bit binaries get 8
+/// hex digits instead of 16
+#[derive(Debug, Copy, Clone)]
+struct Layout {
+    width: usize,
+    /// Whether addresses and offsets are zero padded to `width`, for `--pad-addresses`
+    pad: bool,
+    colors: theme::Theme,
+    /// Whether a set low bit in the address of a function means it is Thumb code, as on ARM
+    thumb: bool,
+}
+
+/// Marks a function address whose Thumb bit was cleared
+const THUMB_MARK: &str = "[T]";
+
+impl Layout {
+    fn new (opt: &Opt, is_64: bool) -> Self {
+        Layout { width: if is_64 { 16 } else { 8 }, pad: opt.pad_addresses, colors: opt.theme, thumb: false }
+    }
+
+    fn elf (opt: &Opt, elf: &elf::Elf) -> Self {
+        Layout { thumb: elf.header.e_machine == elf::header::EM_ARM, ..Layout::new(opt, elf.is_64) }
+    }
+
+    /// An address or offset in hex, led by `0x` if `prefix`, and zero padded if `--pad-addresses` is on
+    fn hex (&self, n: u64, prefix: bool) -> String {
+        match (self.pad, prefix) {
+            (false, true) => format!("{:#x}", n),
+            (false, false) => format!("{:x}", n),
+            (true, true) => format!("{:#01$x}", n, self.width + 2),
+            (true, false) => format!("{:01$x}", n, self.width),
+        }
+    }
+
+    /// The address of Thumb code that `addr` is, if it is a function's address with its Thumb bit set
+    fn thumb_addr (&self, addr: u64, is_func: bool) -> Option<u64> {
+        if self.thumb && is_func && addr & 1 == 1 { Some(addr & !1) } else { None }
+    }
+
+    /// An address, right aligned in an address column
+    fn addr (&self, addr: u64) -> colored::ColoredString {
+        theme::paint(format!("{:>1$}", self.hex(addr, false), self.width).as_str(), self.colors.addr)
+    }
+
+    fn addr_cell (&self, addr: u64) -> Cell {
+        Cell::new(&format!("{:>1$} ", self.hex(addr, false), self.width)).style_spec(&format!("F{}r", theme::spec(self.colors.addr)))
+    }
+
+    /// The address `addr` of a symbol in an address column, with the Thumb bit of a function cleared and marked
+    /// `[T]`; the column is widened for the mark when the binary can have Thumb code
+    fn func_addr (&self, addr: u64, is_func: bool) -> String {
+        match self.thumb_addr(addr, is_func) {
+            Some(addr) => format!("{} {}", self.addr(addr), THUMB_MARK.cyan()),
+            None if self.thumb => format!("{} {:2$}", self.addr(addr), "", THUMB_MARK.len()),
+            None => self.addr(addr).to_string(),
+        }
+    }
+
+    fn func_addr_cell (&self, addr: u64, is_func: bool) -> Cell {
+        match self.thumb_addr(addr, is_func) {
+            Some(addr) => Cell::new(&format!("{:>1$} {2} ", self.hex(addr, false), self.width, THUMB_MARK)).style_spec("Fcr"),
+            None if self.thumb => Cell::new(&format!("{:>1$} {2:3$} ", self.hex(addr, false), self.width, "", THUMB_MARK.len())).style_spec(&format!("F{}r", theme::spec(self.colors.addr))),
+            None => self.addr_cell(addr),
+        }
+    }
+
+    /// A code address like the entry point, with its Thumb bit cleared and marked `[T]`
+    fn codex (&self, addr: u64) -> String {
+        match self.thumb_addr(addr, true) {
+            Some(addr) => format!("{} {}", self.addrx(addr), THUMB_MARK.cyan()),
+            None => self.addrx(addr).to_string(),
+        }
+    }
+
+    /// An address on its own, without the `0x`
+    fn bare_addr (&self, addr: u64) -> colored::ColoredString {
+        theme::paint(self.hex(addr, false).as_str(), self.colors.addr)
+    }
+
+    fn addrx (&self, addr: u64) -> colored::ColoredString {
+        theme::paint(self.hex(addr, true).as_str(), self.colors.addr)
+    }
+
+    fn off (&self, off: u64) -> colored::ColoredString {
+        theme::paint(self.hex(off, true).as_str(), self.colors.offset)
+    }
+
+    fn offs (&self, off: isize) -> colored::ColoredString {
+        theme::paint(self.hex(off as u64, true).as_str(), self.colors.offset)
+    }
+
+    fn sz (&self, sz: u64) -> colored::ColoredString {
+        theme::paint(format!("{:#x}", sz).as_str(), self.colors.size)
+    }
+
+    fn idx (&self, i: usize) -> colored::ColoredString {
+        let colors = &self.colors;
+        let index = format!("{:>4}", i);
+        if i.is_multiple_of(2) { theme::paint_bg(theme::paint(index.as_str(), colors.index), colors.index_alt) } else { theme::paint_bg(theme::paint(index.as_str(), colors.index_alt), colors.index) }
+    }
+
+    fn offsetx_cell (&self, offset: u64) -> Cell {
+        Cell::new(&format!("{} ", self.hex(offset, true))).style_spec(&format!("F{}", theme::spec(self.colors.offset)))
+    }
+
+    fn addrx_cell (&self, addr: u64) -> Cell {
+        Cell::new(&format!("{} ", self.hex(addr, true))).style_spec(&format!("F{}", theme::spec(self.colors.addr)))
+    }
+
+    fn memx_cell (&self, maddr: u64) -> Cell {
+        Cell::new(&format!("{} ", self.hex(maddr, true))).style_spec(&format!("bF{}", theme::spec(self.colors.addr)))
+    }
+
+    fn sz_cell (&self, size: u64) -> Cell {
+        Cell::new(&format!("{:<#x} ", size)).style_spec(&format!("F{}", theme::spec(self.colors.size)))
+    }
+
+    fn memsz_cell (&self, memsz: u64) -> Cell {
+        Cell::new(&format!("{:<#x} ", memsz)).style_spec(&format!("bF{}", theme::spec(self.colors.size)))
+    }
+
+    fn idx_cell (&self, i: usize) -> Cell {
+        let cell = Cell::new(&i.to_string());
+        let (index, index_alt) = (theme::spec(self.colors.index), theme::spec(self.colors.index_alt));
+        if i.is_multiple_of(2) { cell.style_spec(&format!("F{}B{}", index_alt, index)) } else { cell.style_spec(&format!("F{}B{}", index, index_alt)) }
+    }
+}
+
+fn x_cell (num: u64) -> Cell {
+    Cell::new(&format!("{:#x}", num))
+}
+
+/// Printed for a name whose index is out of bounds, as in a truncated or corrupt binary
+const BAD_INDEX: &str = "<bad index>";
+
+/// The string at `offset` in `strtab`, or `<bad index>` when it is out of bounds
+fn strtab_get<'a> (strtab: &goblin::strtab::Strtab<'a>, offset: usize) -> &'a str {
+    strtab.get(offset).unwrap_or(BAD_INDEX)
+}
+
+fn shndx_cell (idx: usize, shdrs: &elf::SectionHeaders, strtab: &goblin::strtab::Strtab) -> Cell {
+    if idx >= shdrs.len() {
+        if idx == 0xfff1 { // associated symbol is absolute, todo, move this to goblin
+            Cell::new(&format!("ABS")).style_spec("iFw")
+        } else {
+            Cell::new(&format!("BAD_IDX={}", idx)).style_spec("irFw")
+        }
+    } else if idx != 0 {
+        let shdr = &shdrs[idx];
+        let link_name = strtab_get(strtab, shdr.sh_name);
+        Cell::new(&format!("{}({})", link_name, idx))
+    } else {
+        Cell::new("")
+    }
+}
+
+/// The visibility encoded in `st_other`, along with the raw value if any other bits are set
+fn visibility (st_other: u8) -> String {
+    if st_other & !0x3 != 0 {
+        format!("{} ({:#x})", visibility_to_str(st_other), st_other)
+    } else {
+        visibility_to_str(st_other).to_owned()
+    }
+}
+
+fn visibility_cell (st_other: u8) -> Cell {
+    let cell = Cell::new(&visibility(st_other));
+    match st_other & 0x3 {
+        STV_HIDDEN => cell.style_spec("FD"),
+        STV_PROTECTED => cell.style_spec("Fy"),
+        _ => cell,
+    }
+}
+
+fn hdr (colors: &theme::Theme, name: &str) -> colored::ColoredString {
+    if colors.color_headers { theme::paint(name.to_string().dimmed(), colors.header).underline() } else { name.normal() }
+}
+
+fn hdr_size (colors: &theme::Theme, name: &str, size: usize) -> colored::ColoredString {
+    hdr(colors, &format!("{}({})", name, size))
+}
+
+fn fmt_header (fmt: &mut ::std::fmt::Formatter, colors: &theme::Theme, name: &str, size: usize) -> ::std::fmt::Result {
+    writeln!(fmt, "{}:\n", hdr_size(colors, name, size))?;
+    Ok(())
+}
+
+/// Like `fmt_header`, but with how many of the `total` entries are listed, if the filters hid some
+fn fmt_filtered_header (fmt: &mut ::std::fmt::Formatter, colors: &theme::Theme, name: &str, shown: usize, total: usize) -> ::std::fmt::Result {
+    if shown == total {
+        fmt_header(fmt, colors, name, total)
+    } else {
+        writeln!(fmt, "{}:\n", hdr(colors, &format!("{}({}/{})", name, shown, total)))
+    }
+}
+
+/// Whether an entry of `size` bytes passes `--min-size` and `--max-size`
+fn size_shown (opt: &Opt, size: u64) -> bool {
+    opt.min_size.is_none_or(|Number(min)| size >= min) && opt.max_size.is_none_or(|Number(max)| size <= max)
+}
+
+/// The `--total-size` line below a table of entries of `sizes`
+fn fmt_total_size<I: Iterator<Item = u64>> (fmt: &mut ::std::fmt::Formatter, opt: &Opt, layout: Layout, sizes: I, prefix: &str) -> ::std::fmt::Result {
+    if opt.total_size {
+        writeln!(fmt, "{}total size: {}", prefix, layout.sz(sizes.fold(0u64, |total, size| total.saturating_add(size))))?;
+    }
+    Ok(())
+}
+
+fn fmt_arch_info (fmt: &mut ::std::fmt::Formatter, colors: &theme::Theme, info: &[(&str, String)]) -> ::std::fmt::Result {
+    fmt_header(fmt, colors, "Arch", info.len())?;
+    for &(label, ref value) in info {
+        let value = if label == "unknown" { value.red() } else { value.normal() };
+        writeln!(fmt, "{:>16} {}", label.bold(), value)?;
+    }
+    writeln!(fmt)
+}
+
+/// The full 32-bit `cpusubtype`, which goblin splits into bytes, in file order for 64-bit headers but not for 32-bit ones
+fn mach_cpusubtype (mach: &mach::MachO) -> u32 {
+    let header = &mach.header;
+    let bytes = if mach.is_64 {
+        [header.cpusubtype, header.padding1, header.padding2, header.caps]
+    } else {
+        [header.padding1, header.padding2, header.caps, header.cpusubtype]
+    };
+    if mach.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) }
+}
+
+fn fmt_gopclntab (fmt: &mut ::std::fmt::Formatter, opt: &Opt, layout: Layout, pclntab: &gopclntab::Pclntab) -> ::std::fmt::Result {
+    let colors = &opt.theme;
+    fmt_header(fmt, colors, "Go Syms", pclntab.funcs.len())?;
+    if opt.pretty {
+        let mut table = new_table(row![br->"Addr", b->"Symbol"]);
+        for func in &pclntab.funcs {
+            table.add_row(Row::new(vec![
+                layout.addr_cell(func.entry),
+                string_cell(opt, func.name),
+            ]));
+        }
+        fmt_table(fmt, opt, &table)?;
+    } else {
+        for func in &pclntab.funcs {
+            writeln!(fmt, "{} {}", layout.addr(func.entry), string(opt, func.name))?;
+        }
+    }
+    writeln!(fmt)?;
+
+    fmt_header(fmt, colors, "Go Files", pclntab.files.len())?;
+    for file in &pclntab.files {
+        writeln!(fmt, "{:>2$} {}", "", file, layout.width)?;
+    }
+    writeln!(fmt)
+}
+
+/// The offsets of every occurrence of `search` in `bytes`
+fn find_all (bytes: &[u8], search: &str) -> Vec<usize> {
+    let search = search.as_bytes();
+    if search.is_empty() {
+        return Vec::new();
+    }
+    bytes.windows(search.len()).enumerate().filter(|&(_, window)| window == search).map(|(i, _)| i).collect()
+}
+
+/// The most bytes of the string at a `--search` match that are shown
+const MATCH_STRING_MAX: usize = 256;
+
+/// The string a `--search` match starts, up to its NUL or first unprintable byte, which tells apart the longer strings a
+/// short search term is in
+fn match_string (bytes: &[u8], offset: usize) -> String {
+    let rest = bytes.get(offset..).unwrap_or(&[]);
+    let len = rest.iter().take(MATCH_STRING_MAX).take_while(|&&byte| is_printable(byte)).count();
+    let mut string = String::from_utf8_lossy(&rest[..len]).into_owned();
+    if len == MATCH_STRING_MAX && rest.get(len).is_some_and(|&byte| is_printable(byte)) {
+        string.push_str("..");
+    }
+    string
+}
+
+/// The symbol name as it will be displayed, i.e., demangled if requested
+fn symbol_name (opt: &Opt, s: &str) -> String {
+    if !opt.demangle {
+        return s.into();
+    }
+    if let Some(demangled) = opt.demangled.lock().unwrap().get(s) {
+        return demangled.clone();
+    }
+    let demangled = demangle(s);
+    opt.demangled.lock().unwrap().insert(s.to_owned(), demangled.clone());
+    demangled
+}
+
+/// The Rust or C++ demangling of `s`, or `s` if it isn't mangled
+fn demangle (s: &str) -> String {
+    let rust = rustc_demangle::demangle(s).to_string();
+    if rust != s {
+        return rust;
+    }
+    // Rust's legacy mangling is also an Itanium `_ZN` name, so C++ only gets what rustc_demangle leaves alone;
+    // cpp_demangle also takes bare type manglings, which would turn a symbol like `i` into `int`
+    if !s.starts_with("_Z") && !s.starts_with("__Z") {
+        return rust;
+    }
+    match cpp_demangle::Symbol::new(s) {
+        Ok(symbol) => symbol.to_string(),
+        Err(_) => rust,
+    }
+}
+
+/// The section name as it will be displayed, where the symbol of a per symbol section, like the
+/// `.text._ZN3foo3barEv` of `-ffunction-sections`, is demangled if requested
+fn section_name (opt: &Opt, name: &str) -> String {
+    match name.find("._") {
+        Some(dot) if opt.demangle && dot > 0 => format!("{}{}", &name[..dot + 1], symbol_name(opt, &name[dot + 1..])),
+        _ => name.into(),
+    }
+}
+
+fn string (opt: &Opt, s: &str) -> colored::ColoredString {
+    theme::paint(symbol_name(opt, s).as_str().reverse().bold(), opt.theme.string)
+}
+
+/// Truncates `s` to `width` characters, unless `--full-names` was passed
+fn truncate (opt: &Opt, s: &str, width: usize) -> String {
+    if opt.full_names { s.to_owned() } else { format!("{:.*}", width, s) }
+}
+
+/// The width of a name column; the widest of `names` when `--full-names` was passed
+fn name_width<S: AsRef<str>, I: Iterator<Item = S>> (opt: &Opt, names: I, width: usize) -> usize {
+    if opt.full_names { names.fold(width, |width, name| ::std::cmp::max(width, name.as_ref().len())) } else { width }
+}
+
+/// Printed for a Mach-o segment whose name isn't UTF-8
+const BAD_SEGMENT_NAME: &str = "<bad segment name>";
+
+/// Lists the rebase or bind opcodes of the `LC_DYLD_INFO` stream `name`, naming the segments by their index in `segments`
+fn fmt_dyld_opcodes (fmt: &mut ::std::fmt::Formatter, opt: &Opt, layout: Layout, name: &str, opcodes: error::Result<Vec<dyld_info::Opcode>>, segments: &[&str]) -> ::std::fmt::Result {
+    use dyld_info::Operand;
+    let colors = &opt.theme;
+    let opcodes = match opcodes {
+        Ok(opcodes) => opcodes,
+        Err(err) => {
+            fmt_header(fmt, colors, name, 0)?;
+            writeln!(fmt, "{}", format!("{}", err).red())?;
+            return writeln!(fmt);
+        },
+    };
+    let operand = |operand: &Operand| match *operand {
+        Operand::Number(n) => format!("{:#x}", n),
+        Operand::Count(n) => n.to_string(),
+        Operand::Addend(n) => n.to_string(),
+        Operand::Ordinal(ordinal) => dyld_info::ordinal_to_str(ordinal),
+        Operand::Segment(segment) => format!("{}({})", segments.get(segment as usize).cloned().unwrap_or(BAD_INDEX), segment),
+        Operand::Type(typ) => dyld_info::type_to_str(typ).to_owned(),
+        Operand::Flags(flags) => format!("{:#x}", flags),
+        Operand::Symbol(symbol) => symbol_name(opt, symbol),
+    };
+    fmt_header(fmt, colors, name, opcodes.len())?;
+    if opt.pretty {
+        let mut table = new_table(row![b->"Offset", b->"Opcode", b->"Operands"]);
+        for opcode in &opcodes {
+            table.add_row(Row::new(vec![
+                layout.offsetx_cell(opcode.offset as u64),
+                Cell::new(opcode.name).style_spec("Fc"),
+                Cell::new(&opcode.operands.iter().map(&operand).collect::<Vec<_>>().join(", ")),
+            ]));
+        }
+        fmt_table(fmt, opt, &table)?;
+    } else {
+        for opcode in &opcodes {
+            let operands = opcode.operands.iter().map(&operand).collect::<Vec<_>>();
+            if operands.is_empty() {
+                writeln!(fmt, "{:>8} {}", layout.off(opcode.offset as u64), opcode.name.cyan())?;
+            } else {
+                writeln!(fmt, "{:>8} {:<40} {}", layout.off(opcode.offset as u64), opcode.name.cyan(), operands.join(", "))?;
+            }
+        }
+    }
+    writeln!(fmt)
+}
+
+/// A Mach-o binary, with its own bytes, i.e., its slice of a fat binary
+struct MachO<'a>(&'a mach::MachO<'a>, Opt, &'a [u8]);
+
+impl<'a> ::std::fmt::Display for MachO<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.1.theme;
+        use mach::header;
+        use mach::load_command;
+        use mach::exports::{Export};
+
+        let mach = &self.0;
+        let opt = &self.1;
+        let layout = Layout::new(opt, mach.is_64);
+        let header = &mach.header;
+        let endianness = if header.is_little_endian() { "little-endian" } else { "big-endian" };
+        let kind = {
+            let typ_cell = header.filetype;
+            let kind_str = header::filetype_to_str(typ_cell).reverse().bold();
+            match typ_cell {
+                header::MH_OBJECT =>  kind_str.yellow(),
+                header::MH_EXECUTE => kind_str.red(),
+                header::MH_DYLIB =>  kind_str.blue(),
+                header::MH_DYLINKER =>  kind_str.yellow(),
+                header::MH_DYLIB_STUB =>  kind_str.blue(),
+                header::MH_DSYM =>  kind_str.green(),
+                header::MH_CORE => kind_str.black(),
+                _ => kind_str.normal(),
+            }
+        };
+        let machine = header.cputype;
+        let machine_str = {
+            mach::constants::cputype::cpu_type_to_str(machine).bold()
+        };
+        let gopclntab = mach.segments.sections().ok()
+            .and_then(|sections| sections.into_iter().flatten().find(|section| section.name().ok() == Some(gopclntab::MACH_SECTION)))
+            .and_then(|section| gopclntab::parse(section.data, mach.little_endian).ok());
+        write!(fmt, "{} {} {}-{} @ {}",
+                 hdr(colors, "Mach-o"),
+                 kind,
+                 machine_str,
+                 endianness,
+                 layout.addrx(mach.entry),
+        )?;
+        if let Some(ref pclntab) = gopclntab {
+            write!(fmt, " {}", pclntab.version.as_str().cyan().bold())?;
+        }
+        writeln!(fmt, ":")?;
+        writeln!(fmt, "")?;
+
+        if opt.arch_info {
+            fmt_arch_info(fmt, colors, &arch::mach_subtype(header.cputype, mach_cpusubtype(mach)))?;
+        }
+
+        let lcs = &mach.load_commands;
+        fmt_header(fmt, colors, "LoadCommands", mach.load_commands.len())?;
+        let lc_width = name_width(opt, lcs.iter().map(|lc| load_command::cmd_to_str(lc.command.cmd())), 27);
+        for (i, lc) in lcs.into_iter().enumerate() {
+            let name = {
+                let name = load_command::cmd_to_str(lc.command.cmd());
+                let name = truncate(opt, name, 27);
+                match lc.command {
+                    load_command::CommandVariant::Segment32        (_command) => name.red(),
+                    load_command::CommandVariant::Segment64        (_command) => name.red(),
+                    load_command::CommandVariant::Symtab           (_command) => name.yellow(),
+                    load_command::CommandVariant::Dysymtab         (_command) => name.green(),
+                    load_command::CommandVariant::LoadDylinker     (_command) => name.yellow(),
+                    load_command::CommandVariant::LoadDylib        (_command)
+                    | load_command::CommandVariant::LoadUpwardDylib(_command)
+                    | load_command::CommandVariant::ReexportDylib  (_command)
+                    | load_command::CommandVariant::LazyLoadDylib  (_command) => name.blue(),
+                    load_command::CommandVariant::DyldInfo         (_command)
+                    | load_command::CommandVariant::DyldInfoOnly   (_command) => name.cyan(),
+                    load_command::CommandVariant::Unixthread       (_command) => name.red(),
+                    load_command::CommandVariant::Main             (_command) => name.red(),
+                    _ => name.normal(),
+                }
+            };
+            write!(fmt, "{} ", layout.idx(i))?;
+            writeln!(fmt, "{:<width$} ", name, width = lc_width)?;
+        }
+
+        writeln!(fmt, "")?;
+        let fmt_section = |fmt: &mut ::std::fmt::Formatter, i: usize, section: &load_command::Section | -> ::std::fmt::Result {
+            if let Ok(name) = section.name() {
+                write!(fmt,   "    {}: {:>16}", layout.idx(i), string(opt, name))?;
+                write!(fmt,   "    addr: {:>8} ",     layout.addrx(section.addr))?;
+                write!(fmt,   "    size: {:>8} ",     layout.sz(section.size))?;
+                write!(fmt,   "    offset: {:>8} ",   layout.off(section.offset as u64))?;
+                write!(fmt,   "    align: {} ",    section.align)?;
+                write!(fmt,   "    reloff: {} ",   layout.off(section.reloff as u64))?;
+                write!(fmt,   "    nreloc: {} ",   section.nreloc)?;
+                write!(fmt,   "    flags: {:#10x} ",    section.flags)?;
+                writeln!(fmt, "    data: {}",    layout.sz(section.data.len() as u64))
+            } else {
+                writeln!(fmt,   "    {}: {:>16}", layout.idx(i), "BAD SECTION NAME")
+            }
+        };
+
+        let fmt_sections = |fmt: &mut ::std::fmt::Formatter, name: &str, sections: &[load_command::Section] | -> ::std::fmt::Result {
+            writeln!(fmt, "  {}", hdr_size(colors, name, sections.len()).yellow())?;
+            let mut sections = sections.iter().enumerate().collect::<Vec<_>>();
+            match opt.sort_sections {
+                Some(SortSections::Offset) => sections.sort_by_key(|&(_, section)| section.offset),
+                Some(SortSections::Addr) => sections.sort_by_key(|&(_, section)| section.addr),
+                Some(SortSections::Size) => sections.sort_by_key(|&(_, section)| ::std::cmp::Reverse(section.size)),
+                Some(SortSections::Name) => sections.sort_by(|&(_, a), &(_, b)| a.name().unwrap_or("").cmp(b.name().unwrap_or(""))),
+                None => (),
+            }
+            for (i, section) in sections {
+                fmt_section(fmt, i, section)?;
+            }
+            Ok(())
+        };
+
+        let segments = &*mach.segments;
+        fmt_header(fmt, colors, "Segments", segments.len())?;
+        for (ref i, ref segment) in segments.into_iter().enumerate() {
+            write!(fmt, "  {}:",     layout.idx(*i))?;
+            let name = segment.name().unwrap_or(BAD_SEGMENT_NAME);
+            match segment.sections() {
+                Ok(sections) => fmt_sections(fmt, name, &sections)?,
+                Err(err) => writeln!(fmt, "  {} {}", hdr(colors, name).yellow(), format!("{}", err).red())?,
+            }
+        }
+
+        writeln!(fmt, "")?;
+
+        let bitcode = segments.iter()
+            .filter(|segment| segment.name().ok() == Some(bitcode::MACH_SEGMENT))
+            .filter_map(|segment| segment.sections().ok())
+            .flat_map(|sections| sections.into_iter())
+            .filter(|section| section.name().ok().is_some_and(|name| bitcode::MACH_SECTIONS.contains(&name)))
+            .collect::<Vec<_>>();
+        if !bitcode.is_empty() {
+            fmt_header(fmt, colors, "Embedded Bitcode", bitcode.len())?;
+            for section in bitcode {
+                write!(fmt, "{},{} offset: {} size: {}", bitcode::MACH_SEGMENT, section_name(opt, section.name().unwrap_or(BAD_SEGMENT_NAME)), layout.off(section.offset as u64), layout.sz(section.size))?;
+                // -fembed-bitcode-marker leaves a single byte where the bitcode would go
+                if section.size <= 1 {
+                    write!(fmt, " {}", "(marker only)".dimmed())?;
+                }
+                writeln!(fmt)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        let fmt_exports = |fmt: &mut ::std::fmt::Formatter, name: &str, syms: &[Export] | -> ::std::fmt::Result {
+            if !opt.shows(syms.len()) {
+                return Ok(());
+            }
+            fmt_header(fmt, colors, name, syms.len())?;
+            for sym in syms {
+                write!(fmt, "{} ", layout.addr(sym.offset))?;
+                write!(fmt, "{} ", string(opt, &sym.name))?;
+                writeln!(fmt, "({})", layout.sz(sym.size as u64))?;
+            }
+            writeln!(fmt, "")
+        };
+
+        let exports = match mach.exports () { Ok(exports) => exports, Err(_) => Vec::new() };
+        fmt_exports(fmt, "Exports", &exports)?;
+
+        let imports = match mach.imports () { Ok(imports) => imports, Err(_) => Vec::new() };
+        if opt.shows(imports.len()) {
+            fmt_header(fmt, colors, "Imports", imports.len())?;
+            for sym in imports {
+                // imports are found by their file offset, not their address; the column is as wide as the exports'
+                write!(fmt, "{:>1$} ", layout.off(sym.offset), layout.width)?;
+                write!(fmt, "{} ", string(opt, sym.name))?;
+                write!(fmt, "({}) ", layout.sz(sym.size as u64))?;
+                writeln!(fmt, "-> {}", string(opt, sym.dylib).blue())?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if opt.rebase_opcodes || opt.bind_opcodes {
+            let segments = mach.segments.iter().map(|segment| segment.name().unwrap_or(BAD_SEGMENT_NAME)).collect::<Vec<_>>();
+            match dyld_info::command(mach) {
+                Some(command) => {
+                    if opt.rebase_opcodes {
+                        let opcodes = dyld_info::stream(self.2, command.rebase_off, command.rebase_size).and_then(dyld_info::rebase);
+                        fmt_dyld_opcodes(fmt, opt, layout, "Rebase Opcodes", opcodes, &segments)?;
+                    }
+                    if opt.bind_opcodes {
+                        let streams = [
+                            ("Bind Opcodes", command.bind_off, command.bind_size, false),
+                            ("Weak Bind Opcodes", command.weak_bind_off, command.weak_bind_size, false),
+                            ("Lazy Bind Opcodes", command.lazy_bind_off, command.lazy_bind_size, true),
+                        ];
+                        for &(name, offset, size, lazy) in &streams {
+                            let opcodes = dyld_info::stream(self.2, offset, size).and_then(|data| dyld_info::bind(data, lazy));
+                            fmt_dyld_opcodes(fmt, opt, layout, name, opcodes, &segments)?;
+                        }
+                    }
+                },
+                None => {
+                    writeln!(fmt, "{}", "no LC_DYLD_INFO, the binary may use chained fixups instead".dimmed())?;
+                    writeln!(fmt)?;
+                },
+            }
+        }
+
+        // the symbol table, for naming the function starts, the stabs, and whether the binary is stripped
+        let symbols = nlist::parse(self.2, mach);
+        match function_starts::parse(self.2, mach) {
+            Ok(Some(functions)) => {
+                // the symbol at each start, if the binary isn't stripped; external names win over local ones
+                let mut names = ::std::collections::HashMap::new();
+                if let Ok(ref symbols) = symbols {
+                    for symbol in symbols.iter().filter(|symbol| !symbol.is_stab() && symbol.n_type & nlist::N_TYPE == nlist::N_SECT && !symbol.name.is_empty()) {
+                        if symbol.is_extern() || !names.contains_key(&symbol.n_value) {
+                            names.insert(symbol.n_value, symbol.name);
+                        }
+                    }
+                }
+                fmt_header(fmt, colors, "Function Starts", functions.len())?;
+                if opt.pretty {
+                    let mut table = new_table(row![b->"Address", b->"Symbol"]);
+                    for function in &functions {
+                        let name = match names.get(function) {
+                            Some(name) => string_cell(opt, name),
+                            None => Cell::new("<unknown>").style_spec("i"),
+                        };
+                        table.add_row(Row::new(vec![layout.addr_cell(*function), name]));
+                    }
+                    fmt_table(fmt, opt, &table)?;
+                } else {
+                    for function in &functions {
+                        match names.get(function) {
+                            Some(name) => writeln!(fmt, "{} {}", layout.addr(*function), string(opt, name))?,
+                            None => writeln!(fmt, "{} {}", layout.addr(*function), "<unknown>".dimmed())?,
+                        }
+                    }
+                }
+                writeln!(fmt)?;
+            },
+            Ok(None) => (),
+            Err(err) => {
+                writeln!(fmt, "{}: {}", hdr(colors, "Function Starts"), format!("{}", err).red())?;
+                writeln!(fmt)?;
+            },
+        }
+
+        let stabs = symbols.as_ref().map(|symbols| symbols.iter().filter(|symbol| symbol.is_stab()).collect::<Vec<_>>()).unwrap_or_default();
+        if !stabs.is_empty() {
+            // n_sect counts the sections of all the segments from 1
+            let sections = segments.iter()
+                .flat_map(|segment| segment.sections().unwrap_or(Vec::new()).into_iter())
+                .map(|section| format!("{},{}", section.segname().unwrap_or(BAD_SEGMENT_NAME), section.name().unwrap_or(BAD_SEGMENT_NAME)))
+                .collect::<Vec<_>>();
+            let section = |n_sect: u8| match n_sect {
+                0 => String::new(),
+                n_sect => sections.get(n_sect as usize - 1).cloned().unwrap_or_else(|| format!("{}({})", BAD_INDEX, n_sect)),
+            };
+            fmt_header(fmt, colors, "Stabs Debug Symbols", stabs.len())?;
+            if opt.pretty {
+                let mut table = new_table(row![b->"Value", b->"Type", b->"Section", br->"Desc", b->"Name"]);
+                for stab in &stabs {
+                    table.add_row(Row::new(vec![
+                        layout.addr_cell(stab.n_value),
+                        Cell::new(nlist::stab_to_str(stab.n_type)).style_spec("Fc"),
+                        Cell::new(&section(stab.n_sect)),
+                        Cell::new(&format!("{:#x}", stab.n_desc)).style_spec("r"),
+                        string_cell(opt, stab.name),
+                    ]));
+                }
+                fmt_table(fmt, opt, &table)?;
+            } else {
+                for stab in &stabs {
+                    write!(fmt, "{} {:<9} {:<24} ", layout.addr(stab.n_value), nlist::stab_to_str(stab.n_type).cyan(), section(stab.n_sect))?;
+                    // the line number of a N_SLINE is in n_desc
+                    if stab.n_type == nlist::N_SLINE {
+                        write!(fmt, "line: {:<6} ", stab.n_desc)?;
+                    } else {
+                        write!(fmt, "n_desc: {:#06x} ", stab.n_desc)?;
+                    }
+                    writeln!(fmt, "{}", string(opt, stab.name))?;
+                }
+            }
+            writeln!(fmt)?;
+        }
+
+        if let Some(ref pclntab) = gopclntab {
+            fmt_gopclntab(fmt, opt, layout, pclntab)?;
+        }
+
+        let tls_sections = mach.segments.sections().unwrap_or_default().into_iter().flatten().filter(|section| {
+            let typ = section.flags & mach::constants::SECTION_TYPE;
+            typ == mach::constants::S_THREAD_LOCAL_REGULAR || typ == mach::constants::S_THREAD_LOCAL_ZEROFILL || typ == mach::constants::S_THREAD_LOCAL_VARIABLES
+        }).collect::<Vec<_>>();
+        if !tls_sections.is_empty() {
+            fmt_header(fmt, colors, "TLS", tls_sections.len())?;
+            // a tlv descriptor is a thunk, a key, and an offset into the template
+            let sizeof_tlv_descriptor = if mach.header.container() == container::Container::Big { 24 } else { 12 };
+            for (i, section) in tls_sections.iter().enumerate() {
+                let name = section.name().unwrap_or("BAD SECTION NAME");
+                write!(fmt, "{} {:>16} ", layout.idx(i), string(opt, name))?;
+                write!(fmt, "addr: {} ", layout.addrx(section.addr))?;
+                write!(fmt, "size: {} ", layout.sz(section.size))?;
+                write!(fmt, "align: {:#x} ", 1u64.checked_shl(section.align).unwrap_or(0))?;
+                if section.flags & mach::constants::SECTION_TYPE == mach::constants::S_THREAD_LOCAL_VARIABLES {
+                    write!(fmt, "descriptors: {}", section.size / sizeof_tlv_descriptor)?;
+                }
+                writeln!(fmt)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        let rpaths = mach.load_commands.iter().filter_map(|lc| match lc.command {
+            load_command::CommandVariant::Rpath(command) => Some(self.2.pread::<&str>(lc.offset + command.path as usize).unwrap_or("")),
+            _ => None,
+        }).collect::<Vec<_>>();
+        // the prefixes dyld substitutes; anything else that isn't absolute is relative to the working directory
+        let special = |path: &str| ["@executable_path", "@loader_path", "@rpath"].iter().cloned().find(|prefix| path.starts_with(prefix));
+
+        let rpath_libs = mach.libs[1..].iter().filter(|lib| special(lib) == Some("@rpath")).count();
+        if rpath_libs == 0 {
+            fmt_header(fmt, colors, "Libraries", mach.libs.len())?;
+        } else {
+            writeln!(fmt, "{} {}:\n", hdr_size(colors, "Libraries", mach.libs.len()), format!("[{} @rpath relative]", rpath_libs).magenta())?;
+        }
+        for lib in &mach.libs[1..] {
+            match special(lib) {
+                // dyld tries each LC_RPATH in turn, so the first directory with a library of this name wins
+                Some("@rpath") if rpaths.is_empty() => writeln!(fmt, "{:>16} {}", string(opt, lib).blue(), "(@rpath relative, but there are no rpaths)".red())?,
+                Some("@rpath") => writeln!(fmt, "{:>16} {}", string(opt, lib).blue(), "(@rpath relative, searched through the rpaths)".magenta())?,
+                Some(_) => writeln!(fmt, "{:>16} {}", string(opt, lib).blue(), "(loader relative)".cyan())?,
+                None => writeln!(fmt, "{:>16} ", string(opt, lib).blue())?,
+            }
+        }
+        writeln!(fmt)?;
+
+        let specials = rpaths.iter().filter(|path| special(path).is_some()).count();
+        let absolutes = rpaths.iter().filter(|path| path.starts_with('/')).count();
+        let relatives = rpaths.len() - specials - absolutes;
+        let counts = format!("[{} absolute, {} special, {} relative]", absolutes, specials, relatives);
+        if rpaths.is_empty() {
+            fmt_header(fmt, colors, "RPaths", 0)?;
+        } else {
+            writeln!(fmt, "{} {}:\n", hdr_size(colors, "RPaths", rpaths.len()), if relatives != 0 { counts.red().bold() } else { counts.normal() })?;
+        }
+        for path in &rpaths {
+            match special(path) {
+                Some(prefix) => {
+                    let colored = match prefix {
+                        "@executable_path" => prefix.yellow(),
+                        "@loader_path" => prefix.cyan(),
+                        _ => prefix.magenta(),
+                    };
+                    writeln!(fmt, "{:>16} {}{}", "", colored.bold(), &path[prefix.len()..])?;
+                },
+                None if path.starts_with('/') => writeln!(fmt, "{:>16} {}", "", string(opt, path))?,
+                // whoever controls the working directory the binary is started from controls what it loads
+                None => writeln!(fmt, "{:>16} {} {}", "", string(opt, path).red(), "(relative, hijackable)".red())?,
+            }
+        }
+        writeln!(fmt)?;
+
+        let entry_commands = mach.load_commands.iter().filter(|lc| matches!(lc.command, load_command::CommandVariant::Main(_) | load_command::CommandVariant::Unixthread(_))).collect::<Vec<_>>();
+        fmt_header(fmt, colors, "Entry", entry_commands.len())?;
+        for lc in entry_commands {
+            let name = load_command::cmd_to_str(lc.command.cmd()).red();
+            match lc.command {
+                load_command::CommandVariant::Main(command) => {
+                    // entryoff is relative to the start of __TEXT, which maps the start of the file
+                    write!(fmt, "{:>16} entryoff: {} ", name, layout.off(command.entryoff))?;
+                    if let Some(text) = segments.iter().find(|segment| segment.name().ok() == Some("__TEXT")) {
+                        write!(fmt, "-> {} ", layout.addrx(text.vmaddr + command.entryoff))?;
+                    }
+                    writeln!(fmt, "stacksize: {}", if command.stacksize == 0 { "default".dimmed() } else { layout.sz(command.stacksize) })?;
+                },
+                _ => {
+                    let cputype = mach.header.cputype;
+                    let states = self.2.get(lc.offset..)
+                        .ok_or_else(|| error::Error::Malformed(format!("thread command at {:#x} is past the end of the binary", lc.offset)))
+                        .and_then(|bytes| thread::parse(bytes, cputype, mach.little_endian));
+                    let states = match states {
+                        Ok(states) => states,
+                        Err(err) => {
+                            writeln!(fmt, "{:>16} {}", name, err)?;
+                            continue;
+                        },
+                    };
+                    for state in states {
+                        write!(fmt, "{:>16} flavor: {} ", name, thread::flavor_to_str(cputype, state.flavor))?;
+                        match state.pc() {
+                            Some(pc) => writeln!(fmt, "{}: {}", state.pc, layout.addrx(pc))?,
+                            None => writeln!(fmt)?,
+                        }
+                        if opt.pretty {
+                            let mut table = new_table(row![b->"Register", br->"Value"]);
+                            for &(register, value) in &state.registers {
+                                table.add_row(Row::new(vec![
+                                    Cell::new(register),
+                                    if register == state.pc { layout.addrx_cell(value) } else { x_cell(value) },
+                                ]));
+                            }
+                            fmt_table(fmt, opt, &table)?;
+                        } else {
+                            for registers in state.registers.chunks(4) {
+                                write!(fmt, "{:>16}", "")?;
+                                for &(register, value) in registers {
+                                    let value = if register == state.pc { layout.addrx(value) } else { format!("{:#x}", value).normal() };
+                                    write!(fmt, " {:>6}: {:<18}", register, value)?;
+                                }
+                                writeln!(fmt)?;
+                            }
+                        }
+                    }
+                },
+            }
+        }
+        writeln!(fmt)?;
+
+        writeln!(fmt, "Name: {}", if let &Some(ref name) = &mach.name{ name } else { "None" })?;
+        writeln!(fmt, "is_64: {}", mach.header.container() == container::Container::Big )?;
+        writeln!(fmt, "is_lib: {}", mach.header.filetype == header::MH_DYLIB)?;
+        writeln!(fmt, "little_endian: {}", mach.header.is_little_endian())?;
+        match symbols {
+            Ok(ref symbols) => writeln!(fmt, "stripped: {}", if symbols.is_empty() { "yes".red() } else { "no".green() })?,
+            Err(ref err) => writeln!(fmt, "stripped: {} ({})", "unknown".yellow(), format!("{}", err).red())?,
+        }
+
+        Ok(())
+    }
+}
+
+/// The members of an archive, recursing into them `--archive-depth` deep
+struct Archive<'a>(&'a [ar::Member<'a>], Opt);
+
+/// Prints the symbols of a Mach-o object, skipping its stabs
+fn fmt_nlists (fmt: &mut ::std::fmt::Formatter, opt: &Opt, layout: Layout, symbols: &[nlist::Nlist], prefix: &str) -> ::std::fmt::Result {
+    let colors = &opt.theme;
+    let symbols = symbols.iter().filter(|symbol| !symbol.is_stab()).collect::<Vec<_>>();
+    write!(fmt, "{}", prefix)?;
+    fmt_header(fmt, colors, "Syms", symbols.len())?;
+    for symbol in symbols {
+        let typ = nlist::n_type_to_str(symbol.n_type).bold();
+        let typ = match symbol.n_type & nlist::N_TYPE {
+            nlist::N_SECT => typ.red(),
+            nlist::N_UNDF => typ.dimmed(),
+            _ => typ.normal(),
+        };
+        write!(fmt, "{}{} {:<4} ", prefix, layout.addr(symbol.n_value), typ)?;
+        write!(fmt, "{} ", string(opt, symbol.name))?;
+        if symbol.n_type & nlist::N_TYPE == nlist::N_SECT {
+            write!(fmt, "n_sect: {} ", symbol.n_sect)?;
+        }
+        writeln!(fmt, "{}", if symbol.is_extern() { "EXT".red() } else { "".normal() })?;
+    }
+    writeln!(fmt)
+}
+
+fn fmt_members (fmt: &mut ::std::fmt::Formatter, opt: &Opt, members: &[ar::Member], depth: usize, prefix: &str) -> ::std::fmt::Result {
+    let colors = &opt.theme;
+    // the offsets of members, like any file offsets, are padded as if for a 64-bit binary
+    let layout = Layout::new(opt, true);
+    for member in members {
+        writeln!(fmt, "{}{:>16} {} size: {}", prefix, layout.off(member.offset as u64), string(opt, &member.name), layout.sz(member.data.len() as u64))?;
+        if depth == 0 {
+            continue;
+        }
+        let nested = format!("{}    ", prefix);
+        // the objects and archives in the archive, e.g., not bitcode or text files
+        enum Nested<'a> {
+            Elf(Box<elf::Elf<'a>>),
+            Mach(bool, Vec<nlist::Nlist<'a>>),
+            Archive(Vec<ar::Member<'a>>),
+            Other,
+        }
+        // goblin needs 16 bytes to peek at
+        let peek = if member.data.len() < 16 { Ok(Hint::Unknown(0)) } else { goblin::peek(&mut ::std::io::Cursor::new(member.data)) };
+        let nested_member = peek.and_then(|peek| match peek {
+            Hint::Elf(_) => Ok(Nested::Elf(Box::new(parse_elf(member.data)?))),
+            Hint::Mach(_) => {
+                let mach = parse_mach(member.data)?;
+                Ok(Nested::Mach(mach.is_64, nlist::parse(member.data, &mach)?))
+            },
+            Hint::Archive => Ok(Nested::Archive(ar::members(member.data)?)),
+            _ => Ok(Nested::Other),
+        });
+        match nested_member {
+            Ok(Nested::Elf(elf)) => {
+                let xindex = xindex_table(&elf, member.data, elf::section_header::SHT_SYMTAB);
+                let syms = elf.syms.iter().enumerate().map(|(i, sym)| (sym, &elf.strtab, false, real_shndx(sym, i, &xindex))).collect::<Vec<_>>();
+                writeln!(fmt)?;
+                fmt_syms(fmt, opt, &elf, "Syms", &syms, &nested)?;
+            },
+            Ok(Nested::Mach(is_64, symbols)) => {
+                writeln!(fmt)?;
+                fmt_nlists(fmt, opt, Layout::new(opt, is_64), &symbols, &nested)?;
+            },
+            Ok(Nested::Archive(members)) => {
+                writeln!(fmt)?;
+                write!(fmt, "{}", nested)?;
+                fmt_header(fmt, colors, "Members", members.len())?;
+                fmt_members(fmt, opt, &members, depth - 1, &nested)?;
+                writeln!(fmt)?;
+            },
+            Ok(Nested::Other) => (),
+            Err(err) => writeln!(fmt, "{}{}", nested, format!("{}", err).dimmed())?,
+        }
+    }
+    Ok(())
+}
+
+impl<'a> ::std::fmt::Display for Archive<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let opt = &self.1;
+        let colors = &opt.theme;
+        writeln!(fmt, "{} {}\n", hdr(colors, "Archive"), format!("{} members", self.0.len()).bold())?;
+        fmt_header(fmt, colors, "Members", self.0.len())?;
+        fmt_members(fmt, opt, self.0, opt.archive_depth, "")?;
+        writeln!(fmt)
+    }
+}
+
+/// The resource directory tree of a PE, with the bytes its data entries point into
+struct PeResources<'a>(Vec<resource::Entry>, &'a [u8], Opt, Layout);
+
+impl<'a> PeResources<'a> {
+    /// Prints `entries` at `depth` in the type -> name -> language tree, under the resource type `typ`
+    fn fmt_entries(&self, fmt: &mut ::std::fmt::Formatter, entries: &[resource::Entry], prefix: &str, depth: usize, typ: Option<u32>) -> ::std::fmt::Result {
+        use resource::{Id, Node};
+        let layout = self.3;
+        for (i, entry) in entries.iter().enumerate() {
+            let last = i + 1 == entries.len();
+            write!(fmt, "{}{}", prefix, if last { "└── " } else { "├── " })?;
+            let label = match (depth, &entry.id) {
+                (0, &Id::Id(id)) => format!("{}({})", resource::rt_to_str(id), id).cyan(),
+                (2, &Id::Id(id)) => format!("lang: {:#x}", id).normal(),
+                (_, &Id::Id(id)) => id.to_string().normal(),
+                (_, Id::Name(name)) => string(&self.2, name),
+            };
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            match entry.node {
+                Node::Directory(ref entries) => {
+                    writeln!(fmt, "{}", label)?;
+                    let typ = match (depth, &entry.id) { (0, &Id::Id(id)) => Some(id), _ => typ };
+                    self.fmt_entries(fmt, entries, &child_prefix, depth + 1, typ)?;
+                },
+                Node::Data(ref data) => {
+                    write!(fmt, "{} rva: {} ", label, layout.addrx(data.rva as u64))?;
+                    match data.offset {
+                        Some(offset) => write!(fmt, "offset: {} ", layout.off(offset as u64))?,
+                        None => write!(fmt, "offset: {} ", "none".dimmed())?,
+                    }
+                    writeln!(fmt, "size: {} codepage: {}", layout.sz(data.size as u64), data.codepage)?;
+                    if self.2.verbose && typ == Some(resource::RT_MANIFEST) {
+                        let manifest = data.offset.and_then(|offset| self.1.get(offset..offset + data.size as usize));
+                        if let Some(manifest) = manifest {
+                            for line in String::from_utf8_lossy(manifest).lines() {
+                                writeln!(fmt, "{}    {}", child_prefix, line.trim_end())?;
+                            }
+                        }
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ::std::fmt::Display for PeResources<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.2.theme;
+        fmt_header(fmt, colors, "Resources", self.0.iter().map(resource::Entry::leaves).sum())?;
+        self.fmt_entries(fmt, &self.0, "  ", 0, None)?;
+        writeln!(fmt)
+    }
+}
+
+/// The tools that built a PE, from the Rich header the MSVC linker leaves in the DOS stub
+struct PeRich<'a>(rich::Rich, &'a [u8], Opt, Layout);
+
+impl<'a> ::std::fmt::Display for PeRich<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let rich = &self.0;
+        let opt = &self.2;
+        let colors = &opt.theme;
+        let layout = self.3;
+        fmt_header(fmt, colors, "Rich Header", rich.entries.len())?;
+        write!(fmt, "offset: {} key: {:#010x} ", layout.off(rich.offset as u64), rich.key)?;
+        let checksum = rich.checksum(self.1);
+        if checksum == rich.key {
+            writeln!(fmt, "{}", "checksum ok".green())?;
+        } else {
+            writeln!(fmt, "{} {}", "checksum mismatch:".red().bold(), format!("{:#010x}", checksum).red())?;
+        }
+        if opt.hash {
+            writeln!(fmt, "richhash: {}", rich.hash().bold())?;
+        }
+        writeln!(fmt)?;
+        if opt.pretty {
+            let mut table = new_table(row![br->"Product", b->"Tool", br->"Build", br->"Count"]);
+            for entry in &rich.entries {
+                table.add_row(Row::new(vec![
+                    Cell::new(&format!("{:#06x}", entry.product)),
+                    Cell::new(rich::product_to_str(entry.product)).style_spec("Fc"),
+                    Cell::new(&entry.build.to_string()),
+                    Cell::new(&entry.count.to_string()),
+                ]));
+            }
+            fmt_table(fmt, opt, &table)?;
+        } else {
+            for entry in &rich.entries {
+                writeln!(fmt, "{:>8} {:<20} build: {:<6} count: {}", format!("{:#06x}", entry.product), rich::product_to_str(entry.product).cyan(), entry.build, entry.count)?;
+            }
+        }
+        writeln!(fmt)
+    }
+}
+
+/// The DLLs a PE imports from, with what it imports from each
+struct PeImports<'a>(Vec<pe_import::Dll<'a>>, Opt, Layout);
+
+impl<'a> ::std::fmt::Display for PeImports<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        use pe_import::Lookup;
+        let opt = &self.1;
+        let colors = &opt.theme;
+        let layout = self.2;
+        fmt_header(fmt, colors, "Imports", self.0.iter().map(|dll| dll.imports.len()).sum())?;
+        if opt.hash || opt.imphash {
+            writeln!(fmt, "imphash: {}\n", pe_import::imphash(&self.0).bold())?;
+        }
+        for dll in &self.0 {
+            write!(fmt, "{} ({}) iat: {} ", string(opt, dll.name).blue(), dll.imports.len(), layout.addrx(dll.iat as u64))?;
+            if dll.ilt != 0 {
+                write!(fmt, "ilt: {} ", layout.addrx(dll.ilt as u64))?;
+            }
+            if dll.time_date_stamp != 0 {
+                // -1 means the IAT was bound at link time with the old style of binding
+                write!(fmt, "{}", "bound".bold())?;
+            }
+            writeln!(fmt)?;
+            if opt.pretty {
+                let mut table = new_table(row![b->"IAT", b->"ILT", br->"Hint", br->"Ordinal", b->"Name"]);
+                for import in &dll.imports {
+                    let ilt = match import.ilt { Some(ilt) => layout.addrx_cell(ilt as u64), None => Cell::new("") };
+                    let (hint, ordinal, name) = match import.lookup {
+                        Lookup::Name { hint, name } => (Cell::new(&hint.to_string()), Cell::new(""), string_cell(opt, name)),
+                        Lookup::Ordinal(ordinal) => (Cell::new(""), Cell::new(&ordinal.to_string()).style_spec("bFm"), Cell::new("(by ordinal)").style_spec("i")),
+                    };
+                    table.add_row(Row::new(vec![layout.addrx_cell(import.iat as u64), ilt, hint, ordinal, name]));
+                }
+                fmt_table(fmt, opt, &table)?;
+            } else {
+                for import in &dll.imports {
+                    write!(fmt, "{:>16} ", layout.addrx(import.iat as u64))?;
+                    match import.ilt {
+                        Some(ilt) => write!(fmt, "ilt: {:<10} ", layout.addrx(ilt as u64))?,
+                        None => write!(fmt, "{:<15} ", "")?,
+                    }
+                    match import.lookup {
+                        Lookup::Name { hint, name } => writeln!(fmt, "hint: {:<5} {}", hint, string(opt, name))?,
+                        Lookup::Ordinal(ordinal) => writeln!(fmt, "{} {}", format!("ordinal: {}", ordinal).magenta().bold(), "(by ordinal)".dimmed())?,
+                    }
+                }
+            }
+            writeln!(fmt)?;
+        }
+        Ok(())
+    }
+}
+
+/// The exports of a PE binary, with the raw bytes to read forwarder strings from
+struct PeExports<'a>(&'a pe::PE<'a>, &'a [u8], Opt);
+
+impl<'a> PeExports<'a> {
+    /// The `DLL.Function` (or `DLL.#ordinal`) a forwarded export points at; goblin's split of it drops the
+    /// last character of the DLL name, so it is read here
+    fn forwarder(&self, export: &pe::export::Export) -> Option<String> {
+        use pe::export::Reexport;
+        let reexport = export.reexport.as_ref()?;
+        match clr::find_offset(export.rva as u32, &self.0.sections).and_then(|offset| self.1.pread::<&str>(offset).ok()) {
+            Some(forwarder) => Some(forwarder.to_owned()),
+            None => Some(match *reexport {
+                Reexport::DLLName { export, lib } => format!("{}.{}", lib, export),
+                // the export of an ordinal forwarder is the DLL
+                Reexport::DLLOrdinal { export, ordinal } => format!("{}.#{}", export, ordinal),
+            }),
+        }
+    }
+}
+
+impl<'a> ::std::fmt::Display for PeExports<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let opt = &self.2;
+        let colors = &opt.theme;
+        let layout = Layout::new(opt, self.0.is_64);
+        let exports = &self.0.exports;
+        fmt_header(fmt, colors, "Exports", exports.len())?;
+        if opt.pretty {
+            let mut table = new_table(row![b->"RVA", b->"Name", b->"Forwarded To"]);
+            for export in exports {
+                let row = match self.forwarder(export) {
+                    Some(forwarder) => vec![
+                        layout.addrx_cell(export.rva as u64),
+                        Cell::new(&symbol_name(opt, export.name)).style_spec("bFc"),
+                        Cell::new(&format!("→ {}", forwarder)).style_spec("Fc"),
+                    ],
+                    None => vec![layout.addrx_cell(export.rva as u64), string_cell(opt, export.name), Cell::new("")],
+                };
+                table.add_row(Row::new(row));
+            }
+            fmt_table(fmt, opt, &table)?;
+        } else {
+            for export in exports {
+                write!(fmt, "{:>16} ", layout.addrx(export.rva as u64))?;
+                match self.forwarder(export) {
+                    Some(forwarder) => writeln!(fmt, "{} {}", string(opt, export.name).cyan(), format!("→ {}", forwarder).cyan())?,
+                    None => writeln!(fmt, "{}", string(opt, export.name))?,
+                }
+            }
+        }
+        writeln!(fmt)
+    }
+}
+
+/// The CLR header of a .NET assembly
+struct Clr<'a>(clr::Clr<'a>, Opt, Layout);
+
+impl<'a> ::std::fmt::Display for Clr<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let header = &self.0.header;
+        let opt = &self.1;
+        let colors = &opt.theme;
+        let layout = self.2;
+        writeln!(fmt, "{} runtime v{}.{} ({}):", hdr(colors, "CLR"), header.major_runtime_version, header.minor_runtime_version, layout.sz(header.cb as u64))?;
+        writeln!(fmt)?;
+        write!(fmt, "flags: {:#x} ", header.flags)?;
+        for &flag in &clr::COMIMAGE_FLAGS {
+            if header.flags & flag == flag {
+                write!(fmt, "{} ", clr::flag_to_str(flag).bold())?;
+            }
+        }
+        writeln!(fmt)?;
+        if header.flags & clr::COMIMAGE_FLAGS_NATIVE_ENTRYPOINT != 0 {
+            writeln!(fmt, "entry point rva: {}", layout.addrx(header.entry_point as u64))?;
+        } else {
+            writeln!(fmt, "entry point token: {:#010x}", header.entry_point)?;
+        }
+        let directories = [
+            ("metadata", header.metadata),
+            ("resources", header.resources),
+            ("strong name signature", header.strong_name_signature),
+            ("code manager table", header.code_manager_table),
+            ("vtable fixups", header.vtable_fixups),
+            ("export address table jumps", header.export_address_table_jumps),
+            ("managed native header", header.managed_native_header),
+        ];
+        for &(name, directory) in &directories {
+            if directory.size != 0 {
+                writeln!(fmt, "{}: rva: {} size: {}", name, layout.addrx(directory.rva as u64), layout.sz(directory.size as u64))?;
+            } else {
+                writeln!(fmt, "{}: {}", name, "none".dimmed())?;
+            }
+        }
+        writeln!(fmt)?;
+
+        match self.0.metadata {
+            Ok(ref metadata) => {
+                writeln!(fmt, "{} {} v{}.{}:", hdr(colors, "Metadata"), string(opt, metadata.version), metadata.major_version, metadata.minor_version)?;
+                writeln!(fmt)?;
+                fmt_header(fmt, colors, "Streams", metadata.streams.len())?;
+                for (i, stream) in metadata.streams.iter().enumerate() {
+                    writeln!(fmt, "{} {:>16} offset: {} size: {}", layout.idx(i), string(opt, stream.name), layout.off(stream.offset as u64), layout.sz(stream.size as u64))?;
+                }
+            },
+            Err(ref err) => writeln!(fmt, "{}: {}", hdr(colors, "Metadata"), err)?,
+        }
+        Ok(())
+    }
+}
+
+/// The debug directory of a PE, with the PDB each CodeView entry names
+struct PeDebug<'a>(Vec<pe_debug::Entry<'a>>, Opt, Layout);
+
+impl<'a> ::std::fmt::Display for PeDebug<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        use pe_debug::{CodeView, Data};
+        let opt = &self.1;
+        let colors = &opt.theme;
+        let layout = self.2;
+        // the timestamps of a reproducible build are a hash of its contents, not a time
+        let reproducible = self.0.iter().any(|entry| entry.typ == pe_debug::IMAGE_DEBUG_TYPE_REPRO);
+        fmt_header(fmt, colors, "Debug Directory", self.0.len())?;
+        for (i, entry) in self.0.iter().enumerate() {
+            write!(fmt, "{} {:<13} ", layout.idx(i), pe_debug::type_to_str(entry.typ).bold())?;
+            write!(fmt, "timestamp: {:#010x}{} ", entry.time_date_stamp, if reproducible { " (hash)" } else { "" })?;
+            if entry.major_version != 0 || entry.minor_version != 0 {
+                write!(fmt, "version: {}.{} ", entry.major_version, entry.minor_version)?;
+            }
+            if entry.characteristics != 0 {
+                write!(fmt, "characteristics: {:#x} ", entry.characteristics)?;
+            }
+            writeln!(fmt, "rva: {} offset: {} size: {}", layout.addrx(entry.address_of_raw_data as u64), layout.off(entry.pointer_to_raw_data as u64), layout.sz(entry.size_of_data as u64))?;
+            match entry.data {
+                Ok(Data::CodeView(CodeView::Rsds { ref guid, age, path })) => {
+                    writeln!(fmt, "  ├──RSDS guid: {} age: {}", pe_debug::guid_to_string(guid).cyan(), age)?;
+                    writeln!(fmt, "  └──pdb: {}", string(opt, path).yellow())?;
+                },
+                Ok(Data::CodeView(CodeView::Nb10 { offset, timestamp, age, path })) => {
+                    writeln!(fmt, "  ├──NB10 offset: {} timestamp: {:#010x} age: {}", layout.off(offset as u64), timestamp, age)?;
+                    writeln!(fmt, "  └──pdb: {}", string(opt, path).yellow())?;
+                },
+                Ok(Data::Repro([])) => writeln!(fmt, "  └──{}", "reproducible".green())?,
+                Ok(Data::Repro(hash)) => writeln!(fmt, "  └──{} hash: {}", "reproducible".green(), hex(hash))?,
+                Ok(Data::Pogo(ref pogo)) => {
+                    let signature = pogo.signature.to_be_bytes();
+                    let signature = String::from_utf8_lossy(&signature);
+                    writeln!(fmt, "  ├──signature: {} ({:#010x}) entries: {}", signature.trim_end_matches('\0').bold(), pogo.signature, pogo.entries.len())?;
+                    for (j, pogo_entry) in pogo.entries.iter().enumerate() {
+                        let branch = if j + 1 == pogo.entries.len() { "└──" } else { "├──" };
+                        writeln!(fmt, "  {}{} rva: {} size: {}", branch, section_name(opt, pogo_entry.name), layout.addrx(pogo_entry.rva as u64), layout.sz(pogo_entry.size as u64))?;
+                    }
+                },
+                Ok(Data::None) => (),
+                Err(ref err) => writeln!(fmt, "  └──{}", format!("{}", err).red())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A COFF object file, as written by the Windows toolchains
+struct Coff<'a>(&'a coff::Coff<'a>, Opt);
+
+impl<'a> Coff<'a> {
+    fn flags (&self, characteristics: u32) -> String {
+        coff::IMAGE_SCN_FLAGS.iter().filter(|&&(flag, _)| characteristics & flag != 0).map(|&(_, name)| name).collect::<Vec<_>>().join(" ")
+    }
+
+    fn symbol_name (&self, index: u32) -> Option<&'a str> {
+        self.0.symbol(index).map(|sym| sym.name)
+    }
+}
+
+impl<'a> ::std::fmt::Display for Coff<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let coff = &self.0;
+        let opt = &self.1;
+        let colors = &opt.theme;
+        let layout = Layout::new(opt, false);
+        let machine = coff.header.machine;
+        writeln!(fmt, "{} {} {} timestamp: {:#x}", hdr(colors, "COFF"), "OBJECT".reverse().bold().yellow(), coff::machine_to_str(machine).bold(), coff.header.time_date_stamp)?;
+        if coff.header.characteristics != 0 {
+            writeln!(fmt, "characteristics: {:#x}", coff.header.characteristics)?;
+        }
+        writeln!(fmt)?;
+
+        fmt_header(fmt, colors, "Sections", coff.sections.len())?;
+        if opt.pretty {
+            let mut table = new_table(row![b->"Idx", b->"Name", b->"Offset", b->"Size", br->"Relocs", b->"Align", b->"Flags"]);
+            for (i, section) in coff.sections.iter().enumerate() {
+                table.add_row(Row::new(vec![
+                    layout.idx_cell(i + 1),
+                    Cell::new(&section_name(opt, section.name)).style_spec("b"),
+                    layout.offsetx_cell(section.pointer_to_raw_data as u64),
+                    layout.sz_cell(section.size_of_raw_data as u64),
+                    Cell::new(&section.relocations.len().to_string()).style_spec("r"),
+                    x_cell(section.alignment() as u64),
+                    Cell::new(&self.flags(section.characteristics)).style_spec("lbW"),
+                ]));
+            }
+            fmt_table(fmt, opt, &table)?;
+        } else {
+            for (i, section) in coff.sections.iter().enumerate() {
+                write!(fmt, "{} {:<16} ", layout.idx(i + 1), section_name(opt, section.name).bold())?;
+                write!(fmt, "offset: {} size: {} ", layout.off(section.pointer_to_raw_data as u64), layout.sz(section.size_of_raw_data as u64))?;
+                write!(fmt, "relocs: {} align: {:#x} ", section.relocations.len(), section.alignment())?;
+                writeln!(fmt, "{}", self.flags(section.characteristics).bold())?;
+            }
+        }
+        fmt_total_size(fmt, opt, layout, coff.sections.iter().map(|section| section.size_of_raw_data as u64), "")?;
+        writeln!(fmt)?;
+
+        fmt_header(fmt, colors, "Relocations", coff.sections.iter().map(|section| section.relocations.len()).sum())?;
+        for section in coff.sections.iter().filter(|section| !section.relocations.is_empty()) {
+            writeln!(fmt, "{} ({}):", section_name(opt, section.name).bold(), section.relocations.len())?;
+            if opt.pretty {
+                let mut table = new_table(row![b->"Offset", br->"Type", b->"Symbol"]);
+                for reloc in &section.relocations {
+                    let symbol = match self.symbol_name(reloc.symbol) {
+                        Some(name) => string_cell(opt, name),
+                        None => Cell::new(BAD_INDEX).style_spec("irFw"),
+                    };
+                    table.add_row(Row::new(vec![
+                        layout.addr_cell(reloc.virtual_address as u64),
+                        Cell::new(coff::reloc_to_str(machine, reloc.typ)).style_spec("r"),
+                        symbol,
+                    ]));
+                }
+                fmt_table(fmt, opt, &table)?;
+            } else {
+                for reloc in &section.relocations {
+                    write!(fmt, "{} {:>14} ", layout.addr(reloc.virtual_address as u64), coff::reloc_to_str(machine, reloc.typ))?;
+                    match self.symbol_name(reloc.symbol) {
+                        Some(name) => writeln!(fmt, "{}", string(opt, name))?,
+                        None => writeln!(fmt, "{}", BAD_INDEX.red())?,
+                    }
+                }
+            }
+            writeln!(fmt)?;
+        }
+
+        fmt_header(fmt, colors, "Syms", coff.symbols.len())?;
+        if opt.pretty {
+            let mut table = new_table(row![b->"Idx", b->"Value", b->"Class", b->"Section", b->"Name"]);
+            for sym in &coff.symbols {
+                let class = Cell::new(coff::storage_class_to_str(sym.storage_class));
+                table.add_row(Row::new(vec![
+                    layout.idx_cell(sym.index),
+                    layout.addr_cell(sym.value as u64),
+                    if sym.storage_class == coff::IMAGE_SYM_CLASS_EXTERNAL { class.style_spec("bFr") } else { class },
+                    Cell::new(&section_name(opt, coff.section_name(sym.section_number))),
+                    if sym.is_function() { string_cell(opt, sym.name) } else { Cell::new(&symbol_name(opt, sym.name)) },
+                ]));
+            }
+            fmt_table(fmt, opt, &table)?;
+        } else {
+            for sym in &coff.symbols {
+                let class = coff::storage_class_to_str(sym.storage_class).bold();
+                let class = match sym.storage_class {
+                    coff::IMAGE_SYM_CLASS_EXTERNAL if sym.section_number == coff::IMAGE_SYM_UNDEFINED => class.dimmed(),
+                    coff::IMAGE_SYM_CLASS_EXTERNAL => class.red(),
+                    _ => class.normal(),
+                };
+                write!(fmt, "{} {} {:<13} {:<16} ", layout.idx(sym.index), layout.addr(sym.value as u64), class, section_name(opt, coff.section_name(sym.section_number)))?;
+                if sym.is_function() {
+                    writeln!(fmt, "{} {}", string(opt, sym.name), "FUNC".bold())?;
+                } else {
+                    writeln!(fmt, "{}", symbol_name(opt, sym.name))?;
+                }
+            }
+        }
+        writeln!(fmt)
+    }
+}
+
+/// LLVM bitcode, as shipped for LTO
+struct Bitcode<'a>(&'a bitcode::Bitcode, &'a Opt);
+
+impl<'a> ::std::fmt::Display for Bitcode<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bitcode = &self.0;
+        let colors = &self.1.theme;
+        let layout = Layout::new(self.1, false);
+        write!(fmt, "{} {} size: {}", hdr(colors, "LLVM"), "BITCODE".reverse().bold().yellow(), layout.sz(bitcode.size as u64))?;
+        if let Some(ref wrapper) = bitcode.wrapper {
+            write!(fmt, " wrapped at: {} version: {} cputype: {}", layout.off(wrapper.offset as u64), wrapper.version, mach::constants::cputype::cpu_type_to_str(wrapper.cputype))?;
+        }
+        writeln!(fmt)
+    }
+}
+
+/// How many bytes of each wasm custom section are hexdumped
+const CUSTOM_SECTION_PREVIEW: usize = 32;
+
+struct Wasm<'a> {
+    module: &'a wasm::Module<'a>,
+    opt: Opt,
+    bytes: &'a [u8],
+}
+
+impl<'a> ::std::fmt::Display for Wasm<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let module = &self.module;
+        let opt = &self.opt;
+        let colors = &opt.theme;
+        let layout = Layout::new(opt, false);
+        writeln!(fmt, "{} {} version {}:", hdr(colors, "WASM"), "MODULE".bold().reverse().blue(), module.version)?;
+        writeln!(fmt)?;
+
+        if opt.shows(module.sections.len()) {
+            fmt_header(fmt, colors, "Sections", module.sections.len())?;
+            for (i, section) in module.sections.iter().enumerate() {
+                let name = if section.id == 0 { string(opt, section.name) } else { section.name.bold() };
+                writeln!(fmt, "{} {:>16} offset: {} size: {}", layout.idx(i), name, layout.off(section.offset as u64), layout.sz(section.size as u64))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if opt.shows(module.types.len()) {
+            fmt_header(fmt, colors, "Types", module.types.len())?;
+            for i in 0..module.types.len() {
+                writeln!(fmt, "{} {}", layout.idx(i), module.signature(i as u32))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if opt.shows(module.imports.len()) {
+            fmt_header(fmt, colors, "Imports", module.imports.len())?;
+            for import in &module.imports {
+                let typ = match import.ty {
+                    wasm::TypeRef::Func(typ) => module.signature(typ),
+                    wasm::TypeRef::Table(ref table) => format!("table {}", wasm::table_to_string(table)),
+                    wasm::TypeRef::Memory(ref memory) => format!("memory {}", wasm::memory_to_string(memory)),
+                    wasm::TypeRef::Global(_) => "global".into(),
+                    wasm::TypeRef::Tag(_) => "tag".into(),
+                };
+                writeln!(fmt, "{:>16} {} -> {} {}", "", string(opt, import.name), import.module.blue(), typ)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        // defined functions are numbered after the imported ones
+        let nimports = module.imported_functions();
+        if opt.shows(module.functions.len()) {
+            fmt_header(fmt, colors, "Functions", module.functions.len())?;
+            for (i, &typ) in module.functions.iter().enumerate() {
+                let index = (nimports + i) as u32;
+                write!(fmt, "{} type: {:<4} {} ", layout.idx(index as usize), typ, module.signature(typ))?;
+                match module.names.get(&index) {
+                    Some(name) => writeln!(fmt, "{}", string(opt, name))?,
+                    None => writeln!(fmt)?,
+                }
+            }
+            writeln!(fmt)?;
+        }
+
+        let ntables = module.imported_tables();
+        if opt.shows(module.tables.len()) {
+            fmt_header(fmt, colors, "Tables", module.tables.len())?;
+            for (i, table) in module.tables.iter().enumerate() {
+                writeln!(fmt, "{} {}", layout.idx(ntables + i), wasm::table_to_string(table))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        let nmemories = module.imported_memories();
+        if opt.shows(module.memories.len()) {
+            fmt_header(fmt, colors, "Memories", module.memories.len())?;
+            for (i, memory) in module.memories.iter().enumerate() {
+                writeln!(fmt, "{} {}", layout.idx(nmemories + i), wasm::memory_to_string(memory))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if opt.shows(module.exports.len()) {
+            fmt_header(fmt, colors, "Exports", module.exports.len())?;
+            for export in &module.exports {
+                let kind = match export.kind {
+                    wasm::ExternalKind::Func => "func",
+                    wasm::ExternalKind::Table => "table",
+                    wasm::ExternalKind::Memory => "memory",
+                    wasm::ExternalKind::Global => "global",
+                    wasm::ExternalKind::Tag => "tag",
+                };
+                writeln!(fmt, "{} {:>8} {}", layout.idx(export.index as usize), kind.bold(), string(opt, export.name))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if opt.shows(module.customs.len()) {
+            fmt_header(fmt, colors, "Custom Sections", module.customs.len())?;
+            for custom in &module.customs {
+                writeln!(fmt, "{:>16} offset: {} size: {}", string(opt, custom.name), layout.off(custom.offset as u64), layout.sz(custom.data.len() as u64))?;
+                fmt_hexdump(fmt, layout, &custom.data[..::std::cmp::min(custom.data.len(), CUSTOM_SECTION_PREVIEW)], custom.offset)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if let Some(ref search) = opt.search {
+            writeln!(fmt, "Matches for {:?}:", search)?;
+            let base = opt.offset.map(|Number(base)| base).unwrap_or(0);
+            for offset in find_all(self.bytes, search) {
+                writeln!(fmt, "  {} {:?}", layout.hex(offset as u64 + base, true), match_string(self.bytes, offset))?;
+                for (i, section) in module.sections.iter().enumerate() {
+                    if offset >= section.offset && offset < section.offset + section.size {
+                        writeln!(fmt, "  ├──{}({}) ∈ {}", section.name, i, layout.addrx((offset - section.offset) as u64))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The widest instruction whose bytes are shown in full; longer ones are cut off with `..`
+const INSN_BYTES_WIDTH: usize = 8;
+
+/// A headerless code blob, disassembled as `arch`
+struct Raw<'a> {
+    insns: &'a [disasm::Insn],
+    arch: disasm::Arch,
+    base: u64,
+    size: usize,
+    opt: Opt,
+}
+
+impl<'a> ::std::fmt::Display for Raw<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let opt = &self.opt;
+        let colors = &opt.theme;
+        let layout = Layout::new(opt, self.arch.is_64());
+        let arch = disasm::ARCHES.iter().find(|&&(_, arch)| arch == self.arch).map(|&(name, _)| name).unwrap_or("unknown");
+        writeln!(fmt, "{} {} @ {} size: {}:", hdr(colors, "RAW"), arch.bold(), layout.addrx(self.base), layout.sz(self.size as u64))?;
+        writeln!(fmt)?;
+
+        fmt_header(fmt, colors, "Instructions", self.insns.len())?;
+        let hex = |bytes: &[u8]| {
+            let shown = bytes.iter().take(INSN_BYTES_WIDTH).map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" ");
+            if bytes.len() > INSN_BYTES_WIDTH { format!("{} ..", shown) } else { shown }
+        };
+        if self.opt.pretty {
+            let mut table = new_table(row![br->"Addr", b->"Bytes", b->"Mnemonic", b->"Operands"]);
+            for insn in self.insns {
+                table.add_row(Row::new(vec![
+                    layout.addr_cell(insn.address),
+                    Cell::new(&hex(&insn.bytes)).style_spec("FD"),
+                    Cell::new(&insn.mnemonic).style_spec("b"),
+                    Cell::new(&insn.operands),
+                ]));
+            }
+            fmt_table(fmt, &self.opt, &table)?;
+        } else {
+            let width = INSN_BYTES_WIDTH * 3 + 2;
+            for insn in self.insns {
+                writeln!(fmt, "{} {:<width$} {} {}", layout.addr(insn.address), hex(&insn.bytes).dimmed(), insn.mnemonic.bold(), insn.operands, width = width)?;
+            }
+        }
+        writeln!(fmt)?;
+        Ok(())
+    }
+}
+
+/// The file contents of `shdr`, which is empty for SHT_NOBITS sections
+fn section_bytes<'a> (bytes: &'a [u8], shdr: &elf::SectionHeader) -> error::Result<&'a [u8]> {
+    if shdr.sh_type == elf::section_header::SHT_NOBITS {
+        return Ok(&[]);
+    }
+    bytes.get(shdr.sh_offset as usize..shdr.sh_offset.saturating_add(shdr.sh_size) as usize)
+        .ok_or_else(|| error::Error::Malformed(format!("section at {:#x} of size {:#x} is past the end of the file", shdr.sh_offset, shdr.sh_size)))
+}
+
+/// The first section called `name`
+fn named_section<'a> (elf: &'a elf::Elf, name: &str) -> Option<&'a elf::SectionHeader> {
+    elf.section_headers.iter().find(|shdr| elf.shdr_strtab.get(shdr.sh_name).ok() == Some(name))
+}
+
+/// The contents of the first section called `name`, if there is one
+fn named_section_bytes<'a> (elf: &elf::Elf, bytes: &'a [u8], name: &str) -> Option<error::Result<&'a [u8]>> {
+    named_section(elf, name).map(|shdr| section_bytes(bytes, shdr))
+}
+
+/// Sections of NUL separated strings naming the tools, and the options, that built the binary
+const PRODUCER_SECTIONS: [&str; 2] = [".comment", ".GCC.command.line"];
+/// Producer strings longer than this are cut short
+const MAX_PRODUCER_LEN: usize = 80;
+
+/// The distinct strings in the producer sections, with anything unprintable replaced
+fn producers (elf: &elf::Elf, bytes: &[u8]) -> Vec<String> {
+    let mut producers = Vec::new();
+    for name in &PRODUCER_SECTIONS {
+        let data = match named_section_bytes(elf, bytes, name) {
+            Some(Ok(data)) => data,
+            _ => continue,
+        };
+        for producer in data.split(|&byte| byte == 0).filter(|producer| !producer.is_empty()) {
+            let producer = String::from_utf8_lossy(producer);
+            let mut printable = producer.chars().take(MAX_PRODUCER_LEN).map(|c| if c.is_control() { '.' } else { c }).collect::<String>();
+            if producer.chars().count() > MAX_PRODUCER_LEN {
+                printable.push('…');
+            }
+            if !producers.contains(&printable) {
+                producers.push(printable);
+            }
+        }
+    }
+    producers
+}
+
+/// The file offset of the virtual address `vaddr`, through the PT_LOAD segment that maps it
+fn vaddr_to_offset (elf: &elf::Elf, vaddr: u64) -> Option<usize> {
+    elf.program_headers.iter()
+        .filter(|phdr| phdr.p_type == elf::program_header::PT_LOAD)
+        .find(|phdr| vaddr >= phdr.p_vaddr && vaddr - phdr.p_vaddr < phdr.p_filesz)
+        .and_then(|phdr| phdr.p_offset.checked_add(vaddr - phdr.p_vaddr))
+        .map(|offset| offset as usize)
+}
+
+/// A SysV DT_HASH table, with the length of each bucket's chain
+struct SysvHash {
+    nbucket: u32,
+    nchain: u32,
+    lengths: Vec<usize>,
+}
+
+/// Reads the table at DT_HASH, if the binary has one
+fn sysv_hash (elf: &elf::Elf, bytes: &[u8]) -> Option<error::Result<SysvHash>> {
+    let dynamic = elf.dynamic.as_ref()?;
+    let vaddr = dynamic.dyns.iter().find(|dyn| dyn.d_tag == elf::dyn::DT_HASH)?.d_val;
+    let read = || {
+        let offset = vaddr_to_offset(elf, vaddr)
+            .ok_or_else(|| error::Error::Malformed(format!("DT_HASH {:#x} isn't in a loaded segment", vaddr)))?;
+        let le = scroll::Endian::from(elf.little_endian);
+        let nbucket = bytes.pread_with::<u32>(offset, le)?;
+        let nchain = bytes.pread_with::<u32>(offset + 4, le)?;
+        // both tables follow the two counts, and are read whole, so check they are in the file first
+        let words = nbucket as usize + nchain as usize;
+        if offset + 8 + words * 4 > bytes.len() {
+            return Err(error::Error::Malformed(format!("DT_HASH ({} buckets and {} chains at {:#x}) is past the end of the file", nbucket, nchain, offset)));
+        }
+        let word = |i: usize| bytes.pread_with::<u32>(offset + 8 + i * 4, le);
+        let mut lengths = Vec::with_capacity(nbucket as usize);
+        for bucket in 0..nbucket as usize {
+            let mut length = 0;
+            let mut sym = word(bucket)?;
+            // a chain can't be longer than the symbol table, unless it loops
+            while sym != 0 && sym < nchain && length < nchain as usize {
+                length += 1;
+                sym = word(nbucket as usize + sym as usize)?;
+            }
+            lengths.push(length);
+        }
+        Ok(SysvHash { nbucket, nchain, lengths })
+    };
+    Some(read())
+}
+
+/// How many bytes of each section --preview shows, few enough to keep the section table within a terminal
+const SECTION_PREVIEW: usize = 8;
+
+/// The first bytes of `shdr` as hex and printable ASCII, `<zero>` for SHT_NOBITS sections, and nothing for empty ones
+fn section_preview (bytes: &[u8], shdr: &elf::SectionHeader) -> String {
+    if shdr.sh_type == elf::section_header::SHT_NOBITS {
+        return "<zero>".into();
+    }
+    match section_bytes(bytes, shdr) {
+        Ok(data) => {
+            let data = &data[..::std::cmp::min(data.len(), SECTION_PREVIEW)];
+            let ascii = data.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }).collect::<String>();
+            if data.is_empty() { String::new() } else { format!("{} {}", hex(data), ascii) }
+        },
+        Err(_) => "<past the end of the file>".into(),
+    }
+}
+
+/// goblin allocates the symbol and relocation tables of sections by the size their headers claim before reading them,
+/// so a corrupt size would abort on the allocation, and indexes the string table of the symbol table without checking
+/// it; such headers are rejected before goblin sees them
+fn check_table_sizes (bytes: &[u8], shdrs: &[elf::SectionHeader]) -> error::Result<()> {
+    use elf::section_header::{self, SHT_SYMTAB, SHT_REL, SHT_RELA};
+    for (i, shdr) in shdrs.iter().enumerate() {
+        if (shdr.sh_type == SHT_SYMTAB || shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA) && shdr.sh_size > bytes.len() as u64 {
+            return Err(error::Error::Malformed(format!("{} section {} claims {:#x} bytes, more than the whole file", section_header::sht_to_str(shdr.sh_type), i, shdr.sh_size)));
+        }
+        if shdr.sh_type == SHT_SYMTAB && shdr.sh_link as usize >= shdrs.len() {
+            return Err(error::Error::Malformed(format!("symbol table section {} links to section {}, past the last one", i, shdr.sh_link)));
+        }
+    }
+    Ok(())
+}
+
+/// goblin allocates the dynamic array, and the dynamic symbol and relocation tables it points to, by the sizes they
+/// claim before reading them, and looks up the names of the needed libraries without checking them; such a PT_DYNAMIC
+/// is rejected before goblin sees it
+fn check_dynamic (bytes: &[u8], phdrs: &[elf::ProgramHeader], ctx: container::Ctx) -> error::Result<()> {
+    use elf::program_header::{PT_DYNAMIC, PT_LOAD};
+    let too_big = |what: &str, size: u64| Err(error::Error::Malformed(format!("{} claims {:#x} bytes, more than the whole file", what, size)));
+    match phdrs.iter().find(|phdr| phdr.p_type == PT_DYNAMIC) {
+        Some(phdr) if phdr.p_filesz > bytes.len() as u64 => return too_big("PT_DYNAMIC", phdr.p_filesz),
+        Some(_) => (),
+        None => return Ok(()),
+    }
+    // goblin turns the addresses of the first PT_LOAD into file offsets by adding this bias to them
+    let bias = phdrs.iter().find(|phdr| phdr.p_type == PT_LOAD).map_or(0, |phdr| {
+        if ctx.container.is_big() { phdr.p_vaddr.wrapping_neg() as usize } else { (phdr.p_vaddr as u32).wrapping_neg() as usize }
+    });
+    let dynamic = match elf::dyn::Dynamic::parse(bytes, phdrs, bias, ctx)? {
+        Some(dynamic) => dynamic,
+        None => return Ok(()),
+    };
+    let info = &dynamic.info;
+    for &(what, size) in &[("DT_RELASZ", info.relasz), ("DT_RELSZ", info.relsz), ("DT_PLTRELSZ", info.pltrelsz)] {
+        if size > bytes.len() {
+            return too_big(what, size as u64);
+        }
+    }
+    // goblin counts the dynamic symbols by the distance from DT_SYMTAB to DT_STRTAB
+    if info.syment != 0 && info.strtab > info.symtab && info.strtab - info.symtab > bytes.len() {
+        return too_big("DT_SYMTAB", (info.strtab - info.symtab) as u64);
+    }
+    if info.needed_count > 0 {
+        let strtab = goblin::strtab::Strtab::parse(bytes, info.strtab, info.strsz, 0x0)?;
+        for dyn in dynamic.dyns.iter().filter(|dyn| dyn.d_tag == elf::dyn::DT_NEEDED) {
+            if strtab.get(dyn.d_val as usize).is_err() {
+                return Err(error::Error::Malformed(format!("DT_NEEDED name at {:#x} isn't in the dynamic string table", dyn.d_val)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses an ELF, including one with too many sections for `e_shnum`, which goblin reads as having none.
+/// Such files set `e_shnum` to 0 and keep the real count in the first section header's `sh_size`,
+/// and the section string table index in its `sh_link`.
+fn parse_elf (bytes: &[u8]) -> error::Result<elf::Elf<'_>> {
+    use elf::section_header::{SHN_XINDEX, SHT_SYMTAB, SHT_REL, SHT_RELA};
+    let header = bytes.pread::<elf::Header>(0)?;
+    let container = if header.e_ident[elf::header::EI_CLASS] == elf::header::ELFCLASS64 { container::Container::Big } else { container::Container::Little };
+    let ctx = container::Ctx::new(container, scroll::Endian::from(header.e_ident[elf::header::EI_DATA] == elf::header::ELFDATA2LSB));
+    // headers that don't parse are left for goblin to report
+    if let Ok(shdrs) = elf::SectionHeader::parse(bytes, header.e_shoff as usize, header.e_shnum as usize, ctx) {
+        check_table_sizes(bytes, &shdrs)?;
+    }
+    if let Ok(phdrs) = elf::ProgramHeader::parse(bytes, header.e_phoff as usize, header.e_phnum as usize, ctx) {
+        check_dynamic(bytes, &phdrs, ctx)?;
+    }
+    let mut elf = elf::Elf::parse(bytes)?;
+    if elf.header.e_shnum != 0 || elf.header.e_shoff == 0 {
+        return Ok(elf);
+    }
+    let shoff = elf.header.e_shoff as usize;
+    let first = elf::SectionHeader::parse(bytes, shoff, 1, ctx)?;
+    let shnum = first[0].sh_size as usize;
+    if shnum > bytes.len() / elf.header.e_shentsize.max(1) as usize {
+        return Err(error::Error::Malformed(format!("extended section count {:#x} doesn't fit in the file", shnum)));
+    }
+    elf.section_headers = elf::SectionHeader::parse(bytes, shoff, shnum, ctx)?;
+    check_table_sizes(bytes, &elf.section_headers)?;
+    let shstrndx = if elf.header.e_shstrndx as u32 == SHN_XINDEX { first[0].sh_link as usize } else { elf.header.e_shstrndx as usize };
+    if let Some(shdr) = elf.section_headers.get(shstrndx) {
+        elf.shdr_strtab = goblin::strtab::Strtab::parse(bytes, shdr.sh_offset as usize, shdr.sh_size as usize, 0x0)?;
+    }
+    if let Some(shdr) = elf.section_headers.iter().find(|shdr| shdr.sh_type == SHT_SYMTAB) {
+        let count = shdr.sh_size.checked_div(shdr.sh_entsize).unwrap_or(0);
+        elf.syms = elf::Sym::parse(bytes, shdr.sh_offset as usize, count as usize, ctx)?;
+        if let Some(shdr) = elf.section_headers.get(shdr.sh_link as usize) {
+            elf.strtab = goblin::strtab::Strtab::parse(bytes, shdr.sh_offset as usize, shdr.sh_size as usize, 0x0)?;
+        }
+    }
+    if elf.header.e_type == elf::header::ET_REL {
+        let mut relocs = Vec::new();
+        for (idx, shdr) in elf.section_headers.iter().enumerate() {
+            if shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA {
+                relocs.push((idx, elf::Reloc::parse(bytes, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx)?));
+            }
+        }
+        elf.shdr_relocs = relocs;
+    }
+    Ok(elf)
+}
+
+/// goblin slices the data of every segment, and of its sections when asked for them, by the offset and size their
+/// load commands claim without checking them, so a segment or section past the end of the file is rejected first
+fn check_segment_ranges (bytes: &[u8], header: &mach::header::Header) -> error::Result<()> {
+    use mach::load_command::{CommandVariant, LoadCommand, Section32, Section64};
+    use mach::load_command::{SIZEOF_SEGMENT_COMMAND_32, SIZEOF_SEGMENT_COMMAND_64, SIZEOF_SECTION_32, SIZEOF_SECTION_64};
+    let ctx = header.ctx()?;
+    let fits = |offset: u64, size: u64| offset.checked_add(size).is_some_and(|end| end <= bytes.len() as u64);
+    let mut offset = header.size();
+    for _ in 0..header.ncmds {
+        let cmd = LoadCommand::parse(bytes, &mut offset, ctx.le)?;
+        let (fileoff, filesize, nsects, cmdsize) = match cmd.command {
+            CommandVariant::Segment32(segment) => (segment.fileoff as u64, segment.filesize as u64, segment.nsects as usize, segment.cmdsize as usize),
+            CommandVariant::Segment64(segment) => (segment.fileoff, segment.filesize, segment.nsects as usize, segment.cmdsize as usize),
+            _ => continue,
+        };
+        if !fits(fileoff, filesize) {
+            return Err(error::Error::Malformed(format!("segment at {:#x} of size {:#x} is past the end", fileoff, filesize)));
+        }
+        if nsects.saturating_mul(SIZEOF_SECTION_32) > cmdsize {
+            return Err(error::Error::Malformed(format!("{:#x} sections don't fit in their segment command", nsects)));
+        }
+        for i in 0..nsects {
+            let (offset, size) = if ctx.container.is_big() {
+                let section = bytes.pread_with::<Section64>(cmd.offset + SIZEOF_SEGMENT_COMMAND_64 + i * SIZEOF_SECTION_64, ctx.le)?;
+                (section.offset as u64, section.size)
+            } else {
+                let section = bytes.pread_with::<Section32>(cmd.offset + SIZEOF_SEGMENT_COMMAND_32 + i * SIZEOF_SECTION_32, ctx.le)?;
+                (section.offset as u64, section.size as u64)
+            };
+            if !fits(offset, size) {
+                return Err(error::Error::Malformed(format!("section at {:#x} of size {:#x} is past the end", offset, size)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses the Mach-o binary at the start of `bytes`; goblin allocates room for `ncmds` load commands up front, so
+/// a count that can't fit in the file is rejected first
+fn parse_mach (bytes: &[u8]) -> error::Result<mach::MachO<'_>> {
+    let header = bytes.pread::<mach::header::Header>(0)?;
+    if header.ncmds.saturating_mul(mach::load_command::SIZEOF_LOAD_COMMAND) > bytes.len() {
+        return Err(error::Error::Malformed(format!("{:#x} load commands don't fit in the file", header.ncmds)));
+    }
+    check_segment_ranges(bytes, &header)?;
+    mach::MachO::parse(bytes, 0)
+}
+
+/// The architectures of the fat binary in `bytes`, each with its bytes
+fn fat_arches (bytes: &[u8]) -> error::Result<Vec<(mach::fat::FatArch, &[u8])>> {
+    let multi = mach::MultiArch::new(bytes)?;
+    if multi.narches.saturating_mul(mach::fat::SIZEOF_FAT_ARCH) > bytes.len() {
+        return Err(error::Error::Malformed(format!("{:#x} fat architectures don't fit in the file", multi.narches)));
+    }
+    multi.arches()?.into_iter().map(|arch| {
+        let start = arch.offset as usize;
+        let data = bytes.get(start..start.saturating_add(arch.size as usize))
+            .ok_or_else(|| error::Error::Malformed(format!("fat architecture at {:#x} of size {:#x} is past the end", arch.offset, arch.size)))?;
+        Ok((arch, data))
+    }).collect()
+}
+
+/// goblin looks up the export and import directories of a PE, and the tables and names they point to, by RVAs it
+/// assumes are inside a section, and allocates the export tables by the counts they claim; a directory that breaks
+/// either assumption is rejected before goblin sees it
+fn check_pe_directories (bytes: &[u8]) -> error::Result<()> {
+    use pe::export::ExportDirectoryTable;
+    use pe::import::{ImportDirectoryEntry, IMPORT_BY_ORDINAL_32, IMPORT_RVA_MASK_32};
+    use scroll::Gread;
+    let le = scroll::LE;
+    let header = pe::header::Header::parse(bytes)?;
+    let optional = match header.optional_header {
+        Some(optional) => optional,
+        None => return Ok(()),
+    };
+    let mut offset = header.dos_header.pe_pointer as usize + pe::header::SIZEOF_COFF_HEADER + header.coff_header.size_of_optional_header as usize;
+    let sections = (0..header.coff_header.number_of_sections)
+        .map(|_| pe::section_table::SectionTable::parse(bytes, &mut offset))
+        .collect::<error::Result<Vec<_>>>()?;
+    // the same lookup goblin makes, which sums the bounds of every section it tries
+    let offset_of = |rva: u32, what: &str| -> error::Result<usize> {
+        for section in &sections {
+            let end = section.virtual_address.checked_add(section.virtual_size)
+                .ok_or_else(|| error::Error::Malformed(format!("section at RVA {:#x} of size {:#x} is past the end of the address space", section.virtual_address, section.virtual_size)))?;
+            if section.virtual_address <= rva && rva < end {
+                return Ok((rva - section.virtual_address) as usize + section.pointer_to_raw_data as usize);
+            }
+        }
+        Err(error::Error::Malformed(format!("{} at RVA {:#x} isn't in any section", what, rva)))
+    };
+    if let Some(export_table) = *optional.data_directories.get_export_table() {
+        let table = ExportDirectoryTable::parse(bytes, offset_of(export_table.virtual_address, "export directory")?)?;
+        if table.number_of_name_pointers as usize > bytes.len() / 4 || table.address_table_entries as usize > bytes.len() / 4 {
+            return Err(error::Error::Malformed(format!("{:#x} export names or {:#x} export addresses don't fit in the file", table.number_of_name_pointers, table.address_table_entries)));
+        }
+        let names = offset_of(table.name_pointer_rva, "export name pointer table")?;
+        let ordinals = offset_of(table.ordinal_table_rva, "export ordinal table")?;
+        let addresses = offset_of(table.export_address_table_rva, "export address table")?;
+        offset_of(table.name_rva, "export directory name")?;
+        let forwarders = export_table.virtual_address..export_table.virtual_address.saturating_add(export_table.size);
+        for i in 0..table.number_of_name_pointers as usize {
+            offset_of(bytes.pread_with::<u32>(names + i * 4, le)?, "export name")?;
+            let ordinal = bytes.pread_with::<u16>(ordinals + i * 2, le)? as usize;
+            if ordinal >= table.address_table_entries as usize {
+                continue;
+            }
+            let rva = bytes.pread_with::<u32>(addresses + ordinal * 4, le)?;
+            let offset = offset_of(rva, "export")?;
+            // goblin splits a forwarder at its first dot, and expects a library name before it
+            if forwarders.contains(&rva) && bytes.get(offset) == Some(&b'.') {
+                return Err(error::Error::Malformed(format!("forwarder at RVA {:#x} has no library name", rva)));
+            }
+        }
+    }
+    if let Some(import_table) = *optional.data_directories.get_import_table() {
+        let mut offset = offset_of(import_table.virtual_address, "import directory")?;
+        loop {
+            let entry = bytes.gread_with::<ImportDirectoryEntry>(&mut offset, le)?;
+            if entry.is_null() {
+                break;
+            }
+            offset_of(entry.import_address_table_rva, "import address table")?;
+            let mut lookup = offset_of(entry.import_lookup_table_rva, "import lookup table")?;
+            loop {
+                let bitfield = bytes.gread_with::<u32>(&mut lookup, le)?;
+                if bitfield == 0 {
+                    break;
+                }
+                if bitfield & IMPORT_BY_ORDINAL_32 == 0 {
+                    offset_of(bitfield & IMPORT_RVA_MASK_32, "import name")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses the PE binary in `bytes`
+fn parse_pe (bytes: &[u8]) -> error::Result<pe::PE<'_>> {
+    check_pe_directories(bytes)?;
+    pe::PE::parse(bytes)
+}
+
+/// The SHT_SYMTAB_SHNDX table of the symbol table section of type `symtab_type`, which holds the real section index of every symbol whose `st_shndx` is SHN_XINDEX
+fn xindex_table (elf: &elf::Elf, bytes: &[u8], symtab_type: u32) -> Vec<u32> {
+    use elf::section_header::SHT_SYMTAB_SHNDX;
+    let le = scroll::Endian::from(elf.little_endian);
+    elf.section_headers.iter().position(|shdr| shdr.sh_type == symtab_type)
+        .and_then(|symtab| elf.section_headers.iter().find(|shdr| shdr.sh_type == SHT_SYMTAB_SHNDX && shdr.sh_link as usize == symtab))
+        .and_then(|shdr| section_bytes(bytes, shdr).ok())
+        .map(|data| (0..data.len() / 4).filter_map(|i| data.pread_with::<u32>(i * 4, le).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// The section index of the `index`th symbol `sym`, looked up in `xindex` if it is SHN_XINDEX
+fn real_shndx (sym: &elf::Sym, index: usize, xindex: &[u32]) -> usize {
+    let shn_xindex = elf::section_header::SHN_XINDEX as usize;
+    if sym.st_shndx == shn_xindex {
+        xindex.get(index).map(|&shndx| shndx as usize).unwrap_or(shn_xindex)
+    } else {
+        sym.st_shndx
+    }
+}
+
+/// The contents of `shdr`, decompressed if it is SHF_COMPRESSED unless `--raw-compressed` was passed
+fn section_data<'a> (opt: &Opt, elf: &elf::Elf, bytes: &'a [u8], shdr: &elf::SectionHeader) -> error::Result<::std::borrow::Cow<'a, [u8]>> {
+    let data = section_bytes(bytes, shdr)?;
+    if shdr.sh_flags as u32 & elf::section_header::SHF_COMPRESSED != 0 && !opt.raw_compressed {
+        Ok(compressed::decompress(data, elf.is_64, elf.little_endian)?.into())
+    } else {
+        Ok(data.into())
+    }
+}
+
+/// Describes how a SHF_COMPRESSED section is compressed, e.g. "compressed zlib, 0x1a2 -> 0x4f0 bytes, align 0x1"
+fn compression (elf: &elf::Elf, bytes: &[u8], shdr: &elf::SectionHeader) -> Option<String> {
+    if shdr.sh_flags as u32 & elf::section_header::SHF_COMPRESSED == 0 {
+        return None;
+    }
+    let chdr = section_bytes(bytes, shdr).and_then(|data| compressed::Chdr::parse(data, elf.is_64, elf.little_endian));
+    Some(match chdr {
+        Ok(chdr) => match chdr.ch_type {
+            compressed::ELFCOMPRESS_ZLIB | compressed::ELFCOMPRESS_ZSTD =>
+                format!("compressed {}, {:#x} -> {:#x} bytes, align {:#x}", compressed::type_to_str(chdr.ch_type), shdr.sh_size, chdr.ch_size, chdr.ch_addralign),
+            typ => format!("compressed with unknown type {:#x}", typ),
+        },
+        Err(_) => "compressed, bad compression header".into(),
+    })
+}
+
+/// The contents of a `.gnu_debuglink` section
+struct Debuglink<'a> {
+    name: &'a str,
+    crc: u32,
+}
+
+fn debuglink<'a> (elf: &elf::Elf, bytes: &'a [u8]) -> Option<Debuglink<'a>> {
+    let data = named_section_bytes(elf, bytes, ".gnu_debuglink")?.ok()?;
+    let name = data.pread::<&str>(0).ok()?;
+    // the crc follows the name, aligned to 4 bytes
+    let crc = data.pread_with::<u32>((name.len() + 1 + 3) & !3, scroll::Endian::from(elf.little_endian)).ok()?;
+    Some(Debuglink { name, crc })
+}
+
+/// The CRC-32 used by `.gnu_debuglink`, which is the same one as zlib's
+fn crc32 (bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = i as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb88320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+        *entry = crc;
+    }
+    !bytes.iter().fold(!0u32, |crc, &byte| table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// The desc of the binary's first GNU note of type `n_type`
+fn gnu_note<'a> (elf: &elf::Elf, bytes: &'a [u8], n_type: u32) -> Option<&'a [u8]> {
+    use elf::{section_header, program_header};
+    let sections = elf.section_headers.iter()
+        .filter(|shdr| shdr.sh_type == section_header::SHT_NOTE)
+        .filter_map(|shdr| section_bytes(bytes, shdr).ok().map(|data| (data, shdr.sh_addralign)));
+    // the section headers may be stripped, but the note segments can't be
+    let segments = elf.program_headers.iter()
+        .filter(|phdr| phdr.p_type == program_header::PT_NOTE)
+        .filter_map(|phdr| bytes.get(phdr.p_offset as usize..phdr.p_offset.saturating_add(phdr.p_filesz) as usize).map(|data| (data, phdr.p_align)));
+    sections.chain(segments)
+        .filter_map(|(data, align)| note::parse(data, align, elf.little_endian).ok())
+        .flatten()
+        .find(|note| note.name == note::NOTE_GNU && note.n_type == n_type)
+        .map(|note| note.desc)
+}
+
+/// The GNU build-id of the binary
+fn build_id<'a> (elf: &elf::Elf, bytes: &'a [u8]) -> Option<&'a [u8]> {
+    gnu_note(elf, bytes, note::NT_GNU_BUILD_ID)
+}
+
+/// The OS and oldest kernel the binary runs on, from its `.note.ABI-tag`
+fn abi_tag (elf: &elf::Elf, bytes: &[u8]) -> Option<note::AbiTag> {
+    gnu_note(elf, bytes, note::NT_GNU_ABI_TAG).and_then(|desc| note::abi_tag(desc, elf.little_endian).ok())
+}
+
+/// The oldest glibc the binary runs on, which is the newest `GLIBC_` version it needs
+fn glibc_needed (elf: &elf::Elf, bytes: &[u8]) -> Option<Vec<u32>> {
+    let shdr = elf.section_headers.iter().find(|shdr| shdr.sh_type == elf::section_header::SHT_GNU_VERNEED)?;
+    let needed = version::verneed(section_bytes(bytes, shdr).ok()?, elf.little_endian, &elf.dynstrtab).ok()?;
+    needed.iter().filter_map(|needed| version::glibc(needed.name)).max()
+}
+
+/// The glibc the library at `path` is, by the newest `GLIBC_` version it defines
+fn glibc_provided (path: &Path) -> Option<Vec<u32>> {
+    let mut bytes = Vec::new();
+    File::open(path).and_then(|mut file| file.read_to_end(&mut bytes)).ok()?;
+    let elf = elf::Elf::parse(&bytes).ok()?;
+    let (_, defined) = elf_exports(&elf, &bytes).ok()?;
+    defined.iter().filter_map(|version| version::glibc(version.name)).max()
+}
+
+fn hex (bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Finds, reads into `contents`, and checks the debug file for the build-id `expected`, as given by `--debug-info`
+fn find_build_id_file (debug_info: &str, expected: &[u8], contents: &mut Vec<u8>) -> error::Result<::std::path::PathBuf> {
+    if expected.len() < 2 {
+        return Err(error::Error::Malformed(format!("build-id {} is too short to locate debug info with", hex(expected))));
+    }
+    let id = hex(expected);
+    let relative = Path::new(&id[..2]).join(format!("{}.debug", &id[2..]));
+    let root = Path::new(debug_info);
+    let path = if debug_info == "auto" {
+        Path::new("/usr/lib/debug/.build-id").join(relative)
+    } else if root.is_dir() {
+        let build_id_dir = root.join(".build-id");
+        if build_id_dir.is_dir() { build_id_dir.join(relative) } else { root.join(relative) }
+    } else {
+        root.to_path_buf()
+    };
+    let mut fd = File::open(&path).map_err(|err| ::std::io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    contents.clear();
+    fd.read_to_end(contents)?;
+    let debug_elf = parse_elf(contents)?;
+    match build_id(&debug_elf, contents) {
+        Some(debug_id) if debug_id == expected => Ok(path),
+        Some(debug_id) => Err(error::Error::Malformed(format!("{} has build-id {}, but the binary has {}; it is debug info for a different build", path.display(), hex(debug_id), id))),
+        None => Err(error::Error::Malformed(format!("{} has no build-id, so it can't be checked against the binary's {}", path.display(), id))),
+    }
+}
+
+enum DebugLookup {
+    Found(::std::path::PathBuf),
+    /// The file exists, but isn't the one the binary was linked with
+    Mismatch(::std::path::PathBuf, u32),
+    Missing(Vec<::std::path::PathBuf>),
+}
+
+/// Searches the same places gdb does for the debug file of the binary at `path`, reading it into `contents` if found
+fn find_debug_file (path: &Path, link: &Debuglink, contents: &mut Vec<u8>) -> error::Result<DebugLookup> {
+    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    let absolute = ::std::fs::canonicalize(path).ok().and_then(|path| path.parent().map(|dir| dir.to_path_buf())).unwrap_or(dir.clone());
+    let global = Path::new("/usr/lib/debug").join(absolute.strip_prefix("/").unwrap_or(&absolute));
+    let candidates = vec![dir.join(link.name), dir.join(".debug").join(link.name), global.join(link.name)];
+    let mut mismatch = None;
+    for candidate in &candidates {
+        if let Ok(mut fd) = File::open(candidate) {
+            contents.clear();
+            fd.read_to_end(contents)?;
+            let crc = crc32(contents);
+            if crc == link.crc {
+                return Ok(DebugLookup::Found(candidate.clone()));
+            } else if mismatch.is_none() {
+                mismatch = Some((candidate.clone(), crc));
+            }
+        }
+    }
+    contents.clear();
+    Ok(match mismatch {
+        Some((path, crc)) => DebugLookup::Mismatch(path, crc),
+        None => DebugLookup::Missing(candidates),
+    })
+}
+
+/// Where the dynamic linker would load a DT_NEEDED library from
+enum LibraryLookup {
+    /// The name is a path, which is loaded as is
+    Direct(::std::path::PathBuf),
+    /// In a DT_RPATH or DT_RUNPATH directory, as it is written there
+    Path(&'static str, String, ::std::path::PathBuf),
+    /// In one of the default directories, or those of `--library-path`
+    Default(::std::path::PathBuf),
+    NotFound,
+}
+
+/// The directories searched after the rpaths; ld.so.cache usually lists the same ones on a multiarch system
+fn default_library_dirs (elf: &elf::Elf) -> Vec<String> {
+    use elf::header::{EM_386, EM_X86_64, EM_ARM, EM_AARCH64};
+    let triple = match elf.header.e_machine {
+        EM_386 => Some("i386-linux-gnu"),
+        EM_X86_64 => Some("x86_64-linux-gnu"),
+        EM_ARM => Some("arm-linux-gnueabihf"),
+        EM_AARCH64 => Some("aarch64-linux-gnu"),
+        _ => None,
+    };
+    let mut dirs = Vec::new();
+    if let Some(triple) = triple {
+        dirs.push(format!("/lib/{}", triple));
+        dirs.push(format!("/usr/lib/{}", triple));
+    }
+    if elf.is_64 {
+        dirs.push("/lib64".into());
+        dirs.push("/usr/lib64".into());
+    }
+    dirs.push("/lib".into());
+    dirs.push("/usr/lib".into());
+    dirs
+}
+
+/// The value of `$PLATFORM`, as glibc sets it for the machine
+fn platform (machine: u16) -> &'static str {
+    use elf::header::{EM_386, EM_X86_64, EM_ARM, EM_AARCH64};
+    match machine {
+        EM_386 => "i686",
+        EM_X86_64 => "x86_64",
+        EM_ARM => "v7l",
+        EM_AARCH64 => "aarch64",
+        _ => "unknown",
+    }
+}
+
+/// Where the dynamic linker would find each of the libraries of the binary at `path`, in `--sysroot` if given
+fn find_libraries (opt: &Opt, elf: &elf::Elf, path: &Path) -> Vec<LibraryLookup> {
+    use std::path::PathBuf;
+    let origin = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_string_lossy().into_owned(),
+        _ => ".".to_owned(),
+    };
+    let lib = if elf.is_64 { "lib64" } else { "lib" };
+    // $ORIGIN is where the binary is, here; every other directory is on the system it runs on
+    let dir = |dir: &str| -> PathBuf {
+        let expanded = dir.replace("${ORIGIN}", &origin).replace("$ORIGIN", &origin)
+            .replace("${LIB}", lib).replace("$LIB", lib)
+            .replace("${PLATFORM}", platform(elf.header.e_machine)).replace("$PLATFORM", platform(elf.header.e_machine));
+        match opt.sysroot {
+            Some(ref sysroot) if !dir.starts_with("$ORIGIN") && !dir.starts_with("${ORIGIN}") => Path::new(sysroot).join(expanded.trim_start_matches('/')),
+            _ => PathBuf::from(expanded),
+        }
+    };
+    let paths = |tag: u64| elf.dynamic.as_ref().map_or(Vec::new(), |dynamic| {
+        dynamic.dyns.iter().filter(|dyn| dyn.d_tag == tag).filter_map(|dyn| elf.dynstrtab.get(dyn.d_val as usize).ok()).collect::<Vec<_>>()
+    });
+    // DT_RPATH is ignored when there's a DT_RUNPATH
+    let (tag, rpaths) = match paths(elf::dyn::DT_RUNPATH) {
+        ref runpaths if !runpaths.is_empty() => ("RUNPATH", runpaths.clone()),
+        _ => ("RPATH", paths(elf::dyn::DT_RPATH)),
+    };
+    let rpaths = rpaths.iter().flat_map(|rpath| rpath.split(':')).filter(|rpath| !rpath.is_empty()).collect::<Vec<_>>();
+    let defaults = match opt.library_path {
+        Some(ref library_path) => library_path.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from).collect::<Vec<_>>(),
+        None => default_library_dirs(elf).iter().map(|default| dir(default)).collect(),
+    };
+    elf.libraries.iter().map(|library| {
+        if library.contains('/') {
+            let path = dir(library);
+            return if path.is_file() { LibraryLookup::Direct(path) } else { LibraryLookup::NotFound };
+        }
+        for rpath in &rpaths {
+            let candidate = dir(rpath).join(library);
+            if candidate.is_file() {
+                return LibraryLookup::Path(tag, rpath.to_string(), candidate);
+            }
+        }
+        match defaults.iter().map(|default| default.join(library)).find(|candidate| candidate.is_file()) {
+            Some(candidate) => LibraryLookup::Default(candidate),
+            None => LibraryLookup::NotFound,
+        }
+    }).collect()
+}
+
+impl LibraryLookup {
+    fn path (&self) -> Option<&Path> {
+        match *self {
+            LibraryLookup::Direct(ref path) | LibraryLookup::Path(_, _, ref path) | LibraryLookup::Default(ref path) => Some(path),
+            LibraryLookup::NotFound => None,
+        }
+    }
+}
+
+/// The library among `libraries` that first defines each exported dynamic symbol, as the dynamic linker searches them.
+///
+/// Only the direct dependencies are searched; libraries that can't be read or parsed, like linker scripts, are skipped.
+fn import_providers (libraries: &[String], lookups: &[LibraryLookup]) -> ::std::collections::HashMap<String, String> {
+    use elf::{sym, section_header};
+    let mut providers = ::std::collections::HashMap::new();
+    for (library, lookup) in libraries.iter().zip(lookups) {
+        let mut bytes = Vec::new();
+        match lookup.path().map(|path| File::open(path).and_then(|mut file| file.read_to_end(&mut bytes))) {
+            Some(Ok(_)) => (),
+            _ => continue,
+        }
+        let elf = match elf::Elf::parse(&bytes) {
+            Ok(elf) => elf,
+            Err(_) => continue,
+        };
+        let exports = elf.dynsyms.iter()
+            .filter(|sym| sym.st_shndx != section_header::SHN_UNDEF as usize && sym.st_bind() != sym::STB_LOCAL)
+            .filter(|sym| sym.st_other & 0x3 != STV_HIDDEN && sym.st_other & 0x3 != STV_INTERNAL);
+        for sym in exports {
+            if let Ok(name) = elf.dynstrtab.get(sym.st_name) {
+                providers.entry(name.to_owned()).or_insert_with(|| library.to_string());
+            }
+        }
+    }
+    providers
+}
+
+struct Elf<'a> {
+    elf: &'a elf::Elf<'a>,
+    bytes: &'a [u8],
+    opt: Opt,
+    /// Where each of the libraries resolves
+    libraries: Vec<LibraryLookup>,
+    /// The library defining each import, if `--resolve-imports` is on
+    import_providers: Option<::std::collections::HashMap<String, String>>,
+    /// The outcome of `--follow-debuglink`
+    debug_lookup: Option<DebugLookup>,
+    /// The debug file found by `--debug-info`
+    debug_info: Option<::std::path::PathBuf>,
+    /// The separate debug file, whose symbols replace the stripped ones
+    debug_elf: Option<elf::Elf<'a>>,
+    /// The interpreters to accept, if `--check-interp` is on
+    known_interpreters: Option<Vec<String>>,
+}
+
+/// The file offset of the bytes `sym` names, through its section, or the PT_LOAD segment mapping its address if the
+/// section headers are gone; None if it's undefined, absolute, or in .bss
+fn sym_file_offset (elf: &elf::Elf, sym: &elf::Sym, shndx: usize) -> Option<u64> {
+    use elf::section_header::{SHN_UNDEF, SHN_LORESERVE, SHT_NOBITS};
+    if shndx == SHN_UNDEF as usize || shndx >= SHN_LORESERVE as usize {
+        return None;
+    }
+    match elf.section_headers.get(shndx) {
+        Some(shdr) if shdr.sh_type == SHT_NOBITS => None,
+        // relocatable objects have section relative symbols
+        Some(shdr) if elf.header.e_type == elf::header::ET_REL => shdr.sh_offset.checked_add(sym.st_value),
+        Some(shdr) => sym.st_value.checked_sub(shdr.sh_addr).filter(|&delta| delta <= shdr.sh_size).and_then(|delta| shdr.sh_offset.checked_add(delta)),
+        None => vaddr_to_offset(elf, sym.st_value).map(|offset| offset as u64),
+    }
+}
+
+/// Prints the symbol table `name` of `elf`, with each line indented by `prefix`.
+///
+/// Symbols are paired with their string table, whether they came from separate debug info, and their real section index.
+fn fmt_syms (fmt: &mut ::std::fmt::Formatter, opt: &Opt, elf: &elf::Elf, name: &str, syms: &[(&elf::Sym, &goblin::strtab::Strtab, bool, usize)], prefix: &str) -> ::std::fmt::Result {
+    use elf::sym;
+    let colors = &opt.theme;
+    let layout = Layout::elf(opt, elf);
+    let total = syms.len();
+    let mut syms = syms.iter().filter(|&&(sym, _, _, _)| size_shown(opt, sym.st_size)).cloned().collect::<Vec<_>>();
+    match opt.sort_syms {
+        Some(SortSyms::Addr) => syms.sort_by_key(|&(sym, _, _, _)| sym.st_value),
+        Some(SortSyms::Name) => syms.sort_by(|&(a, a_strtab, _, _), &(b, b_strtab, _, _)| a_strtab.get(a.st_name).unwrap_or("").cmp(b_strtab.get(b.st_name).unwrap_or(""))),
+        Some(SortSyms::Size) => syms.sort_by_key(|&(sym, _, _, _)| ::std::cmp::Reverse(sym.st_size)),
+        None => (),
+    }
+    let syms = &syms;
+    if !opt.shows(syms.len()) {
+        return Ok(());
+    }
+    write!(fmt, "{}", prefix)?;
+    fmt_filtered_header(fmt, colors, name, syms.len(), total)?;
+    if opt.pretty {
+        let mut title = row![br->"Addr", bl->"Bind", bl->"Type", b->"Symbol", b->"Size", b->"Section", b->"Visibility"];
+        if opt.both_addrs {
+            title.insert_cell(1, Cell::new("Offset").style_spec("br"));
+        }
+        let mut table = new_table(title);
+        let row = |&(sym, strtab, dbg, shndx): &(&elf::Sym, &goblin::strtab::Strtab, bool, usize)| {
+            let bind_cell = {
+                let bind_cell = Cell::new(&format!("{:<8}",sym::bind_to_str(sym.st_bind())));
+                match sym.st_bind() {
+                    sym::STB_LOCAL => bind_cell.style_spec("bBCFD"),
+                    sym::STB_GLOBAL => bind_cell.style_spec("bBRFD"),
+                    sym::STB_WEAK => bind_cell.style_spec("bBMFD"),
+                    _ => bind_cell
+                }
+            };
+            let typ_cell = {
+                let typ_cell = Cell::new(&format!("{:<9}", sym::type_to_str(sym.st_type())));
+                match sym.st_type() {
+                    sym::STT_OBJECT => typ_cell.style_spec("bFY"),
+                    sym::STT_FUNC => typ_cell.style_spec("bFR"),
+                    sym::STT_GNU_IFUNC => typ_cell.style_spec("bFC"),
+                    _ => typ_cell
+                }
+            };
+            let mut row = Row::new(vec![
+                layout.func_addr_cell(sym.st_value, sym.st_type() == sym::STT_FUNC),
+                bind_cell,
+                typ_cell,
+                if dbg {
+                    Cell::new(&format!("{} (dbg)", symbol_name(opt, strtab_get(strtab, sym.st_name)))).style_spec("iFy")
+                } else {
+                    string_cell(opt, strtab_get(strtab, sym.st_name))
+                },
+                layout.sz_cell(sym.st_size),
+                shndx_cell(shndx, &elf.section_headers, &elf.shdr_strtab),
+                visibility_cell(sym.st_other),
+            ]);
+            if opt.both_addrs {
+                row.insert_cell(1, sym_file_offset(elf, sym, shndx).map_or(Cell::new(""), |offset| layout.offsetx_cell(offset)));
+            }
+            row
+        };
+        // the rows come back in symbol order either way
+        #[cfg(feature = "parallel")]
+        let rows = {
+            use rayon::prelude::*;
+            syms.par_iter().map(row).collect::<Vec<_>>()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let rows = syms.iter().map(row).collect::<Vec<_>>();
+        for row in rows {
+            table.add_row(row);
+        }
+        table.get_format().indent(prefix.chars().count());
+        fmt_table(fmt, opt, &table)?;
+    } else {
+        let bind_width = name_width(opt, syms.iter().map(|&(sym, _, _, _)| sym::bind_to_str(sym.st_bind())), 8);
+        let typ_width = name_width(opt, syms.iter().map(|&(sym, _, _, _)| sym::type_to_str(sym.st_type())), 9);
+        let sym_width = name_width(opt, syms.iter().map(|&(sym, strtab, _, _)| symbol_name(opt, strtab.get(sym.st_name).unwrap_or(""))), 0);
+        for &(sym, strtab, dbg, shndx) in syms {
+            let bind = {
+                let bind_str = truncate(opt, sym::bind_to_str(sym.st_bind()), 8).reverse().bold();
+                match sym.st_bind() {
+                    sym::STB_LOCAL => bind_str.cyan(),
+                    sym::STB_GLOBAL => bind_str.red(),
+                    sym::STB_WEAK => bind_str.magenta(),
+                    _ => bind_str.normal().clear(),
+                }
+            };
+            let typ_cell = {
+                let typ_str = truncate(opt, sym::type_to_str(sym.st_type()), 9).bold();
+                match sym.st_type() {
+                    sym::STT_OBJECT => typ_str.yellow(),
+                    sym::STT_FUNC => typ_str.red(),
+                    sym::STT_GNU_IFUNC => typ_str.cyan(),
+                    _ => typ_str.clear(),
+                }
+            };
+            write!(fmt, "{}{} ", prefix, layout.func_addr(sym.st_value, sym.st_type() == sym::STT_FUNC))?;
+            write!(fmt, "{:<bind_width$} {:<typ_width$} ", bind, typ_cell, bind_width = bind_width, typ_width = typ_width)?;
+            write!(fmt, "{:<width$} ", string(opt, strtab_get(strtab, sym.st_name)), width = sym_width)?;
+            if dbg {
+                write!(fmt, "{} ", "(dbg)".dimmed())?;
+            }
+            write!(fmt, "st_size: {} ",  layout.sz(sym.st_size))?;
+            if let Some(offset) = sym_file_offset(elf, sym, shndx).filter(|_| opt.both_addrs) {
+                write!(fmt, "offset: {} ", layout.off(offset))?;
+            }
+            let vis = visibility(sym.st_other);
+            let vis = match sym.st_other & 0x3 {
+                STV_HIDDEN => vis.dimmed(),
+                STV_PROTECTED => vis.yellow(),
+                _ => vis.normal(),
+            };
+            write!(fmt, "visibility: {} ", vis)?;
+            writeln!(fmt, "st_shndx: {:#x}", shndx)?;
+        }
+    }
+    fmt_total_size(fmt, opt, layout, syms.iter().map(|&(sym, _, _, _)| sym.st_size), prefix)?;
+    writeln!(fmt)?;
+    Ok(())
+}
+
+/// The segment type of the GNU property note
+const PT_GNU_PROPERTY: u32 = 0x6474e553;
+
+fn pt_to_str (p_type: u32) -> &'static str {
+    match p_type {
+        PT_GNU_PROPERTY => "PT_GNU_PROPERTY",
+        _ => elf::program_header::pt_to_str(p_type),
+    }
+}
+
+/// Whether `phdr` is a PT_GNU_STACK that asks for an executable stack
+fn is_exec_stack (phdr: &elf::ProgramHeader) -> bool {
+    phdr.p_type == elf::program_header::PT_GNU_STACK && phdr.p_flags & elf::program_header::PF_X != 0
+}
+
+impl<'a> Elf<'a> {
+    /// The GNU program properties, from `.note.gnu.property` or the PT_GNU_PROPERTY segment if the section headers are stripped
+    fn properties(&self) -> Vec<note::Property<'a>> {
+        let shdr = self.elf.section_headers.iter()
+            .find(|shdr| shdr.sh_type == elf::section_header::SHT_NOTE && self.elf.shdr_strtab.get(shdr.sh_name).ok() == Some(".note.gnu.property"));
+        let notes = match shdr {
+            Some(shdr) => section_bytes(self.bytes, shdr).ok().map(|bytes| (bytes, shdr.sh_addralign)),
+            None => self.elf.program_headers.iter().find(|phdr| phdr.p_type == PT_GNU_PROPERTY)
+                .and_then(|phdr| self.bytes.get(phdr.p_offset as usize..phdr.p_offset.saturating_add(phdr.p_filesz) as usize).map(|bytes| (bytes, phdr.p_align))),
+        };
+        let notes = match notes.map(|(bytes, align)| note::parse(bytes, align, self.elf.little_endian)) {
+            Some(Ok(notes)) => notes,
+            _ => return Vec::new(),
+        };
+        notes.into_iter()
+            .filter(|note| note.name == note::NOTE_GNU && note.n_type == note::NT_GNU_PROPERTY_TYPE_0)
+            .flat_map(|note| note::properties(note.desc, self.elf.is_64, self.elf.little_endian).unwrap_or_default())
+            .collect()
+    }
+
+    /// Prints the segments and sections containing file `offset`, with the address it has in each; returns the
+    /// index of the containing section, and the address the PT_LOAD covering it maps it at, if any
+    fn fmt_containers(&self, fmt: &mut ::std::fmt::Formatter, offset: u64) -> Result<(Option<usize>, Option<u64>), ::std::fmt::Error> {
+        let layout = Layout::elf(&self.opt, self.elf);
+        let mut vaddr = None;
+        for (i, phdr) in self.elf.program_headers.iter().enumerate() {
+            if offset >= phdr.p_offset && offset < phdr.p_offset.saturating_add(phdr.p_filesz) {
+                let address = (offset - phdr.p_offset).wrapping_add(phdr.p_vaddr);
+                writeln!(fmt, "  ├──{}({}) ∈ {}", pt_to_str(phdr.p_type), i, layout.addrx(address))?;
+                if phdr.p_type == elf::program_header::PT_LOAD {
+                    vaddr = Some(address);
+                }
+            }
+        }
+        let mut section = None;
+        for (i, shdr) in self.elf.section_headers.iter().enumerate() {
+            if offset >= shdr.sh_offset && offset < shdr.sh_offset.saturating_add(shdr.sh_size) && shdr.sh_type != elf::section_header::SHT_NOBITS {
+                writeln!(fmt, "  ├──{}({}) ∈ {}", strtab_get(&self.elf.shdr_strtab, shdr.sh_name), i, layout.addrx((offset - shdr.sh_offset).wrapping_add(shdr.sh_addr)))?;
+                section = Some(i);
+            }
+        }
+        Ok((section, vaddr))
+    }
+
+    /// The value of the DT_FLAGS_1 entry, or 0 without one
+    fn flags_1(&self) -> u64 {
+        match self.elf.dynamic {
+            Some(ref dynamic) => dynamic.dyns.iter().find(|dyn| dyn.d_tag == elf::dyn::DT_FLAGS_1).map(|dyn| dyn.d_val).unwrap_or(0),
+            None => 0,
+        }
+    }
+}
+
+impl<'a> ::std::fmt::Display for Elf<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        use elf::header;
+        use elf::program_header;
+        use elf::section_header;
+        use elf::sym;
+        use elf::Dynamic;
+        use elf::Syms;
+        use elf::strtab::Strtab;
+        use elf::reloc::{self, Reloc};
+        use colored::*;
+        let opt = &self.opt;
+        let colors = &opt.theme;
+        let layout = Layout::elf(opt, self.elf);
+        let header = &self.elf.header;
+        let endianness = if self.elf.little_endian { "little-endian" } else { "big-endian" };
+        let kind = {
+            let typ_cell = header.e_type;
+            let kind_str = header::et_to_str(typ_cell).reverse().bold();
+            match typ_cell {
+                header::ET_REL =>  kind_str.yellow(),
+                header::ET_EXEC => kind_str.red(),
+                header::ET_DYN =>  kind_str.blue(),
+                header::ET_CORE => kind_str.black(),
+                _ => kind_str.normal(),
+            }
+        };
+        let machine = header.e_machine;
+        let machine_str = {
+            header::machine_to_str(machine).bold()
+        };
+        let shdr_strtab = &self.elf.shdr_strtab;
+        let gopclntab_data = self.elf.section_headers.iter()
+            .find(|shdr| shdr_strtab.get(shdr.sh_name).ok() == Some(gopclntab::ELF_SECTION))
+            .and_then(|shdr| section_data(&self.opt, self.elf, self.bytes, shdr).ok());
+        let gopclntab = gopclntab_data.as_ref().and_then(|bytes| gopclntab::parse(bytes, self.elf.little_endian).ok());
+        write!(fmt, "{} {} {}-{} @ {}",
+                 hdr(colors, "ELF"),
+                 kind,
+                 machine_str,
+                 endianness,
+                 layout.codex(self.elf.entry),
+        )?;
+        if let Some(ref pclntab) = gopclntab {
+            write!(fmt, " {}", pclntab.version.as_str().cyan().bold())?;
+        }
+        if let Some(interpreter) = self.elf.interpreter {
+            write!(fmt, " interp: {}", interpreter.yellow())?;
+            if let Some(libc) = interpreter_libc(interpreter) {
+                write!(fmt, " ({})", libc.bold())?;
+            }
+            if let Some(warning) = interpreter_warning(interpreter) {
+                write!(fmt, " {}", format!("[SUSPICIOUS INTERPRETER: {}]", warning).red().bold())?;
+            }
+            if let Some(ref known) = self.known_interpreters {
+                if !known.iter().any(|known| known == interpreter) {
+                    write!(fmt, " {}", "[UNUSUAL INTERPRETER]".red().bold())?;
+                }
+            }
+        } else if header.e_type == header::ET_EXEC {
+            write!(fmt, " {}", "static".bold())?;
+        } else if header.e_type == header::ET_DYN && self.flags_1() & DF_1_PIE != 0 {
+            // a PIE without an interpreter relocates itself
+            write!(fmt, " {}", "static-pie".bold())?;
+        }
+        if header.e_type == header::ET_EXEC || header.e_type == header::ET_DYN {
+            if is_pie(self.elf) {
+                write!(fmt, " {}", "[PIE]".green().bold())?;
+            } else {
+                write!(fmt, " {}", "[NOT PIE]".red().bold())?;
+            }
+        }
+        writeln!(fmt, ":")?;
+        writeln!(fmt, "")?;
+        writeln!(fmt, "e_phoff: {} e_shoff: {} e_flags: {:#x} e_ehsize: {} e_phentsize: {} e_phnum: {} e_shentsize: {} e_shnum: {} e_shstrndx: {}",
+                 layout.off(header.e_phoff),
+                 layout.off(header.e_shoff),
+                 header.e_flags,
+                 header.e_ehsize,
+                 header.e_phentsize,
+                 header.e_phnum,
+                 header.e_shentsize,
+                 header.e_shnum,
+                 header.e_shstrndx,
+        )?;
+        let ident = &header.e_ident;
+        let data = match ident[header::EI_DATA] {
+            header::ELFDATA2LSB => "2's complement, little endian",
+            header::ELFDATA2MSB => "2's complement, big endian",
+            _ => "invalid",
+        };
+        write!(fmt, "class: {} data: {} version: {} osabi: {}({}) abiversion: {}",
+               if self.elf.is_64 { "ELF64" } else { "ELF32" },
+               data,
+               ident[EI_VERSION],
+               osabi_to_str(ident[EI_OSABI]).bold(),
+               ident[EI_OSABI],
+               ident[EI_ABIVERSION],
+        )?;
+        let padding = &ident[EI_PAD..];
+        if padding.iter().any(|&b| b != 0) {
+            // nothing reads these, which makes them a place to stash a marker or a key
+            write!(fmt, " {}", format!("[NONZERO PADDING: {}]", hex(padding)).red().bold())?;
+        }
+        writeln!(fmt)?;
+        let tag = abi_tag(self.elf, self.bytes);
+        let glibc = glibc_needed(self.elf, self.bytes);
+        if let Some(ref tag) = tag {
+            write!(fmt, "abi tag: {} {}", note::abi_os_to_str(tag.os).bold(), version::glibc_to_string(&tag.version))?;
+        }
+        if let Some(ref needed) = glibc {
+            write!(fmt, "{}glibc needed: {}", if tag.is_some() { " " } else { "" }, version::glibc_to_string(needed).bold())?;
+            if self.opt.check_abi {
+                let libc = self.elf.libraries.iter().zip(&self.libraries)
+                    .find(|&(library, _)| library.starts_with("libc.so"))
+                    .and_then(|(_, lookup)| lookup.path());
+                match libc.and_then(|path| glibc_provided(path).map(|provided| (path, provided))) {
+                    Some((path, ref provided)) => {
+                        write!(fmt, " runtime: {} {}", version::glibc_to_string(provided), format!("({})", path.display()).dimmed())?;
+                        if provided < needed {
+                            write!(fmt, " {}", "[GLIBC TOO OLD]".red().bold())?;
+                        } else {
+                            write!(fmt, " {}", "[OK]".green())?;
+                        }
+                    },
+                    None => write!(fmt, " {}", "[NO RUNTIME GLIBC]".red().bold())?,
+                }
+            }
+        }
+        if tag.is_some() || glibc.is_some() {
+            writeln!(fmt)?;
+        }
+        writeln!(fmt, "")?;
+
+        if self.opt.arch_info {
+            let mut info = arch::elf_flags(header.e_machine, self.elf.is_64, header.e_flags);
+            // x86 has no e_flags, its ISA level and features are in the GNU properties
+            let isa = self.properties().into_iter().find(|property| property.pr_type == note::GNU_PROPERTY_X86_ISA_1_NEEDED && note::is_x86(header.e_machine));
+            if let Some(mask) = isa.and_then(|property| property.value) {
+                let levels = note::property_bits(note::GNU_PROPERTY_X86_ISA_1_NEEDED, header.e_machine).iter()
+                    .filter(|&&(bit, _)| mask as u32 & bit != 0).map(|&(_, name)| name).collect::<Vec<_>>();
+                info.push(("ISA needed", levels.join(" ")));
+            }
+            fmt_arch_info(fmt, colors, &info)?;
+        }
+
+        let ph_width = name_width(&self.opt, self.elf.program_headers.iter().map(|phdr| pt_to_str(phdr.p_type)), 16);
+        let ph_name = |phdr: &elf::ProgramHeader| {
+            let typ_cell = phdr.p_type;
+            let name = truncate(&self.opt, pt_to_str(typ_cell), 16);
+            match typ_cell {
+                program_header::PT_LOAD    => name.red(),
+                program_header::PT_INTERP  => name.yellow(),
+                program_header::PT_DYNAMIC => name.cyan(),
+                program_header::PT_GNU_STACK if is_exec_stack(phdr) => name.red().bold(),
+                program_header::PT_GNU_RELRO => name.green(),
+                _ => name.normal()
+            }
+        };
+        // the hardening features PT_GNU_PROPERTY turns on, e.g. IBT and SHSTK
+        let features = {
+            let machine = self.elf.header.e_machine;
+            self.properties().into_iter()
+                .filter(|property| property.pr_type == note::GNU_PROPERTY_X86_FEATURE_1_AND || property.pr_type == note::GNU_PROPERTY_AARCH64_FEATURE_1_AND)
+                .flat_map(|property| {
+                    let mask = property.value.unwrap_or(0) as u32;
+                    note::property_bits(property.pr_type, machine).iter().filter(move |&&(bit, _)| mask & bit != 0).map(|&(_, name)| name)
+                })
+                .collect::<Vec<_>>()
+        };
+        let ph_annotation = |phdr: &elf::ProgramHeader| {
+            match phdr.p_type {
+                program_header::PT_GNU_STACK if is_exec_stack(phdr) => "[EXECUTABLE STACK]".red().bold(),
+                program_header::PT_GNU_RELRO => "[RELRO]".green(),
+                PT_GNU_PROPERTY if !features.is_empty() => format!("[{}]", features.join(" ")).green(),
+                _ => "".normal(),
+            }
+        };
+
+        let ph_flag = |phdr: &elf::ProgramHeader| {
+            let wx = program_header::PF_W|program_header::PF_X;
+            let rx = program_header::PF_R|program_header::PF_X;
+            let rwx = program_header::PF_R|program_header::PF_W|program_header::PF_X;
+            let rw = program_header::PF_R|program_header::PF_W;
+            let flags = phdr.p_flags;
+            if flags == rwx { "RW+X".to_owned() }
+            else if flags == rw { "RW".to_owned() }
+            else if flags == rx { "R+X".to_owned() }
+            else if flags == wx { "W+X".to_owned() }
+            else if flags == program_header::PF_R { "R".to_owned() }
+            else if flags == program_header::PF_W { "W".to_owned() }
+            else if flags == program_header::PF_R { "R".to_owned() }
+            else { format!("{:#x}", flags) }
+        };
+
+        let phdrs = &self.elf.program_headers;
+        let shown_phdrs = phdrs.iter().enumerate().filter(|&(_, phdr)| size_shown(&self.opt, phdr.p_filesz)).collect::<Vec<_>>();
+        if self.opt.shows(shown_phdrs.len()) {
+            fmt_filtered_header(fmt, colors, "ProgramHeaders", shown_phdrs.len(), phdrs.len())?;
+        }
+        if self.opt.pretty {
+            let mut phdr_table = new_table(row![b->"Idx", b->"Type", b->"Flags", b->"Offset", b->"Vaddr", b->"Paddr", b->"Filesz", b->"Memsz", b->"Align"]);
+            let ph_name_table = |phdr: &elf::ProgramHeader| {
+                let typ_cell = phdr.p_type;
+                let name = pt_to_str(typ_cell);
+                match typ_cell {
+                    program_header::PT_LOAD    => Cell::new(name).style_spec("Fr"),
+                    program_header::PT_INTERP  => Cell::new(name).style_spec("Fy"),
+                    program_header::PT_DYNAMIC => Cell::new(name).style_spec("Fc"),
+                    program_header::PT_GNU_STACK if is_exec_stack(phdr) => Cell::new(&format!("{} [EXECUTABLE STACK]", name)).style_spec("bFr"),
+                    program_header::PT_GNU_RELRO => Cell::new(name).style_spec("Fg"),
+                    _ =>  Cell::new(name),
+                }
+            };
+            for &(i, phdr) in &shown_phdrs {
+                let name_cell = ph_name_table(&phdr);
+                let flags = ph_flag(&phdr);
+                phdr_table.add_row(Row::new(vec![
+                    Cell::new(&i.to_string()),
+                    name_cell,
+                    if is_exec_stack(phdr) { Cell::new(&flags).style_spec("bFr") } else { Cell::new(&flags) },
+                    layout.offsetx_cell(phdr.p_offset),
+                    layout.addrx_cell(phdr.p_vaddr),
+                    layout.memx_cell(phdr.p_paddr),
+                    layout.sz_cell(phdr.p_filesz),
+                    layout.memsz_cell(phdr.p_filesz),
+                    x_cell(phdr.p_align),
+                ]));
+            }
+            fmt_table(fmt, &self.opt, &phdr_table)?;
+        } else {
+            for &(i, phdr) in &shown_phdrs {
+                let name = ph_name(&phdr);
+                let flags = ph_flag(&phdr);
+                write!(fmt, "{} ", layout.idx(i))?;
+                write!(fmt, "{:<width$} ", name, width = ph_width)?;
+                write!(fmt, "{:>4} ", if is_exec_stack(phdr) { flags.red().bold() } else { flags.normal() })?;
+                write!(fmt, "p_offset: {:<16} ", layout.off(phdr.p_offset))?;
+                write!(fmt, "p_vaddr: {:<16} ", layout.addrx(phdr.p_vaddr))?;
+                write!(fmt, "p_paddr: {:<16} ", layout.addrx(phdr.p_paddr).bold())?;
+                write!(fmt, "p_filesz: {:<16} ", layout.sz(phdr.p_filesz))?;
+                write!(fmt, "p_memsz: {:<16} ", layout.sz(phdr.p_memsz).bold())?;
+                write!(fmt, "p_flags: {:#x} ", phdr.p_flags)?;
+                write!(fmt, "p_align: {:#x}", phdr.p_align)?;
+                let annotation = ph_annotation(phdr);
+                if annotation.is_empty() {
+                    writeln!(fmt)?;
+                } else {
+                    writeln!(fmt, " {}", annotation)?;
+                }
+            }
+        }
+        if self.opt.shows(shown_phdrs.len()) {
+            fmt_total_size(fmt, &self.opt, layout, shown_phdrs.iter().map(|&(_, phdr)| phdr.p_filesz), "")?;
+            writeln!(fmt)?;
+        }
+
+        let mut shown_shdrs = self.elf.section_headers.iter().enumerate().filter(|&(_, shdr)| size_shown(&self.opt, shdr.sh_size)).collect::<Vec<_>>();
+        match self.opt.sort_sections {
+            Some(SortSections::Offset) => shown_shdrs.sort_by_key(|&(_, shdr)| shdr.sh_offset),
+            Some(SortSections::Addr) => shown_shdrs.sort_by_key(|&(_, shdr)| shdr.sh_addr),
+            Some(SortSections::Size) => shown_shdrs.sort_by_key(|&(_, shdr)| ::std::cmp::Reverse(shdr.sh_size)),
+            Some(SortSections::Name) => shown_shdrs.sort_by(|&(_, a), &(_, b)| shdr_strtab.get(a.sh_name).unwrap_or("").cmp(shdr_strtab.get(b.sh_name).unwrap_or(""))),
+            None => (),
+        }
+        if self.opt.shows(shown_shdrs.len()) {
+            fmt_filtered_header(fmt, colors, "SectionHeaders", shown_shdrs.len(), self.elf.section_headers.len())?;
+        }
+        let sh_width = name_width(&self.opt, self.elf.section_headers.iter().map(|shdr| shdr_strtab.get(shdr.sh_name).unwrap_or("")), 16);
+        let mut shdr_table = new_table(row![b->"Idx", b->"Name", br->"Type", b->"Flags", b->"Offset", b->"Addr", b->"Size", b->"Link", b->"Entsize", b->"Align"]);
+        if self.opt.preview {
+            if let Some(title) = shdr_table.get_mut_row(0) { title.add_cell(Cell::new("Preview").style_spec("b")) }
+        }
+        if self.opt.pretty {
+            for &(i, shdr) in &shown_shdrs {
+                let name_cell = {
+                    let name = strtab_get(shdr_strtab, shdr.sh_name);
+                    if i % 2 == 0 { Cell::new(name).style_spec("FdBw") } else { Cell::new(name).style_spec("FwBd") }
+                };
+                let flags_cell = {
+                    let shflags = shdr.sh_flags as u32;
+                    if shflags != 0 {
+                        let mut flags = String::new();
+                        for flag in &section_header::SHF_FLAGS {
+                            let flag = *flag;
+                            if shflags & flag == flag {
+                                flags += &section_header::shf_to_str(flag).to_string().split_off(4);
+                                flags += " ";
+                            }
+                        }
+                        if let Some(compression) = compression(self.elf, self.bytes, shdr) {
+                            flags += &compression;
+                        }
+                        Cell::new(&flags).style_spec("lbW")
+                    } else {
+                        Cell::new("")
+                    }
+                };
+                shdr_table.add_row(Row::new(vec![
+                    layout.idx_cell(i),
+                    name_cell,
+                    Cell::new(section_header::sht_to_str(shdr.sh_type)).style_spec("r"),
+                    flags_cell,
+                    layout.offsetx_cell(shdr.sh_offset),
+                    layout.memx_cell(shdr.sh_addr),
+                    layout.memsz_cell(shdr.sh_size),
+                    shndx_cell(shdr.sh_link as usize, &self.elf.section_headers, &self.elf.shdr_strtab),
+                    x_cell(shdr.sh_entsize),
+                    x_cell(shdr.sh_addralign),
+                ]));
+                if self.opt.preview {
+                    let row = shdr_table.len() - 1;
+                    if let Some(row) = shdr_table.get_mut_row(row) { row.add_cell(Cell::new(&section_preview(self.bytes, shdr)).style_spec("d")) }
+                }
+            }
+            fmt_table(fmt, &self.opt, &shdr_table)?;
+        } else {
+            for &(i, shdr) in &shown_shdrs {
+                let name = {
+                    let name = truncate(&self.opt, strtab_get(shdr_strtab, shdr.sh_name), 16);
+                    if i % 2 == 0 { name.white().on_black() } else { name.black().on_white() }
+                };
+                write!(fmt, "{} {:<width$} ", layout.idx(i), name, width = sh_width)?;
+                write!(fmt, "{} ", section_header::sht_to_str(shdr.sh_type))?;
+                write!(fmt, "sh_offset: {} ", layout.off(shdr.sh_offset))?;
+                write!(fmt, "sh_addr: {} ", layout.addrx(shdr.sh_addr))?;
+                write!(fmt, "sh_size: {} ", layout.sz(shdr.sh_size))?;
+                write!(fmt, "sh_link: {} "   , shdr.sh_link)?;
+                write!(fmt, "sh_info: {:#x} ", shdr.sh_info)?;
+                write!(fmt, "sh_entsize: {:#x} ", shdr.sh_entsize)?;
+                write!(fmt, "sh_flags: {:#x} ", shdr.sh_flags)?;
+                write!(fmt, "sh_addralign: {:#x} ", shdr.sh_addralign)?;
+                let shflags = shdr.sh_flags as u32;
+                if shflags != 0 {
+                    writeln!(fmt)?;
+                    write!(fmt, "{:<width$}", "", width = sh_width)?;
+                    for flag in &section_header::SHF_FLAGS {
+                        let flag = *flag;
+                        if shflags & flag == flag {
+                            write!(fmt, "{} ", section_header::shf_to_str(flag).to_string().split_off(4).bold())?;
+                        }
+                    }
+                    if let Some(compression) = compression(self.elf, self.bytes, shdr) {
+                        write!(fmt, "({})", compression)?;
+                    }
+                }
+                if self.opt.preview && shdr.sh_size != 0 {
+                    writeln!(fmt)?;
+                    write!(fmt, "{:<width$}{}", "", section_preview(self.bytes, shdr).dimmed(), width = sh_width)?;
+                }
+                writeln!(fmt)?;
+            }
+        }
+        if self.opt.shows(shown_shdrs.len()) {
+            fmt_total_size(fmt, &self.opt, layout, shown_shdrs.iter().map(|&(_, shdr)| shdr.sh_size), "")?;
+            writeln!(fmt)?;
+        }
+
+        let groups = self.elf.section_headers.iter().enumerate().filter(|&(_, shdr)| shdr.sh_type == section_header::SHT_GROUP).collect::<Vec<_>>();
+        if !groups.is_empty() {
+            fmt_header(fmt, colors, "Groups", groups.len())?;
+            let le = scroll::Endian::from(self.elf.little_endian);
+            for (i, shdr) in groups {
+                // the signature is the symbol sh_info in the symbol table sh_link
+                let dynamic = self.elf.section_headers.get(shdr.sh_link as usize).is_some_and(|symtab| symtab.sh_type == section_header::SHT_DYNSYM);
+                let (syms, strtab) = if dynamic { (&self.elf.dynsyms, &self.elf.dynstrtab) } else { (&self.elf.syms, &self.elf.strtab) };
+                let words = section_bytes(self.bytes, shdr).unwrap_or(&[]).chunks(4).filter_map(|word| word.pread_with::<u32>(0, le).ok()).collect::<Vec<_>>();
+                write!(fmt, "{} {:<width$} ", layout.idx(i), strtab_get(shdr_strtab, shdr.sh_name), width = sh_width)?;
+                match words.first() {
+                    Some(&flags) if flags & GRP_COMDAT != 0 => write!(fmt, "{} ", "COMDAT".bold())?,
+                    Some(&flags) => write!(fmt, "flags: {:#x} ", flags)?,
+                    None => write!(fmt, "{} ", "empty".dimmed())?,
+                }
+                write!(fmt, "group ")?;
+                match syms.get(shdr.sh_info as usize) {
+                    // a section symbol names the group after its section
+                    Some(sym) if sym.st_type() == sym::STT_SECTION => match self.elf.section_headers.get(sym.st_shndx) {
+                        Some(section) => write!(fmt, "'{}' ", string(&self.opt, strtab_get(shdr_strtab, section.sh_name)))?,
+                        None => write!(fmt, "{} ", BAD_INDEX.red())?,
+                    },
+                    Some(sym) => write!(fmt, "'{}' ", string(&self.opt, strtab_get(strtab, sym.st_name)))?,
+                    None => write!(fmt, "{} ", BAD_INDEX.red())?,
+                }
+                write!(fmt, "containing [")?;
+                for (j, &member) in words.iter().skip(1).enumerate() {
+                    if j != 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    match self.elf.section_headers.get(member as usize) {
+                        Some(section) => write!(fmt, "{}", section_name(&self.opt, strtab_get(shdr_strtab, section.sh_name)))?,
+                        None => write!(fmt, "{}", format!("{}({})", BAD_INDEX, member).red())?,
+                    }
+                }
+                writeln!(fmt, "]")?;
+            }
+            writeln!(fmt)?;
+        }
+
+        let bitcode = self.elf.section_headers.iter().filter(|shdr| strtab_get(shdr_strtab, shdr.sh_name) == bitcode::ELF_SECTION).collect::<Vec<_>>();
+        if !bitcode.is_empty() {
+            fmt_header(fmt, colors, "Embedded Bitcode", bitcode.len())?;
+            for shdr in bitcode {
+                writeln!(fmt, "{} offset: {} size: {}", section_name(&self.opt, bitcode::ELF_SECTION), layout.off(shdr.sh_offset), layout.sz(shdr.sh_size))?;
+            }
+            writeln!(fmt)?;
+        }
+
+        fmt_init_arrays(fmt, &self.opt, self.elf, self.bytes)?;
+
+        let dyn_strtab = &self.elf.dynstrtab;
+        let strtab = &self.elf.strtab;
+        let xindex = xindex_table(self.elf, self.bytes, elf::section_header::SHT_SYMTAB);
+        let dyn_xindex = xindex_table(self.elf, self.bytes, elf::section_header::SHT_DYNSYM);
+        let mut syms = self.elf.syms.iter().enumerate().map(|(i, sym)| (sym, strtab, false, real_shndx(sym, i, &xindex))).collect::<Vec<_>>();
+        if let Some(ref debug_elf) = self.debug_elf {
+            // merge in the debug symbols the binary doesn't already have
+            let known = self.elf.syms.iter().map(|sym| (sym.st_value, strtab.get(sym.st_name).unwrap_or(""))).collect::<::std::collections::HashSet<_>>();
+            for sym in &debug_elf.syms {
+                if !known.contains(&(sym.st_value, debug_elf.strtab.get(sym.st_name).unwrap_or(""))) {
+                    syms.push((sym, &debug_elf.strtab, true, sym.st_shndx));
+                }
+            }
+        }
+        let visible = |&&(sym, _, _, _): &&(&elf::Sym, &Strtab, bool, usize)| match self.opt.only_visibility {
+            Some(Visibility(visibility)) => sym.st_other & 0x3 == visibility,
+            None => true,
+        };
+        let syms = syms.iter().filter(&visible).cloned().collect::<Vec<_>>();
+        fmt_syms(fmt, &self.opt, self.elf, "Syms", &syms, "")?;
+        let dynsyms = self.elf.dynsyms.iter().enumerate().map(|(i, sym)| (sym, dyn_strtab, false, real_shndx(sym, i, &dyn_xindex))).collect::<Vec<_>>();
+        let dynsyms = dynsyms.iter().filter(&visible).cloned().collect::<Vec<_>>();
+        fmt_syms(fmt, &self.opt, self.elf, "Dyn Syms", &dynsyms, "")?;
+        if let Some(ref pclntab) = gopclntab {
+            fmt_gopclntab(fmt, &self.opt, layout, pclntab)?;
+        }
+
+        let version_section = |typ| self.elf.section_headers.iter().find(|shdr| shdr.sh_type == typ).and_then(|shdr| section_bytes(self.bytes, shdr).ok());
+        let versyms = version_section(section_header::SHT_GNU_VERSYM).map(|data| version::versym(data, self.elf.little_endian)).unwrap_or_default();
+        let needed = version_section(section_header::SHT_GNU_VERNEED).and_then(|data| version::verneed(data, self.elf.little_endian, dyn_strtab).ok()).unwrap_or_default();
+        // the library a dynamic symbol binds to, by its version requirement
+        let needed_by = |i: usize| {
+            let index = versyms.get(i).map(|versym| versym & version::VERSYM_VERSION).unwrap_or(version::VER_NDX_GLOBAL);
+            needed.iter().find(|needed| needed.index == index)
+        };
+        let keep_reloc = |reloc: &Reloc, syms: &Syms, strtab: &Strtab| -> bool {
+            // r_to_str leaves off the R_ prefix
+            let typ = reloc::r_to_str(reloc.r_type, machine);
+            if self.opt.no_relative && typ.ends_with("_RELATIVE") {
+                return false;
+            }
+            if !self.opt.reloc_type.is_empty() && !self.opt.reloc_type.iter().any(|wanted| wanted.trim_start_matches("R_").eq_ignore_ascii_case(typ)) {
+                return false;
+            }
+            match self.opt.reloc_sym {
+                Some(ref pattern) => {
+                    let name = syms.get(reloc.r_sym).and_then(|sym| strtab.get(sym.st_name).ok()).unwrap_or("");
+                    glob_match(pattern, name) || glob_match(pattern, &symbol_name(&self.opt, name))
+                },
+                None => true,
+            }
+        };
+        let filtering = self.opt.no_relative || !self.opt.reloc_type.is_empty() || self.opt.reloc_sym.is_some();
+        // the allocated section an address is in
+        let section_at = |address: u64| self.elf.section_headers.iter()
+            .find(|shdr| shdr.is_alloc() && shdr.sh_addr <= address && address - shdr.sh_addr < shdr.sh_size)
+            .map(|shdr| strtab_get(shdr_strtab, shdr.sh_name));
+        // the section name of a symbol without a name, like a section symbol
+        let unnamed_sym = |sym: &elf::Sym, index: usize, xindex: &[u32]| -> String {
+            if sym.st_type() == sym::STT_SECTION {
+                match self.elf.section_headers.get(real_shndx(sym, index, xindex)) {
+                    Some(shdr) => section_name(&self.opt, strtab_get(shdr_strtab, shdr.sh_name)),
+                    None => "BAD_IDX".into(),
+                }
+            } else {
+                "ABS".into()
+            }
+        };
+        // the sections a folded run of relocations spans, from the first to the last
+        let folded_sections = |first: &Reloc, last: &Reloc| -> String {
+            let first = section_at(first.r_offset as u64).unwrap_or("<no section>");
+            let last = section_at(last.r_offset as u64).unwrap_or("<no section>");
+            if first == last { first.to_owned() } else { format!("{} to {}", first, last) }
+        };
+        // the relocations of a section apply to `target`; dynamic relocations apply to whichever section their address is in
+        let fmt_relocs = |fmt: &mut ::std::fmt::Formatter, relocs: &[Reloc], syms: &Syms, strtab: &Strtab, xindex: &[u32], dynamic: bool, target: Option<&str> | -> ::std::fmt::Result {
+            if self.opt.pretty {
+                let mut table = new_table(row![br->"Offset", b->"Type", b->"Symbol", br->"Addend", b->"Target Section"]);
+                for group in fold_relocs(relocs, machine, self.opt.fold()) {
+                    let reloc = &group[0];
+                    if group.len() > 1 {
+                        let last = &group[group.len() - 1];
+                        table.add_row(Row::new(vec![
+                            Cell::new(&format!("{:#x}..{:#x}", reloc.r_offset, last.r_offset)).style_spec("r"),
+                            Cell::new(&format!("{} x {}", reloc::r_to_str(reloc.r_type, machine), group.len())).style_spec("b"),
+                            Cell::new(""),
+                            Cell::new(""),
+                            Cell::new(&target.map(str::to_owned).unwrap_or_else(|| folded_sections(reloc, last))),
+                        ]));
+                        continue;
+                    }
+                    let sym = syms.get(reloc.r_sym);
+                    let mut symbol = match sym {
+                        Some(sym) if sym.st_name == 0 => Cell::new(&unnamed_sym(sym, reloc.r_sym, xindex)).style_spec("i"),
+                        Some(sym) => string_cell(&self.opt, strtab_get(strtab, sym.st_name)),
+                        None => Cell::new(BAD_INDEX).style_spec("irFw"),
+                    };
+                    if let Some(sym) = sym.filter(|_| is_copy(reloc.r_type, machine)) {
+                        let from = match if dynamic { needed_by(reloc.r_sym) } else { None } {
+                            Some(needed) => format!(" from {}@{}", needed.file, needed.name),
+                            None => String::new(),
+                        };
+                        symbol = Cell::new(&format!("{} ({:#x}{})", symbol_name(&self.opt, strtab_get(strtab, sym.st_name)), sym.st_size, from)).style_spec("bFr");
+                    }
+                    if is_irelative(reloc.r_type, machine) {
+                        let target = irelative_target(self.elf, self.bytes, reloc);
+                        symbol = match resolver_name(self.elf, target) {
+                            Some(resolver) => Cell::new(&format!("-> {}", symbol_name(&self.opt, resolver))).style_spec("bFC"),
+                            None => Cell::new(&format!("-> {:#x}", target)).style_spec("bFC"),
+                        };
+                    }
+                    table.add_row(Row::new(vec![
+                        layout.addr_cell(reloc.r_offset as u64),
+                        Cell::new(reloc::r_to_str(reloc.r_type, machine)),
+                        symbol,
+                        if reloc.r_addend == 0 { Cell::new("") } else { Cell::new(&format!("+{:#x}", reloc.r_addend)).style_spec(&format!("F{}", theme::spec(colors.offset))) },
+                        Cell::new(target.or_else(|| section_at(reloc.r_offset as u64)).unwrap_or("<no section>")),
+                    ]));
+                }
+                if !relocs.is_empty() {
+                    fmt_table(fmt, &self.opt, &table)?;
+                }
+                writeln!(fmt)?;
+                return Ok(());
+            }
+            for group in fold_relocs(relocs, machine, self.opt.fold()) {
+                let reloc = &group[0];
+                if group.len() > 1 {
+                    // the addends differ, and a count says more than thousands of them
+                    let last = &group[group.len() - 1];
+                    write!(fmt, "{}..{} ", layout.addr(reloc.r_offset as u64), layout.addrx(last.r_offset as u64))?;
+                    write!(fmt, "{} x {}", reloc::r_to_str(reloc.r_type, machine).bold(), group.len())?;
+                    writeln!(fmt, " {}", target.map(str::to_owned).unwrap_or_else(|| folded_sections(reloc, last)).dimmed())?;
+                    continue;
+                }
+                let sym = syms.get(reloc.r_sym);
+                write!(fmt, "{} ", layout.addr(reloc.r_offset as u64))?;
+                let name = match sym {
+                    Some(sym) if sym.st_name == 0 => unnamed_sym(sym, reloc.r_sym, xindex).as_str().dimmed(),
+                    Some(sym) => string(&self.opt, strtab_get(strtab, sym.st_name)),
+                    None => BAD_INDEX.red(),
+                };
+                write!(fmt, "{} ",  reloc::r_to_str(reloc.r_type, machine))?;
+                if is_irelative(reloc.r_type, machine) {
+                    // no symbol, the addend is the resolver the dynamic linker calls to fill in the slot
+                    let target = irelative_target(self.elf, self.bytes, reloc);
+                    match resolver_name(self.elf, target) {
+                        Some(resolver) => write!(fmt, "-> {}", string(&self.opt, resolver).cyan())?,
+                        None => write!(fmt, "-> {}", layout.addrx(target))?,
+                    }
+                    writeln!(fmt, " {}", section_at(reloc.r_offset as u64).unwrap_or("<no section>").dimmed())?;
+                    continue;
+                }
+                let addend = if reloc.r_addend == 0 {
+                    "".normal()
+                } else {
+                    format!("+{}", layout.offs(reloc.r_addend)).normal()
+                };
+                write!(fmt, "{}{}", name, addend)?;
+                if is_copy(reloc.r_type, machine) {
+                    // the dynamic linker copies st_size bytes of the library's definition to r_offset
+                    write!(fmt, " -> {} ({})", section_at(reloc.r_offset as u64).unwrap_or("?"), layout.sz(sym.map_or(0, |sym| sym.st_size)))?;
+                    if let Some(needed) = if dynamic { needed_by(reloc.r_sym) } else { None } {
+                        write!(fmt, " from {}{}", string(&self.opt, needed.file).blue(), format!("@{}", needed.name).green())?;
+                    }
+                } else if dynamic {
+                    // i.e., whether this patches the GOT, data, init_array, etc.
+                    write!(fmt, " {}", section_at(reloc.r_offset as u64).unwrap_or("<no section>").dimmed())?;
+                }
+                writeln!(fmt)?;
+            }
+            writeln!(fmt, "")?;
+            Ok(())
+        };
+        let fmt_relocs_header = |fmt: &mut ::std::fmt::Formatter, name: &str, relocs: &[Reloc], total: usize| -> ::std::fmt::Result {
+            let name = if filtering { hdr(colors, &format!("{}({}/{})", name, relocs.len(), total)) } else { hdr_size(colors, name, total) };
+            let copies = relocs.iter().filter(|reloc| is_copy(reloc.r_type, machine)).count();
+            if copies == 0 {
+                writeln!(fmt, "{}:\n", name)
+            } else {
+                // copy relocations freeze the library's object layout into the executable, i.e., it wasn't built with -fPIC
+                writeln!(fmt, "{} {}:\n", name, format!("[{} COPY]", copies).red().bold())
+            }
+        };
+        let dynamic_relocs = |relocs: &[Reloc]| relocs.iter().filter(|reloc| keep_reloc(reloc, &self.elf.dynsyms, dyn_strtab)).cloned().collect::<Vec<_>>();
+
+        let dynrelas = dynamic_relocs(&self.elf.dynrelas);
+        if self.opt.shows(dynrelas.len()) {
+            fmt_relocs_header(fmt, "Dynamic Relas", &dynrelas, self.elf.dynrelas.len())?;
+            fmt_relocs(fmt, &dynrelas, &self.elf.dynsyms, dyn_strtab, &dyn_xindex, true, None)?;
+        }
+        let dynrels = dynamic_relocs(&self.elf.dynrels);
+        if self.opt.shows(dynrels.len()) {
+            fmt_relocs_header(fmt, "Dynamic Rel", &dynrels, self.elf.dynrels.len())?;
+            fmt_relocs(fmt, &dynrels, &self.elf.dynsyms, dyn_strtab, &dyn_xindex, true, None)?;
+        }
+        let pltrelocs = dynamic_relocs(&self.elf.pltrelocs);
+        if self.opt.shows(pltrelocs.len()) {
+            fmt_relocs_header(fmt, "Plt Relocations", &pltrelocs, self.elf.pltrelocs.len())?;
+            fmt_relocs(fmt, &pltrelocs, &self.elf.dynsyms, dyn_strtab, &dyn_xindex, true, None)?;
+        }
+
+        // ewwwwww, this ain't no ocaml fold
+        let num_shdr_relocs = self.elf.shdr_relocs.iter().fold(0, &|acc, &(_, ref v): &(usize, Vec<_>)| acc + v.len());
+        let shdr_relocs = self.elf.shdr_relocs.iter()
+            .map(|&(idx, ref relocs)| (idx, relocs.len(), relocs.iter().filter(|reloc| keep_reloc(reloc, &self.elf.syms, strtab)).cloned().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let shown = shdr_relocs.iter().fold(0, |acc, (_, _, v)| acc + v.len());
+        if !self.opt.shows(shown) {
+            // --skip-empty
+        } else if filtering {
+            writeln!(fmt, "{}:\n", hdr(colors, &format!("Shdr Relocations({}/{})", shown, num_shdr_relocs)))?;
+        } else {
+            fmt_header(fmt, colors, "Shdr Relocations", num_shdr_relocs)?;
+        }
+        if shown != 0 {
+            for &(idx, total, ref relocs) in &shdr_relocs {
+                if relocs.is_empty() {
+                    continue;
+                }
+                let name = match self.elf.section_headers.get(self.elf.section_headers[idx].sh_info as usize) {
+                    Some(target) => section_name(&self.opt, strtab_get(shdr_strtab, target.sh_name)),
+                    None => BAD_INDEX.into(),
+                };
+                if filtering {
+                    writeln!(fmt, "  {}({}/{})", name.bold(), relocs.len(), total)?;
+                } else {
+                    writeln!(fmt, "  {}({})", name.bold(), relocs.len())?;
+                }
+                fmt_relocs(fmt, relocs.as_slice(), &self.elf.syms, strtab, &xindex, false, Some(&name))?;
+            }
+        }
+
+        fmt_ifuncs(fmt, &self.opt, self.elf, self.bytes)?;
+
+        if let &Some(Dynamic { ref dyns, .. }) = &self.elf.dynamic {
+            fmt_header(fmt, colors, "Dynamic", dyns.len())?;
+            if self.opt.pretty {
+                let mut table = new_table(row![b->"Tag", br->"Value", b->"Interpretation"]);
+                for dyn in dyns {
+                    let val = dyn.d_val;
+                    let interpretation = match dyn_value(dyn.d_tag) {
+                        Some(DynValue::Str) => string_cell(&self.opt, dyn_strtab.get(val as usize).unwrap_or("")),
+                        Some(DynValue::Count) => Cell::new(&val.to_string()),
+                        Some(DynValue::Addr) => layout.addrx_cell(val),
+                        Some(DynValue::Size) => layout.sz_cell(val),
+                        Some(DynValue::Tag) => Cell::new(&tag_to_str(val)),
+                        Some(DynValue::Flags(flags)) => Cell::new(&dyn_flag_names(val, flags)),
+                        None => Cell::new(""),
+                    };
+                    table.add_row(Row::new(vec![
+                        Cell::new(&tag_to_str(dyn.d_tag)).style_spec("Fc"),
+                        Cell::new(&format!("{:#x}", val)),
+                        interpretation,
+                    ]));
+                }
+                fmt_table(fmt, &self.opt, &table)?;
+            } else {
+                for dyn in dyns {
+                    let tag = dyn.d_tag;
+                    let val = dyn.d_val;
+                    let tag_str = tag_to_str(tag).cyan();
+                    write!(fmt, "{:>16} ", tag_str)?;
+                    match dyn_value(tag) {
+                        Some(DynValue::Str) => writeln!(fmt, "{}", string(&self.opt, strtab_get(dyn_strtab, val as usize)))?,
+                        Some(DynValue::Count) => writeln!(fmt, "{}", val)?,
+                        Some(DynValue::Addr) => writeln!(fmt, "{}", layout.addrx(val))?,
+                        Some(DynValue::Size) => writeln!(fmt, "{}", layout.sz(val))?,
+                        Some(DynValue::Tag) => writeln!(fmt, "{}", tag_to_str(val))?,
+                        Some(DynValue::Flags(flags)) => writeln!(fmt, "{}", dyn_flags(val, flags))?,
+                        None => writeln!(fmt, "{:#x}", val)?,
+                    }
+                }
+            }
+            writeln!(fmt)?;
+        } else if self.opt.shows(0) {
+            writeln!(fmt, "{}: None", hdr(colors, "Dynamic"))?;
+            writeln!(fmt)?;
+        }
+
+        match sysv_hash(self.elf, self.bytes) {
+            Some(Ok(hash)) => {
+                writeln!(fmt, "{}: nbucket: {} nchain: {}", hdr(colors, "SysV Hash"), hash.nbucket, hash.nchain)?;
+                writeln!(fmt)?;
+                // nchain is the number of dynamic symbols, whichever way the linker sized .dynsym
+                let dynsyms = self.elf.section_headers.iter().find(|shdr| shdr.sh_type == section_header::SHT_DYNSYM && shdr.sh_entsize != 0)
+                    .map(|shdr| (shdr.sh_size / shdr.sh_entsize) as usize)
+                    .unwrap_or(self.elf.dynsyms.len());
+                if hash.nchain as usize != dynsyms {
+                    writeln!(fmt, "{}", format!("warning: nchain is {}, but there are {} dynamic symbols", hash.nchain, dynsyms).red())?;
+                }
+                let longest = hash.lengths.iter().cloned().max().unwrap_or(0);
+                for length in 0..longest + 1 {
+                    let buckets = hash.lengths.iter().filter(|&&l| l == length).count();
+                    if buckets == 0 {
+                        continue;
+                    }
+                    let percent = buckets as f64 * 100.0 / hash.lengths.len() as f64;
+                    writeln!(fmt, "{:>16} {} buckets ({:.1}%)", format!("length {}:", length), buckets, percent)?;
+                }
+                writeln!(fmt)?;
+            },
+            Some(Err(err)) => {
+                writeln!(fmt, "{}: {}", hdr(colors, "SysV Hash"), format!("{}", err).red())?;
+                writeln!(fmt)?;
+            },
+            None => (),
+        }
+
+        let imports = self.elf.dynsyms.iter().enumerate()
+            .filter(|&(_, sym)| sym.st_shndx == section_header::SHN_UNDEF as usize && sym.st_bind() != sym::STB_LOCAL && sym.st_name != 0)
+            .collect::<Vec<_>>();
+        if self.opt.shows(imports.len()) {
+            fmt_header(fmt, colors, "Imports", imports.len())?;
+            for (i, sym) in imports {
+                write!(fmt, "{} ", layout.addr(sym.st_value))?;
+                let name = dyn_strtab.get(sym.st_name).unwrap_or("");
+                write!(fmt, "{}", string(&self.opt, name))?;
+                let needed = needed_by(i);
+                if let Some(needed) = needed {
+                    write!(fmt, "{}", format!("@{}", needed.name).green())?;
+                }
+                if sym.st_bind() == sym::STB_WEAK {
+                    write!(fmt, " {}", "(weak)".magenta())?;
+                }
+                if let Some(needed) = needed {
+                    write!(fmt, " -> {}", string(&self.opt, needed.file).blue())?;
+                }
+                if let Some(ref providers) = self.import_providers {
+                    match providers.get(name) {
+                        Some(library) => write!(fmt, " {} {}", "defined in".dimmed(), string(&self.opt, library).blue())?,
+                        // a weak reference that nothing defines is just null
+                        None if sym.st_bind() == sym::STB_WEAK => write!(fmt, " {}", "unresolved, but weak (benign)".dimmed())?,
+                        None => write!(fmt, " {}", "UNRESOLVED".red().bold())?,
+                    }
+                }
+                writeln!(fmt)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if self.opt.shows(self.elf.libraries.len()) {
+            fmt_header(fmt, colors, "Libraries", self.elf.libraries.len())?;
+            for (lib, lookup) in self.elf.libraries.iter().zip(&self.libraries) {
+                write!(fmt, "{:>16} ", string(&self.opt, lib).blue())?;
+                match *lookup {
+                    LibraryLookup::Direct(ref path) => writeln!(fmt, "-> {}", path.display())?,
+                    LibraryLookup::Path(tag, ref rpath, ref path) => writeln!(fmt, "-> {} {}", path.display(), format!("({} {})", tag, rpath).dimmed())?,
+                    LibraryLookup::Default(ref path) => writeln!(fmt, "-> {} {}", path.display(), "(default search path)".dimmed())?,
+                    LibraryLookup::NotFound => writeln!(fmt, "{}", "NOT FOUND".red().bold())?,
+                }
+            }
+            writeln!(fmt)?;
+        }
+
+        let tls_phdr = phdrs.iter().enumerate().find(|&(_, phdr)| phdr.p_type == program_header::PT_TLS);
+        let tls_shdrs = self.elf.section_headers.iter().enumerate().filter(|&(_, shdr)| shdr.sh_flags as u32 & section_header::SHF_TLS != 0).collect::<Vec<_>>();
+        if tls_phdr.is_some() || !tls_shdrs.is_empty() {
+            fmt_header(fmt, colors, "TLS", tls_shdrs.len())?;
+            if let Some((i, phdr)) = tls_phdr {
+                write!(fmt, "{} {:<width$} ", layout.idx(i), ph_name(phdr), width = ph_width)?;
+                write!(fmt, "template: {} ", layout.addrx(phdr.p_vaddr))?;
+                write!(fmt, "filesz: {} ", layout.sz(phdr.p_filesz))?;
+                write!(fmt, "memsz: {} ", layout.sz(phdr.p_memsz).bold())?;
+                writeln!(fmt, "align: {:#x}", phdr.p_align)?;
+            }
+            for (i, shdr) in tls_shdrs {
+                write!(fmt, "{} {:<width$} ", layout.idx(i), strtab_get(shdr_strtab, shdr.sh_name), width = sh_width)?;
+                write!(fmt, "{} ", section_header::sht_to_str(shdr.sh_type))?;
+                write!(fmt, "addr: {} ", layout.addrx(shdr.sh_addr))?;
+                write!(fmt, "size: {} ", layout.sz(shdr.sh_size))?;
+                writeln!(fmt, "align: {:#x}", shdr.sh_addralign)?;
+            }
+            // like nm, fall back to the dynamic symbols when the binary is stripped
+            let (syms, strtab) = if !self.elf.syms.is_empty() { (&self.elf.syms, strtab) } else { (&self.elf.dynsyms, dyn_strtab) };
+            let mut tls_syms = syms.iter().filter(|sym| sym.st_type() == sym::STT_TLS && sym.st_shndx != section_header::SHN_UNDEF as usize).collect::<Vec<_>>();
+            tls_syms.sort_by_key(|sym| (sym.st_shndx, sym.st_value));
+            if !tls_syms.is_empty() {
+                writeln!(fmt)?;
+                for sym in tls_syms {
+                    // the value of a TLS symbol is its offset in the TLS block, or in its section for relocatable objects
+                    match tls_phdr {
+                        Some((_, phdr)) if header.e_type != header::ET_REL => write!(fmt, "{} ", layout.addr(phdr.p_vaddr.wrapping_add(sym.st_value)))?,
+                        _ => write!(fmt, "{:>1$} ", "", if self.elf.is_64 { 16 } else { 8 })?,
+                    }
+                    if header.e_type == header::ET_REL {
+                        let section = self.elf.section_headers.get(sym.st_shndx).map(|shdr| strtab_get(shdr_strtab, shdr.sh_name)).unwrap_or(BAD_INDEX);
+                        write!(fmt, "{}+{} ", section, layout.off(sym.st_value))?;
+                    } else {
+                        write!(fmt, "tls offset: {} ", layout.off(sym.st_value))?;
+                    }
+                    write!(fmt, "size: {} ", layout.sz(sym.st_size))?;
+                    writeln!(fmt, "{}", string(&self.opt, strtab_get(strtab, sym.st_name)))?;
+                }
+            }
+            writeln!(fmt)?;
+        }
+
+        let properties = self.properties();
+        if !properties.is_empty() {
+            fmt_header(fmt, colors, "Properties", properties.len())?;
+            let machine = self.elf.header.e_machine;
+            for property in &properties {
+                write!(fmt, "{:>24} ", note::property_to_str(property.pr_type, machine).bold())?;
+                let bits = note::property_bits(property.pr_type, machine);
+                let is_and = property.pr_type == note::GNU_PROPERTY_X86_FEATURE_1_AND || property.pr_type == note::GNU_PROPERTY_AARCH64_FEATURE_1_AND;
+                match property.value {
+                    Some(mask) if !bits.is_empty() => {
+                        for &(bit, name) in bits {
+                            // the AND features are the hardening markers, so say explicitly when they're missing
+                            if mask as u32 & bit != 0 {
+                                write!(fmt, "{}{} ", name, if is_and { ": enabled".green() } else { "".normal() })?;
+                            } else if is_and {
+                                write!(fmt, "{}{} ", name, ": disabled".red())?;
+                            }
+                        }
+                    },
+                    Some(size) if property.pr_type == note::GNU_PROPERTY_STACK_SIZE => write!(fmt, "{}", layout.sz(size))?,
+                    _ => {
+                        for byte in property.data {
+                            write!(fmt, "{:02x}", byte)?;
+                        }
+                    },
+                }
+                writeln!(fmt)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        if let Some(shdr) = named_section(self.elf, ".eh_frame") {
+            let ctx = |shdr: &elf::SectionHeader| eh_frame::Ctx { address: shdr.sh_addr, is_64: self.elf.is_64, le: scroll::Endian::from(self.elf.little_endian) };
+            match section_bytes(self.bytes, shdr).and_then(|data| eh_frame::parse(data, ctx(shdr))) {
+                Ok(frame) => {
+                    write!(fmt, "{}: cies: {} fdes: {}", hdr(colors, "EH Frame"), frame.cies, frame.fdes.len())?;
+                    // the FDEs of relocatable objects point nowhere until they're relocated
+                    if let Some((start, end)) = frame.pc_range().filter(|_| header.e_type != header::ET_REL) {
+                        write!(fmt, " pc range: {}-{}", layout.addrx(start), layout.addrx(end))?;
+                    }
+                    writeln!(fmt)?;
+                    if let Some(hdr_shdr) = named_section(self.elf, ".eh_frame_hdr") {
+                        match section_bytes(self.bytes, hdr_shdr).and_then(|data| eh_frame::parse_hdr(data, ctx(hdr_shdr))) {
+                            Ok(eh_hdr) => {
+                                write!(fmt, "{:>16} version: {} ", ".eh_frame_hdr", eh_hdr.version)?;
+                                if let Some(ptr) = eh_hdr.eh_frame_ptr {
+                                    write!(fmt, "eh_frame_ptr: {} ({}) ", layout.addrx(ptr), eh_frame::encoding_to_str(eh_hdr.eh_frame_ptr_enc))?;
+                                }
+                                if let Some(count) = eh_hdr.fde_count {
+                                    write!(fmt, "fde_count: {} ({}) ", count, eh_frame::encoding_to_str(eh_hdr.fde_count_enc))?;
+                                }
+                                if eh_hdr.has_table() {
+                                    writeln!(fmt, "search table: {}", eh_frame::encoding_to_str(eh_hdr.table_enc))?;
+                                } else {
+                                    writeln!(fmt, "{}", "no search table".dimmed())?;
+                                }
+                                match eh_hdr.fde_count {
+                                    Some(count) if count != frame.fdes.len() as u64 => writeln!(fmt, "{}", format!("warning: .eh_frame_hdr counts {} FDEs, but .eh_frame has {}", count, frame.fdes.len()).red())?,
+                                    _ => (),
+                                }
+                            },
+                            Err(err) => writeln!(fmt, "{:>16} {}", ".eh_frame_hdr", format!("{}", err).red())?,
+                        }
+                    }
+                    if self.opt.eh_frame {
+                        writeln!(fmt)?;
+                        fmt_header(fmt, colors, "FDEs", frame.fdes.len())?;
+                        let mut functions = self.elf.syms.iter().map(|sym| (sym, strtab)).chain(self.elf.dynsyms.iter().map(|sym| (sym, dyn_strtab)))
+                            .filter(|&(sym, _)| sym.st_type() == sym::STT_FUNC && sym.st_value != 0)
+                            .map(|(sym, strtab)| (sym.st_value, sym.st_size, strtab_get(strtab, sym.st_name)))
+                            .collect::<Vec<_>>();
+                        functions.sort();
+                        functions.dedup_by_key(|&mut (value, _, _)| value);
+                        let mut fdes = frame.fdes.iter().collect::<Vec<_>>();
+                        fdes.sort_by_key(|fde| fde.start);
+                        for fde in fdes {
+                            write!(fmt, "{} {} ", layout.addr(fde.start), layout.sz(fde.length))?;
+                            // the last function starting at or before the FDE, if the FDE is inside it
+                            let after = functions.partition_point(|&(value, _, _)| value <= fde.start);
+                            match after.checked_sub(1).map(|nearest| functions[nearest]) {
+                                Some((value, size, name)) if fde.start - value < ::std::cmp::max(size, 1) => {
+                                    write!(fmt, "{}", string(&self.opt, name))?;
+                                    if fde.start != value {
+                                        write!(fmt, "+{:#x}", fde.start - value)?;
+                                    }
+                                    writeln!(fmt)?;
+                                },
+                                _ => writeln!(fmt, "{}", "<unknown>".dimmed())?,
+                            }
+                        }
+                    }
+                },
+                Err(err) => writeln!(fmt, "{}: {}", hdr(colors, "EH Frame"), format!("{}", err).red())?,
+            }
+            writeln!(fmt)?;
+        }
+
+        writeln!(fmt, "Soname: {:?}", self.elf.soname)?;
+        writeln!(fmt, "is_64: {}", self.elf.is_64)?;
+        writeln!(fmt, "is_lib: {}", self.elf.is_lib)?;
+        writeln!(fmt, "little_endian: {}", self.elf.little_endian)?;
+        if self.elf.section_headers.iter().any(|shdr| shdr.sh_type == section_header::SHT_SYMTAB) {
+            writeln!(fmt, "stripped: {}", "no".green())?;
+        } else if debuglink(self.elf, self.bytes).is_some() {
+            // the symbols are in the separate debug file
+            writeln!(fmt, "stripped: {} {}", "yes".red(), "(see debuglink)".dimmed())?;
+        } else {
+            writeln!(fmt, "stripped: {}", "yes".red())?;
+        }
+        writeln!(fmt, "bias: {:#x}", self.elf.bias)?;
+        writeln!(fmt, "entry: {}", layout.bare_addr(self.elf.entry))?;
+        if let Some(id) = build_id(self.elf, self.bytes) {
+            write!(fmt, "build-id: {}", hex(id))?;
+            if let Some(ref path) = self.debug_info {
+                write!(fmt, " -> {}", path.display())?;
+            }
+            writeln!(fmt)?;
+        }
+        if let Some(link) = debuglink(self.elf, self.bytes) {
+            write!(fmt, "debuglink: {} crc: {:#010x}", string(&self.opt, link.name), link.crc)?;
+            match self.debug_lookup {
+                Some(DebugLookup::Found(ref path)) => write!(fmt, " -> {}", path.display())?,
+                Some(DebugLookup::Mismatch(ref path, crc)) => write!(fmt, " {}", format!("(crc mismatch: {} has {:#010x})", path.display(), crc).red())?,
+                Some(DebugLookup::Missing(ref searched)) => {
+                    let searched = searched.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+                    write!(fmt, " {}", format!("(not found in {})", searched.join(", ")).red())?
+                },
+                None => (),
+            }
+            writeln!(fmt)?;
+        }
+        for producer in producers(self.elf, self.bytes) {
+            writeln!(fmt, "comment: {}", producer)?;
+        }
+
+        match self.opt.search {
+            Some(ref search) => {
+                let matches = find_all(self.bytes, search);
+
+                writeln!(fmt)?;
+                writeln!(fmt, "Matches for {:?}:", search)?;
+                for offset in matches {
+                    let base = self.opt.offset.map(|Number(base)| base).unwrap_or(0);
+                    writeln!(fmt, "  {} {:?}", layout.hex(offset as u64 + base, true), match_string(self.bytes, offset))?;
+                    self.fmt_containers(fmt, offset as u64)?;
+                }
+            },
+            None => ()
+        }
+
+        if let Some(Number(at)) = self.opt.at {
+            let base = self.opt.offset.map(|Number(base)| base).unwrap_or(0);
+            writeln!(fmt)?;
+            writeln!(fmt, "{}:\n", hdr(colors, &format!("At {:#x}", at)))?;
+            match at.checked_sub(base) {
+                Some(offset) if offset < self.bytes.len() as u64 => {
+                    let (section, vaddr) = self.fmt_containers(fmt, offset)?;
+                    // relocatable objects have no addresses, their symbols and relocations are section relative
+                    let position = if header.e_type == header::ET_REL {
+                        section.map(|i| offset - self.elf.section_headers[i].sh_offset)
+                    } else {
+                        vaddr
+                    };
+                    // a symbol in another section is a neighbor, not what lives here
+                    let in_section = |shndx: usize| section.map_or(header.e_type != header::ET_REL, |section| shndx == section);
+                    if let Some(position) = position {
+                        let symbols = self.elf.syms.iter().enumerate().map(|(i, sym)| (sym, strtab, real_shndx(sym, i, &xindex)))
+                            .chain(self.elf.dynsyms.iter().enumerate().map(|(i, sym)| (sym, dyn_strtab, real_shndx(sym, i, &dyn_xindex))));
+                        let nearest = symbols
+                            .filter(|&(sym, strtab, shndx)| sym.st_shndx != section_header::SHN_UNDEF as usize && sym.st_type() != sym::STT_SECTION
+                                    && sym.st_type() != sym::STT_FILE && strtab.get(sym.st_name).map(|name| !name.is_empty()).unwrap_or(false)
+                                    && sym.st_value <= position && in_section(shndx))
+                            .max_by_key(|&(sym, _, _)| (sym.st_value, sym.st_size > position - sym.st_value));
+                        if let Some((sym, strtab, _)) = nearest {
+                            let delta = position - sym.st_value;
+                            write!(fmt, "  ├──{}", string(&self.opt, strtab_get(strtab, sym.st_name)))?;
+                            if delta != 0 {
+                                write!(fmt, "+{:#x}", delta)?;
+                            }
+                            write!(fmt, " {} st_size: {}", sym::type_to_str(sym.st_type()), layout.sz(sym.st_size))?;
+                            if delta >= sym.st_size && delta != 0 {
+                                write!(fmt, " {}", "(past its end)".dimmed())?;
+                            }
+                            writeln!(fmt)?;
+                        }
+                        // the relocations that patch a word covering this byte
+                        let word = if self.elf.is_64 { 8 } else { 4 };
+                        let covers = |reloc: &&Reloc| (reloc.r_offset as u64) <= position && position - (reloc.r_offset as u64) < word;
+                        let mut tables: Vec<(&str, &[Reloc], &Syms, &Strtab)> = Vec::new();
+                        if header.e_type == header::ET_REL {
+                            for &(idx, ref relocs) in &self.elf.shdr_relocs {
+                                let shdr = &self.elf.section_headers[idx];
+                                if Some(shdr.sh_info as usize) == section {
+                                    tables.push((strtab_get(shdr_strtab, shdr.sh_name), relocs, &self.elf.syms, strtab));
+                                }
+                            }
+                        } else {
+                            tables.push(("Dynamic Relas", &self.elf.dynrelas, &self.elf.dynsyms, dyn_strtab));
+                            tables.push(("Dynamic Rel", &self.elf.dynrels, &self.elf.dynsyms, dyn_strtab));
+                            tables.push(("Plt Relocations", &self.elf.pltrelocs, &self.elf.dynsyms, dyn_strtab));
+                        }
+                        for &(relocations, relocs, syms, strtab) in &tables {
+                            for reloc in relocs.iter().filter(&covers) {
+                                write!(fmt, "  ├──{} {} {} ", relocations.dimmed(), layout.bare_addr(reloc.r_offset as u64), reloc::r_to_str(reloc.r_type, machine))?;
+                                match syms.get(reloc.r_sym) {
+                                    Some(sym) if sym.st_name != 0 => write!(fmt, "{}", string(&self.opt, strtab.get(sym.st_name).unwrap_or("")))?,
+                                    _ => write!(fmt, "{}", "ABS".dimmed())?,
+                                }
+                                if reloc.r_addend != 0 {
+                                    write!(fmt, "+{}", layout.offs(reloc.r_addend))?;
+                                }
+                                writeln!(fmt)?;
+                            }
+                        }
+                    }
+                },
+                _ => writeln!(fmt, "  {}", "past the end of the binary".red())?,
+            }
+        }
+
+        if self.opt.xrefs.is_some() || self.opt.xrefs_addr.is_some() {
+            // every relocation table, with the symbols it indexes, and the section it applies to if it is a section's
+            let mut tables = vec![
+                ("Dynamic Relas", &self.elf.dynrelas[..], &self.elf.dynsyms, dyn_strtab, &dyn_xindex[..], None),
+                ("Dynamic Rel", &self.elf.dynrels[..], &self.elf.dynsyms, dyn_strtab, &dyn_xindex[..], None),
+                ("Plt Relocations", &self.elf.pltrelocs[..], &self.elf.dynsyms, dyn_strtab, &dyn_xindex[..], None),
+            ];
+            for &(idx, ref relocs) in &self.elf.shdr_relocs {
+                let shdr = &self.elf.section_headers[idx];
+                let target = self.elf.section_headers.get(shdr.sh_info as usize).map_or(BAD_INDEX, |target| strtab_get(shdr_strtab, target.sh_name));
+                tables.push((strtab_get(shdr_strtab, shdr.sh_name), relocs, &self.elf.syms, strtab, &xindex, Some(target)));
+            }
+            let mut table = new_table(row![br->"Offset", b->"Relocations", b->"Type", b->"Symbol", br->"Addend", b->"Section"]);
+            let mut count = 0;
+            for &(relocations, relocs, syms, strtab, xindex, target) in &tables {
+                for reloc in relocs {
+                    let sym = match syms.get(reloc.r_sym) {
+                        Some(sym) => sym,
+                        None => continue,
+                    };
+                    let name = strtab.get(sym.st_name).unwrap_or("");
+                    let by_name = match self.opt.xrefs {
+                        Some(ref pattern) => !name.is_empty() && (glob_match(pattern, name) || glob_match(pattern, &symbol_name(&self.opt, name))),
+                        None => false,
+                    };
+                    // S + A; an undefined symbol's address isn't known until it is bound
+                    let by_addr = match self.opt.xrefs_addr {
+                        Some(Number(address)) => (reloc.r_sym == 0 || sym.st_shndx != section_header::SHN_UNDEF as usize)
+                            && sym.st_value.wrapping_add(reloc.r_addend as u64) == address,
+                        None => false,
+                    };
+                    if !by_name && !by_addr {
+                        continue;
+                    }
+                    count += 1;
+                    let symbol = if sym.st_name == 0 {
+                        Cell::new(&unnamed_sym(sym, reloc.r_sym, xindex)).style_spec("i")
+                    } else {
+                        string_cell(&self.opt, name)
+                    };
+                    table.add_row(Row::new(vec![
+                        layout.addr_cell(reloc.r_offset as u64),
+                        Cell::new(relocations),
+                        Cell::new(reloc::r_to_str(reloc.r_type, machine)),
+                        symbol,
+                        if reloc.r_addend == 0 { Cell::new("") } else { Cell::new(&format!("+{:#x}", reloc.r_addend)).style_spec(&format!("F{}", theme::spec(colors.offset))) },
+                        Cell::new(target.or_else(|| section_at(reloc.r_offset as u64)).unwrap_or("")),
+                    ]));
+                }
+            }
+            let wanted = match (&self.opt.xrefs, self.opt.xrefs_addr) {
+                (Some(pattern), Some(Number(address))) => format!("{} or {:#x}", pattern, address),
+                (Some(pattern), None) => pattern.clone(),
+                (&None, Some(Number(address))) => format!("{:#x}", address),
+                (&None, None) => unreachable!(),
+            };
+            writeln!(fmt)?;
+            writeln!(fmt, "{}:\n", hdr(colors, &format!("Xrefs to {}({})", wanted, count)))?;
+            if count != 0 {
+                fmt_table(fmt, &self.opt, &table)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Pure range checks over the already parsed ELF headers, flagging layouts a sane linker wouldn't produce
+struct Anomalies<'a> {
+    elf: &'a elf::Elf<'a>,
+    size: usize,
+    opt: &'a Opt,
+}
+
+impl<'a> Anomalies<'a> {
+    fn findings(&self) -> Vec<String> {
+        use elf::program_header;
+        use elf::section_header;
+        let elf = &self.elf;
+        let size = self.size as u64;
+        let shdr_strtab = &elf.shdr_strtab;
+        let shdr_name = |i: usize| -> String {
+            let name = shdr_strtab.get(elf.section_headers[i].sh_name).unwrap_or("");
+            format!("{}({})", name, i)
+        };
+        let mut findings = Vec::new();
+
+        let phdrs = &elf.program_headers;
+        for (i, phdr) in phdrs.iter().enumerate() {
+            let name = pt_to_str(phdr.p_type);
+            if phdr.p_offset.saturating_add(phdr.p_filesz) > size {
+                findings.push(format!("segment {}({}) file range [{:#x}, {:#x}) extends past end of file ({:#x})",
+                                      name, i, phdr.p_offset, phdr.p_offset.saturating_add(phdr.p_filesz), size));
+            }
+            if phdr.p_type == program_header::PT_LOAD && phdr.p_memsz == 0 {
+                findings.push(format!("segment {}({}) is a zero-size PT_LOAD", name, i));
+            }
+            let wx = program_header::PF_W | program_header::PF_X;
+            if phdr.p_flags & wx == wx {
+                findings.push(format!("segment {}({}) is both writable and executable", name, i));
+            }
+        }
+
+        let loads = phdrs.iter().enumerate().filter(|&(_, phdr)| phdr.p_type == program_header::PT_LOAD).collect::<Vec<_>>();
+        if elf.entry != 0 && !loads.is_empty() {
+            let executable = loads.iter().any(|&(_, phdr)| {
+                phdr.p_flags & program_header::PF_X != 0 && elf.entry >= phdr.p_vaddr && elf.entry < phdr.p_vaddr.saturating_add(phdr.p_memsz)
+            });
+            if !executable {
+                findings.push(format!("entry point {:#x} is not in any executable segment", elf.entry));
+            }
+        }
+
+        let shdrs = &elf.section_headers;
+        let in_file = |shdr: &elf::SectionHeader| shdr.sh_type != section_header::SHT_NOBITS && shdr.sh_type != section_header::SHT_NULL && shdr.sh_size != 0;
+        for (i, a) in shdrs.iter().enumerate().filter(|&(_, shdr)| in_file(shdr)) {
+            let a_end = a.sh_offset.saturating_add(a.sh_size);
+            for (j, b) in shdrs.iter().enumerate().skip(i + 1).filter(|&(_, shdr)| in_file(shdr)) {
+                let b_end = b.sh_offset.saturating_add(b.sh_size);
+                if a.sh_offset < b_end && b.sh_offset < a_end {
+                    findings.push(format!("sections {} and {} overlap in file range [{:#x}, {:#x})",
+                                          shdr_name(i), shdr_name(j), ::std::cmp::max(a.sh_offset, b.sh_offset), ::std::cmp::min(a_end, b_end)));
+                }
+            }
+        }
+
+        for (i, shdr) in shdrs.iter().enumerate() {
+            // .tbss is laid out in the TLS template, not in the segment's address range
+            let is_tbss = shdr.sh_type == section_header::SHT_NOBITS && shdr.sh_flags as u32 & section_header::SHF_TLS != 0;
+            if !shdr.is_alloc() || shdr.sh_addr == 0 || shdr.sh_size == 0 || is_tbss || loads.is_empty() {
+                continue;
+            }
+            let end = shdr.sh_addr.saturating_add(shdr.sh_size);
+            let container = loads.iter().find(|&&(_, phdr)| shdr.sh_addr >= phdr.p_vaddr && shdr.sh_addr < phdr.p_vaddr.saturating_add(phdr.p_memsz));
+            match container {
+                Some(&(j, phdr)) => {
+                    if end > phdr.p_vaddr.saturating_add(phdr.p_memsz) {
+                        findings.push(format!("section {} [{:#x}, {:#x}) extends past its segment PT_LOAD({})",
+                                              shdr_name(i), shdr.sh_addr, end, j));
+                    }
+                },
+                None => {
+                    findings.push(format!("section {} address {:#x} is outside of every PT_LOAD segment", shdr_name(i), shdr.sh_addr));
+                }
+            }
+        }
+        findings
+    }
+}
+
+impl<'a> ::std::fmt::Display for Anomalies<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.opt.theme;
+        let findings = self.findings();
+        fmt_header(fmt, colors, "Anomalies", findings.len())?;
+        for finding in findings {
+            writeln!(fmt, "{} {}", "warning:".red().bold(), finding)?;
+        }
+        Ok(())
+    }
+}
+
+/// The byte ranges of a file of `size` bytes that none of the `covered` ranges account for
+fn uncovered (mut covered: Vec<(u64, u64)>, size: u64) -> Vec<(u64, u64)> {
+    covered.sort();
+    let mut gaps = Vec::new();
+    let mut end = 0;
+    for (start, stop) in covered {
+        let start = ::std::cmp::min(start, size);
+        if start > end {
+            gaps.push((end, start));
+        }
+        end = ::std::cmp::max(end, stop);
+    }
+    if end < size {
+        gaps.push((end, size));
+    }
+    gaps
+}
+
+/// The byte ranges of a file outside of every section, and, for ELF, outside of every PT_LOAD segment
+struct Gaps<'a> {
+    bytes: &'a [u8],
+    /// The gaps between sections, the headers counting as covered
+    sections: Vec<(u64, u64)>,
+    /// The gaps between PT_LOAD segments
+    segments: Option<Vec<(u64, u64)>>,
+    /// The file ranges of the named sections, to list those that fall in the segment gaps
+    names: Vec<(u64, u64, &'a str)>,
+    layout: Layout,
+}
+
+impl<'a> Gaps<'a> {
+    fn elf(elf: &'a elf::Elf, bytes: &'a [u8], opt: &Opt) -> Self {
+        use elf::program_header::PT_LOAD;
+        use elf::section_header::{SHT_NOBITS, SHT_NULL};
+        let header = &elf.header;
+        let size = bytes.len() as u64;
+        let headers = vec![
+            (0, header.e_ehsize as u64),
+            (header.e_phoff, header.e_phoff.saturating_add(header.e_phnum as u64 * header.e_phentsize as u64)),
+            (header.e_shoff, header.e_shoff.saturating_add(header.e_shnum as u64 * header.e_shentsize as u64)),
+        ];
+        let in_file = elf.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_type != SHT_NULL && shdr.sh_size != 0);
+        let mut sections = headers.clone();
+        sections.extend(in_file.clone().map(|shdr| (shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size))));
+        let loads = elf.program_headers.iter().filter(|phdr| phdr.p_type == PT_LOAD)
+            .map(|phdr| (phdr.p_offset, phdr.p_offset.saturating_add(phdr.p_filesz)))
+            .collect::<Vec<_>>();
+        let names = in_file.map(|shdr| (shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size), elf.shdr_strtab.get(shdr.sh_name).unwrap_or("")))
+            .collect::<Vec<_>>();
+        Gaps {
+            bytes,
+            sections: uncovered(sections, size),
+            segments: if loads.is_empty() { None } else { Some(uncovered(loads, size)) },
+            names,
+            layout: Layout::elf(opt, elf),
+        }
+    }
+
+    fn pe(pe: &pe::PE, bytes: &'a [u8], opt: &Opt) -> Self {
+        let mut sections = pe.sections.iter()
+            .map(|section| (section.pointer_to_raw_data as u64, section.pointer_to_raw_data as u64 + section.size_of_raw_data as u64))
+            .collect::<Vec<_>>();
+        if let Some(ref optional_header) = pe.header.optional_header {
+            sections.push((0, optional_header.windows_fields.size_of_headers as u64));
+        }
+        Gaps { bytes, sections: uncovered(sections, bytes.len() as u64), segments: None, names: Vec::new(), layout: Layout::new(opt, pe.is_64) }
+    }
+
+    fn nonzero(&self, start: u64, end: u64) -> usize {
+        self.bytes[start as usize..end as usize].iter().filter(|&&b| b != 0).count()
+    }
+
+    fn fmt_gaps(&self, fmt: &mut ::std::fmt::Formatter, name: &str, gaps: &[(u64, u64)], names: &[(u64, u64, &str)]) -> ::std::fmt::Result {
+        let layout = self.layout;
+        let colors = &layout.colors;
+        let size = gaps.iter().fold(0, |size, &(start, end)| size + end - start);
+        writeln!(fmt, "{} {}:\n", hdr_size(colors, name, gaps.len()), format!("{:#x} bytes", size).dimmed())?;
+        for &(start, end) in gaps {
+            write!(fmt, "{:>16}-{:<16} size: {:<8} ", layout.off(start), layout.off(end), layout.sz(end - start))?;
+            // bytes that no section accounts for, that aren't padding, are where data gets hidden; the
+            // sections outside of the segments are just not loaded
+            let nonzero = self.nonzero(start, end);
+            let hidden = self.sections.iter()
+                .map(|&(from, to)| (::std::cmp::max(from, start), ::std::cmp::min(to, end)))
+                .filter(|&(from, to)| from < to)
+                .fold(0, |hidden, (from, to)| hidden + self.nonzero(from, to));
+            if nonzero == 0 {
+                write!(fmt, "{}", "zeros".dimmed())?;
+            } else if hidden == nonzero {
+                write!(fmt, "{}", format!("{} nonzero bytes", nonzero).red().bold())?;
+            } else if hidden != 0 {
+                write!(fmt, "{} nonzero bytes, {}", nonzero, format!("{} outside of any section", hidden).red().bold())?;
+            } else {
+                write!(fmt, "{} nonzero bytes", nonzero)?;
+            }
+            let inside = names.iter().filter(|&&(from, to, _)| from < end && start < to).map(|&(_, _, name)| name).collect::<Vec<_>>();
+            if !inside.is_empty() {
+                write!(fmt, " {}", inside.join(" "))?;
+            }
+            writeln!(fmt)?;
+        }
+        writeln!(fmt)
+    }
+}
+
+impl<'a> ::std::fmt::Display for Gaps<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.fmt_gaps(fmt, "Section Gaps", &self.sections, &[])?;
+        if let Some(ref gaps) = self.segments {
+            self.fmt_gaps(fmt, "Segment Gaps", gaps, &self.names)?;
+        }
+        Ok(())
+    }
+}
+
+/// How many of the largest gaps `--alignment-waste` highlights
+const WORST_GAPS: usize = 3;
+
+/// The padding between consecutive sections in the file
+struct AlignmentWaste<'a> {
+    /// The sections on either side of each gap, the end of the first, and the start and alignment of the second
+    gaps: Vec<(&'a str, &'a str, u64, u64, u64)>,
+    size: u64,
+    opt: &'a Opt,
+    layout: Layout,
+}
+
+impl<'a> AlignmentWaste<'a> {
+    fn elf(elf: &'a elf::Elf, size: u64, opt: &'a Opt) -> Self {
+        use elf::section_header::{SHT_NOBITS, SHT_NULL};
+        let mut sections = elf.section_headers.iter()
+            .filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_type != SHT_NULL && shdr.sh_size != 0)
+            .map(|shdr| (shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size), shdr.sh_addralign, strtab_get(&elf.shdr_strtab, shdr.sh_name)))
+            .collect::<Vec<_>>();
+        sections.sort();
+        // overlapping sections leave no gap
+        let gaps = sections.windows(2)
+            .filter(|pair| pair[0].1 < pair[1].0)
+            .map(|pair| (pair[0].3, pair[1].3, pair[0].1, pair[1].0, pair[1].2))
+            .collect();
+        AlignmentWaste { gaps, size, opt, layout: Layout::elf(opt, elf) }
+    }
+}
+
+impl<'a> ::std::fmt::Display for AlignmentWaste<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.opt.theme;
+        let layout = self.layout;
+        let mut sizes = self.gaps.iter().map(|&(_, _, end, start, _)| start - end).collect::<Vec<_>>();
+        sizes.sort_by(|a, b| b.cmp(a));
+        // ties with the smallest of the worst are highlighted too
+        let worst = sizes.get(WORST_GAPS - 1).or(sizes.last()).cloned().unwrap_or(0);
+        fmt_header(fmt, colors, "Alignment Waste", self.gaps.len())?;
+        for &(before, after, end, start, align) in &self.gaps {
+            let size = start - end;
+            write!(fmt, "{:>16} -> {:<16} ", section_name(self.opt, before), section_name(self.opt, after))?;
+            write!(fmt, "{:>16}-{:<16} align: {:<6} ", layout.off(end), layout.off(start), format!("{:#x}", align))?;
+            if size >= worst {
+                write!(fmt, "size: {}", format!("{:#x}", size).red().bold())?;
+            } else {
+                write!(fmt, "size: {}", layout.sz(size))?;
+            }
+            // aligning the section needs less than its alignment, so the rest lines up a segment, e.g. to a page
+            if size >= ::std::cmp::max(align, 1) {
+                write!(fmt, " {}", "(more than its alignment)".dimmed())?;
+            }
+            writeln!(fmt)?;
+        }
+        let total = sizes.iter().sum::<u64>();
+        let percent = if self.size == 0 { 0.0 } else { total as f64 * 100.0 / self.size as f64 };
+        writeln!(fmt)?;
+        writeln!(fmt, "total: {} ({:.2}% of {:#x} bytes)", layout.sz(total), percent, self.size)
+    }
+}
+
+/// The DWARF sections of an ELF, and the units in them
+struct Dwarf<'a> {
+    /// The name, size, and uncompressed size if SHF_COMPRESSED, of each DWARF section
+    sections: Vec<(&'a str, u64, Option<u64>)>,
+    /// The units of `.debug_info` and `.debug_types`, if there is a `.debug_info`
+    units: Option<error::Result<dwarf::Units>>,
+    opt: &'a Opt,
+    layout: Layout,
+}
+
+impl<'a> Dwarf<'a> {
+    fn elf(elf: &'a elf::Elf, bytes: &[u8], opt: &'a Opt) -> Self {
+        let sections = elf.section_headers.iter()
+            .map(|shdr| (strtab_get(&elf.shdr_strtab, shdr.sh_name), shdr))
+            .filter(|&(name, _)| dwarf::is_dwarf_section(name))
+            .map(|(name, shdr)| {
+                let uncompressed = if shdr.sh_flags as u32 & elf::section_header::SHF_COMPRESSED != 0 {
+                    section_bytes(bytes, shdr).and_then(|data| compressed::Chdr::parse(data, elf.is_64, elf.little_endian)).ok().map(|chdr| chdr.ch_size)
+                } else {
+                    None
+                };
+                (name, shdr.sh_size, uncompressed)
+            })
+            .collect();
+        let units = named_section(elf, ".debug_info").map(|debug_info| {
+            let debug_info = section_data(opt, elf, bytes, debug_info)?;
+            let debug_types = match named_section(elf, ".debug_types") {
+                Some(debug_types) => Some(section_data(opt, elf, bytes, debug_types)?),
+                None => None,
+            };
+            dwarf::units(&debug_info, debug_types.as_ref().map(|debug_types| &debug_types[..]), elf.little_endian)
+        });
+        Dwarf { sections, units, opt, layout: Layout::elf(opt, elf) }
+    }
+}
+
+impl<'a> ::std::fmt::Display for Dwarf<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.opt.theme;
+        let layout = self.layout;
+        fmt_header(fmt, colors, "DWARF", self.sections.len())?;
+        if self.sections.is_empty() {
+            return writeln!(fmt, "{}", "no debug info".dimmed());
+        }
+        for &(name, size, uncompressed) in &self.sections {
+            write!(fmt, "{:>20} size: {}", section_name(self.opt, name), layout.sz(uncompressed.unwrap_or(size)))?;
+            if uncompressed.is_some() {
+                write!(fmt, " {}", format!("({:#x} compressed)", size).dimmed())?;
+            }
+            writeln!(fmt)?;
+        }
+        let total = self.sections.iter().map(|&(_, size, uncompressed)| uncompressed.unwrap_or(size)).sum::<u64>();
+        writeln!(fmt)?;
+        writeln!(fmt, "total: {}", layout.sz(total))?;
+        match self.units {
+            Some(Ok(ref units)) => {
+                let versions = units.versions.iter().map(|version| version.to_string()).collect::<Vec<_>>();
+                write!(fmt, "units: {} compile, {} type", units.compile, units.types)?;
+                if !versions.is_empty() {
+                    write!(fmt, ", DWARF {}", versions.join(", ").bold())?;
+                }
+                writeln!(fmt)
+            },
+            Some(Err(ref err)) => writeln!(fmt, "units: {}", format!("{}", err).red()),
+            None => writeln!(fmt, "units: {}", "no .debug_info".dimmed()),
+        }
+    }
+}
+
+/// Whether `name` matches the glob `pattern`, where `*` matches any run of characters, and `?` any one
+fn glob_match (pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // where to resume after the last `*`, if the match after it fails
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn is_copy (r_type: u32, machine: u16) -> bool {
+    use elf::header::{EM_386, EM_X86_64, EM_ARM, EM_AARCH64};
+    use elf::reloc::{R_386_COPY, R_X86_64_COPY, R_ARM_COPY, R_AARCH64_COPY};
+    match machine {
+        EM_386 => r_type == R_386_COPY,
+        EM_X86_64 => r_type == R_X86_64_COPY,
+        EM_ARM => r_type == R_ARM_COPY,
+        EM_AARCH64 => r_type == R_AARCH64_COPY,
+        _ => false,
+    }
+}
+
+/// Whether `r_type` is the IRELATIVE relocation of `machine`, whose addend is an ifunc resolver
+fn is_irelative (r_type: u32, machine: u16) -> bool {
+    use elf::header::{EM_386, EM_X86_64, EM_ARM, EM_AARCH64};
+    use elf::reloc::{R_386_IRELATIVE, R_X86_64_IRELATIVE, R_ARM_IRELATIVE, R_AARCH64_IRELATIVE};
+    match machine {
+        EM_386 => r_type == R_386_IRELATIVE,
+        EM_X86_64 => r_type == R_X86_64_IRELATIVE,
+        EM_ARM => r_type == R_ARM_IRELATIVE,
+        EM_AARCH64 => r_type == R_AARCH64_IRELATIVE,
+        _ => false,
+    }
+}
+
+/// Splits `relocs` into the lines `--fold` prints them on: runs of the same type without a symbol, which only differ in
+/// where they apply, are collapsed; IRELATIVE relocations aren't, since each names a resolver
+fn fold_relocs (relocs: &[elf::Reloc], machine: u16, fold: bool) -> Vec<&[elf::Reloc]> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    while start < relocs.len() {
+        let first = &relocs[start];
+        let foldable = fold && first.r_sym == 0 && !is_irelative(first.r_type, machine);
+        let len = if foldable {
+            relocs[start..].iter().take_while(|reloc| reloc.r_sym == 0 && reloc.r_type == first.r_type).count()
+        } else {
+            1
+        };
+        groups.push(&relocs[start..start + len]);
+        start += len;
+    }
+    groups
+}
+
+/// The resolver address of an IRELATIVE relocation; REL relocations keep the addend in the slot itself
+fn irelative_target (elf: &elf::Elf, bytes: &[u8], reloc: &elf::Reloc) -> u64 {
+    if reloc.is_rela {
+        return reloc.r_addend as u64;
+    }
+    let le = scroll::Endian::from(elf.little_endian);
+    match vaddr_to_offset(elf, reloc.r_offset as u64) {
+        Some(offset) if elf.is_64 => bytes.pread_with::<u64>(offset, le).unwrap_or(0),
+        Some(offset) => bytes.pread_with::<u32>(offset, le).unwrap_or(0) as u64,
+        None => 0,
+    }
+}
+
+/// The name of the function at `address`, e.g. an ifunc resolver; resolvers are usually local, so the symbol table is
+/// searched before the dynamic one, and plain functions are preferred over the ifuncs they resolve
+fn resolver_name<'a> (elf: &'a elf::Elf<'a>, address: u64) -> Option<&'a str> {
+    use elf::sym::{STT_FUNC, STT_GNU_IFUNC};
+    let syms = || elf.syms.iter().map(|sym| (sym, &elf.strtab)).chain(elf.dynsyms.iter().map(|sym| (sym, &elf.dynstrtab)));
+    syms().find(|&(sym, _)| sym.st_value == address && sym.st_type() == STT_FUNC && sym.st_name != 0)
+        .or_else(|| syms().find(|&(sym, _)| sym.st_value == address && sym.st_type() == STT_GNU_IFUNC && sym.st_name != 0))
+        .and_then(|(sym, strtab)| strtab.get(sym.st_name).ok())
+}
+
+/// Lists the STT_GNU_IFUNC symbols, whose value is the resolver the dynamic linker calls to pick an implementation,
+/// with that resolver and the IRELATIVE relocations that call it
+fn fmt_ifuncs (fmt: &mut ::std::fmt::Formatter, opt: &Opt, elf: &elf::Elf, bytes: &[u8]) -> ::std::fmt::Result {
+    use elf::sym::{STT_FUNC, STT_GNU_IFUNC};
+    let colors = &opt.theme;
+    let machine = elf.header.e_machine;
+    let mut ifuncs = elf.syms.iter().map(|sym| (sym, &elf.strtab)).chain(elf.dynsyms.iter().map(|sym| (sym, &elf.dynstrtab)))
+        .filter(|&(sym, _)| sym.st_type() == STT_GNU_IFUNC)
+        .map(|(sym, strtab)| (strtab_get(strtab, sym.st_name), sym.st_value))
+        .collect::<Vec<_>>();
+    ifuncs.sort();
+    ifuncs.dedup();
+    if ifuncs.is_empty() {
+        return Ok(());
+    }
+    let layout = Layout::elf(opt, elf);
+    let irelatives = elf.dynrelas.iter().chain(elf.dynrels.iter()).chain(elf.pltrelocs.iter())
+        .filter(|reloc| is_irelative(reloc.r_type, machine))
+        .map(|reloc| (irelative_target(elf, bytes, reloc), reloc.r_offset as u64))
+        .collect::<Vec<_>>();
+    fmt_header(fmt, colors, "IFUNCs", ifuncs.len())?;
+    for (name, value) in ifuncs {
+        write!(fmt, "{} {} -> ", layout.addr(value), string(opt, name))?;
+        // the ifunc itself sits at the resolver's address, so only another function names the resolver
+        let resolver = elf.syms.iter().map(|sym| (sym, &elf.strtab)).chain(elf.dynsyms.iter().map(|sym| (sym, &elf.dynstrtab)))
+            .find(|&(sym, strtab)| sym.st_value == value && sym.st_type() == STT_FUNC && strtab_get(strtab, sym.st_name) != name)
+            .map(|(sym, strtab)| strtab_get(strtab, sym.st_name));
+        match resolver {
+            Some(resolver) => write!(fmt, "{} {}", string(opt, resolver), "(resolver)".dimmed())?,
+            None => write!(fmt, "{} {}", layout.addrx(value), "(resolver)".dimmed())?,
+        }
+        for &(_, slot) in irelatives.iter().filter(|&&(target, _)| target == value) {
+            write!(fmt, " slot: {}", layout.addrx(slot))?;
+        }
+        writeln!(fmt)?;
+    }
+    writeln!(fmt)
+}
+
+/// Lists the function pointers of the `.preinit_array`, `.init_array`, and `.fini_array` sections, the constructors and
+/// destructors run around `main`, each with the function it points to
+fn fmt_init_arrays (fmt: &mut ::std::fmt::Formatter, opt: &Opt, elf: &elf::Elf, bytes: &[u8]) -> ::std::fmt::Result {
+    use elf::section_header::{SHT_PREINIT_ARRAY, SHT_INIT_ARRAY, SHT_FINI_ARRAY};
+    use elf::header::ET_REL;
+    use elf::sym::{STT_FUNC, STT_SECTION};
+    let colors = &opt.theme;
+    let arrays = elf.section_headers.iter().enumerate()
+        .filter(|&(_, shdr)| shdr.sh_type == SHT_PREINIT_ARRAY || shdr.sh_type == SHT_INIT_ARRAY || shdr.sh_type == SHT_FINI_ARRAY)
+        .collect::<Vec<_>>();
+    if arrays.is_empty() {
+        return Ok(());
+    }
+    let layout = Layout::elf(opt, elf);
+    let word = if elf.is_64 { 8 } else { 4 };
+    let le = scroll::Endian::from(elf.little_endian);
+    // position independent binaries leave the pointers to dynamic relocations
+    let dynamic_relocs = elf.dynrelas.iter().chain(elf.dynrels.iter())
+        .map(|reloc| (reloc.r_offset as u64, reloc))
+        .collect::<::std::collections::HashMap<_, _>>();
+    let mut functions = elf.syms.iter().map(|sym| (sym, &elf.strtab)).chain(elf.dynsyms.iter().map(|sym| (sym, &elf.dynstrtab)))
+        .filter(|&(sym, _)| sym.st_type() == STT_FUNC && sym.st_value != 0)
+        .map(|(sym, strtab)| (sym.st_value, sym.st_size, strtab_get(strtab, sym.st_name)))
+        .collect::<Vec<_>>();
+    functions.sort();
+    functions.dedup_by_key(|&mut (value, _, _)| value);
+    for (i, shdr) in arrays {
+        let name = strtab_get(&elf.shdr_strtab, shdr.sh_name);
+        let data = section_bytes(bytes, shdr).unwrap_or(&[]);
+        // in relocatable objects, the pointers are filled in by the relocations of the section
+        let relocs = elf.shdr_relocs.iter()
+            .filter(|&&(reloc_shdr, _)| elf.section_headers.get(reloc_shdr).is_some_and(|reloc_shdr| reloc_shdr.sh_info as usize == i))
+            .flat_map(|(_, relocs)| relocs)
+            .collect::<Vec<_>>();
+        fmt_header(fmt, colors, name, data.len() / word)?;
+        for offset in (0..data.len() / word).map(|i| i * word) {
+            let value = if elf.is_64 { data.pread_with::<u64>(offset, le).unwrap_or(0) } else { data.pread_with::<u32>(offset, le).unwrap_or(0) as u64 };
+            let slot = if elf.header.e_type == ET_REL { Some(offset as u64) } else { shdr.sh_addr.checked_add(offset as u64) };
+            let slot = match slot {
+                Some(slot) => slot,
+                // a corrupt section can be placed so its slots run past the end of the address space
+                None => continue,
+            };
+            write!(fmt, "{} ", layout.addr(slot))?;
+            if elf.header.e_type == ET_REL {
+                match relocs.iter().find(|reloc| reloc.r_offset as u64 == slot) {
+                    Some(reloc) => {
+                        let sym = elf.syms.get(reloc.r_sym);
+                        let addend = if reloc.is_rela { reloc.r_addend } else { value as isize };
+                        let (target, addend) = match sym {
+                            // a local function is usually reached through its section's symbol, and its offset in the section
+                            Some(section) if section.st_type() == STT_SECTION => {
+                                match elf.syms.iter().find(|sym| sym.st_type() == STT_FUNC && sym.st_shndx == section.st_shndx && sym.st_value == addend as u64) {
+                                    Some(local) => (string(opt, strtab_get(&elf.strtab, local.st_name)).to_string(), 0),
+                                    None => (section_name(opt, elf.section_headers.get(section.st_shndx).map_or(BAD_INDEX, |shdr| strtab_get(&elf.shdr_strtab, shdr.sh_name))), addend),
+                                }
+                            },
+                            Some(sym) => (string(opt, strtab_get(&elf.strtab, sym.st_name)).to_string(), addend),
+                            None => (BAD_INDEX.to_owned(), addend),
+                        };
+                        match addend {
+                            0 => writeln!(fmt, "-> {}", target)?,
+                            _ => writeln!(fmt, "-> {}+{}", target, layout.offs(addend))?,
+                        }
+                    },
+                    None => writeln!(fmt, "-> {}", layout.addrx(value))?,
+                }
+                continue;
+            }
+            let target = match dynamic_relocs.get(&slot) {
+                // REL relocations keep the addend in the slot itself
+                Some(reloc) if reloc.r_sym == 0 => if reloc.is_rela { reloc.r_addend as u64 } else { value },
+                Some(reloc) => {
+                    let name = elf.dynsyms.get(reloc.r_sym).and_then(|sym| elf.dynstrtab.get(sym.st_name).ok()).unwrap_or("");
+                    writeln!(fmt, "-> {}", string(opt, name))?;
+                    continue;
+                },
+                None => value,
+            };
+            write!(fmt, "-> {} ", layout.codex(target))?;
+            // the function containing the address, which is usually its start
+            let address = layout.thumb_addr(target, true).unwrap_or(target);
+            let after = functions.partition_point(|&(value, _, _)| value <= address);
+            match after.checked_sub(1).map(|nearest| functions[nearest]) {
+                Some((value, size, name)) if address - value < ::std::cmp::max(size, 1) => {
+                    write!(fmt, "{}", string(opt, name))?;
+                    if address != value {
+                        write!(fmt, "+{:#x}", address - value)?;
+                    }
+                    writeln!(fmt)?;
+                },
+                _ => writeln!(fmt, "{}", "<unknown>".dimmed())?,
+            }
+        }
+        writeln!(fmt)?;
+    }
+    Ok(())
+}
+
+/// The slots of `.got` and `.got.plt`, with their contents in the file and the dynamic relocations that fill them in
+struct Got<'a> {
+    elf: &'a elf::Elf<'a>,
+    bytes: &'a [u8],
+    opt: &'a Opt,
+}
+
+impl<'a> ::std::fmt::Display for Got<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        use elf::reloc;
+        let opt = self.opt;
+        let colors = &opt.theme;
+        let elf = self.elf;
+        let machine = elf.header.e_machine;
+        let word = if elf.is_64 { 8 } else { 4 };
+        let layout = Layout::new(opt, elf.is_64);
+        let le = scroll::Endian::from(elf.little_endian);
+        let relocs = elf.dynrelas.iter().chain(elf.dynrels.iter()).chain(elf.pltrelocs.iter())
+            .map(|reloc| (reloc.r_offset as u64, reloc))
+            .collect::<::std::collections::HashMap<_, _>>();
+        let mut slots = Vec::new();
+        for shdr in &elf.section_headers {
+            let name = elf.shdr_strtab.get(shdr.sh_name).unwrap_or("");
+            if name != ".got" && name != ".got.plt" {
+                continue;
+            }
+            let data = section_bytes(self.bytes, shdr).unwrap_or(&[]);
+            for (i, offset) in (0..data.len() / word).map(|i| (i, i * word)) {
+                let value = if elf.is_64 { data.pread_with::<u64>(offset, le).unwrap_or(0) } else { data.pread_with::<u32>(offset, le).unwrap_or(0) as u64 };
+                // a corrupt section can be placed so its slots run past the end of the address space
+                if let Some(slot) = shdr.sh_addr.checked_add(offset as u64) {
+                    slots.push((slot, name, i, value));
+                }
+            }
+        }
+        fmt_header(fmt, colors, "GOT", slots.len())?;
+        for (slot, section, i, value) in slots {
+            write!(fmt, "{} {:<8} value: {} ", layout.addr(slot), section, layout.addrx(value))?;
+            match relocs.get(&slot) {
+                Some(reloc) => {
+                    write!(fmt, "{} ", reloc::r_to_str(reloc.r_type, machine))?;
+                    let addend = if reloc.r_addend == 0 { "".normal() } else { format!("+{}", layout.offs(reloc.r_addend)).normal() };
+                    if is_irelative(reloc.r_type, machine) {
+                        // REL relocations keep the addend in the slot itself
+                        let target = if reloc.is_rela { reloc.r_addend as u64 } else { value };
+                        match resolver_name(elf, target) {
+                            Some(name) => writeln!(fmt, "{} {}", string(self.opt, name), "(resolver)".dimmed())?,
+                            None => writeln!(fmt, "{} {}", layout.addrx(target), "(resolver)".dimmed())?,
+                        }
+                    } else if reloc.r_sym != 0 {
+                        let name = elf.dynsyms.get(reloc.r_sym).and_then(|sym| elf.dynstrtab.get(sym.st_name).ok()).unwrap_or("");
+                        writeln!(fmt, "{}{}", string(self.opt, name), addend)?;
+                    } else {
+                        writeln!(fmt, "{}", addend)?;
+                    }
+                },
+                // the first .got.plt slots are filled in by the linker and the dynamic linker, not by relocations
+                None if section == ".got.plt" && i == 0 => writeln!(fmt, "{}", "_DYNAMIC".dimmed())?,
+                None if section == ".got.plt" && i < 3 => writeln!(fmt, "{}", "(reserved)".dimmed())?,
+                None => writeln!(fmt)?,
+            }
+        }
+        writeln!(fmt)
+    }
+}
+
+/// How many bytes of an overlay are hexdumped
+const OVERLAY_PREVIEW: usize = 64;
+
+/// Data appended after the last byte referenced by any header, e.g. installer payloads or signatures
+struct Overlay<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+    layout: Layout,
+}
+
+impl<'a> Overlay<'a> {
+    /// Returns the overlay if the file extends past `extent`, the furthest offset the headers account for
+    fn new(bytes: &'a [u8], extent: u64, layout: Layout) -> Option<Self> {
+        if extent < bytes.len() as u64 {
+            let offset = extent as usize;
+            Some(Overlay { offset, bytes: &bytes[offset..], layout })
+        } else {
+            None
+        }
+    }
+
+    fn guess(&self) -> &'static str {
+        let bytes = self.bytes;
+        let preview = &bytes[..::std::cmp::min(bytes.len(), OVERLAY_PREVIEW)];
+        if bytes.starts_with(b"PK\x03\x04") {
+            "zip archive"
+        } else if bytes.starts_with(b"\x1f\x8b") {
+            "gzip data"
+        } else if bytes.starts_with(elf::header::ELFMAG) {
+            "ELF binary"
+        } else if bytes.starts_with(b"MZ") {
+            "PE binary"
+        } else if bytes.starts_with(&[0xfe, 0xed, 0xfa, 0xce]) || bytes.starts_with(&[0xce, 0xfa, 0xed, 0xfe])
+            || bytes.starts_with(&[0xfe, 0xed, 0xfa, 0xcf]) || bytes.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]) {
+            "Mach-o binary"
+        } else if bytes.starts_with(&[0xca, 0xfe, 0xba, 0xbe]) {
+            "Mach-o fat binary"
+        } else if preview.iter().all(|&b| b == 0) {
+            "zero padding"
+        } else if preview.iter().all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace()) {
+            "ASCII text"
+        } else {
+            "unknown data"
+        }
+    }
+}
+
+fn fmt_hexdump (fmt: &mut ::std::fmt::Formatter, layout: Layout, bytes: &[u8], base: usize) -> ::std::fmt::Result {
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        write!(fmt, "{:>16} ", layout.off((base + i * 16) as u64))?;
+        for j in 0..16 {
+            match chunk.get(j) {
+                Some(byte) => write!(fmt, "{:02x} ", byte)?,
+                None => write!(fmt, "   ")?,
+            }
+        }
+        let ascii = chunk.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }).collect::<String>();
+        writeln!(fmt, " {}", ascii.dimmed())?;
+    }
+    Ok(())
+}
+
+impl<'a> ::std::fmt::Display for Overlay<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let layout = self.layout;
+        let colors = &layout.colors;
+        writeln!(fmt, "{}: offset: {} size: {} ({})", hdr(colors, "Overlay"), layout.off(self.offset as u64), layout.sz(self.bytes.len() as u64), self.guess().bold())?;
+        writeln!(fmt)?;
+        fmt_hexdump(fmt, layout, &self.bytes[..::std::cmp::min(self.bytes.len(), OVERLAY_PREVIEW)], self.offset)
+    }
+}
+
+/// The furthest file offset referenced by the ELF header, program headers, or section headers
+fn elf_extent(elf: &elf::Elf) -> u64 {
+    let header = &elf.header;
+    let mut extent = ::std::cmp::max(header.e_ehsize as u64,
+                                     header.e_phoff + header.e_phnum as u64 * header.e_phentsize as u64);
+    extent = ::std::cmp::max(extent, header.e_shoff + header.e_shnum as u64 * header.e_shentsize as u64);
+    for phdr in &elf.program_headers {
+        extent = ::std::cmp::max(extent, phdr.p_offset.saturating_add(phdr.p_filesz));
+    }
+    for shdr in &elf.section_headers {
+        if shdr.sh_type != elf::section_header::SHT_NOBITS {
+            extent = ::std::cmp::max(extent, shdr.sh_offset.saturating_add(shdr.sh_size));
+        }
+    }
+    extent
+}
+
+/// The furthest file offset referenced by the load commands or segments of a Mach-o binary
+fn mach_extent(mach: &mach::MachO) -> u64 {
+    let mut extent = 0;
+    for lc in &mach.load_commands {
+        extent = ::std::cmp::max(extent, (lc.offset + lc.command.cmdsize()) as u64);
+    }
+    for segment in &*mach.segments {
+        extent = ::std::cmp::max(extent, segment.fileoff.saturating_add(segment.filesize));
+    }
+    extent
+}
+
+/// The furthest file offset referenced by the PE headers, section table, or the certificate table
+fn pe_extent(pe: &pe::PE) -> u64 {
+    let mut extent = 0;
+    for section in &pe.sections {
+        extent = ::std::cmp::max(extent, section.pointer_to_raw_data as u64 + section.size_of_raw_data as u64);
+    }
+    if let Some(ref optional_header) = pe.header.optional_header {
+        extent = ::std::cmp::max(extent, optional_header.windows_fields.size_of_headers as u64);
+        // the certificate table is the one data directory whose address is a file offset rather than an rva
+        if let Some(certificates) = optional_header.data_directories.get_certificate_table() {
+            extent = ::std::cmp::max(extent, certificates.virtual_address as u64 + certificates.size as u64);
+        }
+    }
+    extent
+}
+
+/// A binary discovered at some offset inside of the input
+struct Carved {
+    offset: usize,
+    format: &'static str,
+    arch: String,
+    kind: String,
+    /// The extent of the binary, when its headers let us derive it
+    size: Option<u64>,
+}
+
+impl Carved {
+    fn extension(&self) -> &'static str {
+        match self.format {
+            "ELF" => "elf",
+            "PE" => "exe",
+            "Mach-o" => "macho",
+            "Fat" => "fat",
+            _ => "a",
+        }
+    }
+}
+
+/// Fat headers with more architectures than this are almost certainly something else, e.g. Java class files
+const MAX_FAT_ARCHES: usize = 32;
+
+/// Attempt to parse a binary at the start of `bytes`, requiring the headers following the magic to parse
+fn carve_at(bytes: &[u8], offset: usize) -> Option<Carved> {
+    let carved = |format, arch, kind, size| Some(Carved { offset, format, arch, kind, size });
+    if bytes.starts_with(elf::header::ELFMAG) {
+        let elf = parse_elf(bytes).ok()?;
+        let header = &elf.header;
+        carved("ELF", elf::header::machine_to_str(header.e_machine).to_owned(), elf::header::et_to_str(header.e_type).to_owned(), Some(elf_extent(&elf)))
+    } else if bytes.starts_with(b"MZ") {
+        let pe = parse_pe(bytes).ok()?;
+        let coff_header = &pe.header.coff_header;
+        if coff_header.signature != pe::header::COFF_MAGIC {
+            return None;
+        }
+        let arch = match coff_header.machine {
+            pe::header::COFF_MACHINE_X86 => "x86".to_owned(),
+            pe::header::COFF_MACHINE_X86_64 => "x86_64".to_owned(),
+            machine => format!("{:#x}", machine),
+        };
+        carved("PE", arch, if pe.is_lib { "DLL" } else { "EXE" }.to_owned(), Some(pe_extent(&pe)))
+    } else if bytes.starts_with(archive::MAGIC) {
+        archive::Member::parse(bytes, &mut archive::SIZEOF_MAGIC.clone()).ok()?;
+        carved("Archive", String::new(), String::new(), None)
+    } else {
+        let magic = mach::peek(bytes, 0).ok()?;
+        if magic == mach::fat::FAT_MAGIC {
+            let multi = mach::MultiArch::new(bytes).ok()?;
+            if multi.narches == 0 || multi.narches > MAX_FAT_ARCHES {
+                return None;
+            }
+            let arches = fat_arches(bytes).ok()?;
+            let extent = arches.iter().fold(0, |extent, (arch, _)| ::std::cmp::max(extent, arch.offset as u64 + arch.size as u64));
+            let arch = arches.iter().map(|(arch, _)| mach::constants::cputype::cpu_type_to_str(arch.cputype)).collect::<Vec<_>>().join(",");
+            carved("Fat", arch, String::new(), Some(extent))
+        } else {
+            let mach = parse_mach(bytes).ok()?;
+            let header = &mach.header;
+            carved("Mach-o", mach::constants::cputype::cpu_type_to_str(header.cputype).to_owned(), mach::header::filetype_to_str(header.filetype).to_owned(), Some(mach_extent(&mach)))
+        }
+    }
+}
+
+fn carve (opt: &Opt, bytes: &[u8], out: &mut dyn Write) -> error::Result<()> {
+    let layout = Layout::new(opt, true);
+    let colors = &opt.theme;
+    const MAGICS: [&[u8]; 8] = [
+        elf::header::ELFMAG,
+        b"MZ",
+        archive::MAGIC,
+        &[0xca, 0xfe, 0xba, 0xbe],
+        &[0xfe, 0xed, 0xfa, 0xce],
+        &[0xce, 0xfa, 0xed, 0xfe],
+        &[0xfe, 0xed, 0xfa, 0xcf],
+        &[0xcf, 0xfa, 0xed, 0xfe],
+    ];
+    if let Some(ref dir) = opt.carve_extract {
+        ::std::fs::create_dir_all(dir)?;
+    }
+    let mut carved = Vec::new();
+    for offset in 0..bytes.len() {
+        let candidate = &bytes[offset..];
+        if MAGICS.iter().any(|magic| candidate.starts_with(magic)) {
+            if let Some(binary) = carve_at(candidate, offset) {
+                carved.push(binary);
+            }
+        }
+    }
+    writeln!(out, "{}:\n", hdr_size(colors, "Carved", carved.len()))?;
+    for binary in carved {
+        write!(out, "{:>16} {:<8} {:<10} {:<10} ", layout.off(binary.offset as u64), binary.format.bold(), binary.arch, binary.kind)?;
+        match binary.size {
+            Some(size) => write!(out, "{}", layout.sz(size))?,
+            None => write!(out, "{}", "?".dimmed())?,
+        }
+        if let Some(ref dir) = opt.carve_extract {
+            let end = match binary.size {
+                Some(size) => ::std::cmp::min(bytes.len() as u64, binary.offset as u64 + size) as usize,
+                None => bytes.len(),
+            };
+            let path = Path::new(dir).join(format!("{:#x}.{}", binary.offset, binary.extension()));
+            File::create(&path)?.write_all(&bytes[binary.offset..end])?;
+            write!(out, " -> {}", path.display())?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a separator, quote, or newline
+fn csv_field (field: &str) -> ::std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+fn csv_sections (info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    match info.binary {
+        Binary::Elf(ref elf) => {
+            use elf::section_header;
+            writeln!(out, "index,name,type,flags,offset,addr,size,link,entsize,align")?;
+            for (i, shdr) in elf.section_headers.iter().enumerate() {
+                let name = elf.shdr_strtab.get(shdr.sh_name).unwrap_or("");
+                let flags = section_header::SHF_FLAGS.iter()
+                    .filter(|&&flag| shdr.sh_flags as u32 & flag == flag)
+                    .map(|&flag| section_header::shf_to_str(flag).trim_start_matches("SHF_"))
+                    .collect::<Vec<_>>().join(" ");
+                writeln!(out, "{},{},{},{},{:#x},{:#x},{:#x},{},{},{}", i, csv_field(name), section_header::sht_to_str(shdr.sh_type), flags,
+                         shdr.sh_offset, shdr.sh_addr, shdr.sh_size, shdr.sh_link, shdr.sh_entsize, shdr.sh_addralign)?;
+            }
+        },
+        Binary::Mach(ref mach) => {
+            writeln!(out, "index,segment,section,type,flags,offset,addr,size,align")?;
+            let sections = mach.segments.sections()?.into_iter().flatten();
+            for (i, section) in sections.enumerate() {
+                writeln!(out, "{},{},{},{:#x},{:#x},{:#x},{:#x},{:#x},{}", i, csv_field(section.segname()?), csv_field(section.name()?),
+                         section.flags & mach::constants::SECTION_TYPE, section.flags & mach::constants::SECTION_ATTRIBUTES,
+                         section.offset, section.addr, section.size, 1u64.checked_shl(section.align).unwrap_or(0))?;
+            }
+        },
+        Binary::Pe(ref pe) => {
+            writeln!(out, "index,name,characteristics,offset,addr,size,virtual_size")?;
+            for (i, section) in pe.sections.iter().enumerate() {
+                let name = String::from_utf8_lossy(&section.name);
+                writeln!(out, "{},{},{:#x},{:#x},{:#x},{:#x},{:#x}", i, csv_field(name.trim_end_matches('\0')), section.characteristics,
+                         section.pointer_to_raw_data, section.virtual_address, section.size_of_raw_data, section.virtual_size)?;
+            }
+        },
+        Binary::MachFat(_) => return Err(error::Error::Malformed("--csv-sections needs a single architecture; use --carve to find each one and --offset to select it".into())),
+        _ => return Err(error::Error::Malformed("--csv-sections only supports ELF, Mach-o, and PE binaries".into())),
+    }
+    Ok(())
+}
+
+/// How wide the section name column of `--emit-map` is; longer names get a line of their own, as in ld's maps
+const MAP_NAME_WIDTH: usize = 16;
+
+/// Prints an `ld -Map` style report for `--emit-map`: the allocated sections in address order, each with its symbols by
+/// address and the slack no symbol accounts for, as uncolored text to diff between builds
+fn emit_map (opt: &Opt, info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    use elf::section_header::SHN_LORESERVE;
+    use elf::sym::{STT_SECTION, STT_FILE};
+    let (elf, bytes) = match info.binary {
+        Binary::Elf(ref elf) => (elf, info.bytes),
+        _ => return Err(error::Error::Malformed("--emit-map only supports ELF binaries".into())),
+    };
+    let width = if elf.is_64 { 18 } else { 10 };
+    let xindex = xindex_table(elf, bytes, elf::section_header::SHT_SYMTAB);
+    let dyn_xindex = xindex_table(elf, bytes, elf::section_header::SHT_DYNSYM);
+    // the symbol table first, so its names win over the dynamic symbols at the same place
+    let syms = elf.syms.iter().enumerate().map(|(i, sym)| (sym, &elf.strtab, real_shndx(sym, i, &xindex), false))
+        .chain(elf.dynsyms.iter().enumerate().map(|(i, sym)| (sym, &elf.dynstrtab, real_shndx(sym, i, &dyn_xindex), true)));
+    let mut by_section = ::std::collections::HashMap::<usize, Vec<(u64, u64, &str)>>::new();
+    let mut from_symtab = ::std::collections::HashSet::new();
+    for (sym, strtab, shndx, dynamic) in syms {
+        let name = strtab.get(sym.st_name).unwrap_or("");
+        if name.is_empty() || shndx == 0 || shndx >= SHN_LORESERVE as usize || sym.st_type() == STT_SECTION || sym.st_type() == STT_FILE {
+            continue;
+        }
+        if !dynamic {
+            from_symtab.insert((shndx, sym.st_value, sym.st_size));
+        } else if from_symtab.contains(&(shndx, sym.st_value, sym.st_size)) {
+            continue;
+        }
+        by_section.entry(shndx).or_default().push((sym.st_value, sym.st_size, name));
+    }
+    let mut sections = elf.section_headers.iter().enumerate().filter(|&(_, shdr)| shdr.is_alloc()).collect::<Vec<_>>();
+    sections.sort_by_key(|&(i, shdr)| (shdr.sh_addr, i));
+    writeln!(out, "Memory map")?;
+    for (i, shdr) in sections {
+        let name = elf.shdr_strtab.get(shdr.sh_name).unwrap_or(BAD_INDEX);
+        writeln!(out)?;
+        if name.len() < MAP_NAME_WIDTH {
+            write!(out, "{:<w$}", name, w = MAP_NAME_WIDTH)?;
+        } else {
+            write!(out, "{}\n{:<w$}", name, "", w = MAP_NAME_WIDTH)?;
+        }
+        writeln!(out, "{:#0aw$x} {:#10x}", shdr.sh_addr, shdr.sh_size, aw = width)?;
+        let mut symbols = by_section.remove(&i).unwrap_or_default();
+        symbols.sort();
+        // relocatable objects have section relative symbols
+        let base = if elf.header.e_type == elf::header::ET_REL { 0 } else { shdr.sh_addr };
+        let end = base.saturating_add(shdr.sh_size);
+        // aliases and nested symbols cover the same bytes, so only the bytes past the furthest one so far count
+        let mut covered = 0;
+        let mut furthest = base;
+        for &(value, size, name) in &symbols {
+            writeln!(out, "{:<w$}{:#0aw$x} {:#10x} {}", "", value, size, symbol_name(opt, name), w = MAP_NAME_WIDTH, aw = width)?;
+            let start = ::std::cmp::max(::std::cmp::min(value, end), furthest);
+            let stop = ::std::cmp::min(value.saturating_add(size), end);
+            if stop > start {
+                covered += stop - start;
+                furthest = stop;
+            }
+        }
+        writeln!(out, "{:<w$}symbols: {:#x} slack: {:#x}", "", covered, shdr.sh_size - covered, w = MAP_NAME_WIDTH)?;
+    }
+    Ok(())
+}
+
+/// The PE section characteristic of executable sections, which goblin doesn't define
+const IMAGE_SCN_MEM_EXECUTE: u32 = 0x20000000;
+
+/// The names of the functions a Mach-o binary exports
+fn mach_functions (mach: &mach::MachO) -> error::Result<Vec<String>> {
+    use mach::exports::ExportInfo;
+    use mach::constants::{S_ATTR_PURE_INSTRUCTIONS, S_ATTR_SOME_INSTRUCTIONS};
+    let code = mach.segments.sections()?.into_iter().flatten()
+        .filter(|section| section.flags & (S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS) != 0)
+        .map(|section| (section.offset as u64, (section.offset as u64).saturating_add(section.size)))
+        .collect::<Vec<_>>();
+    Ok(mach.exports()?.into_iter().filter(|export| match export.info {
+        ExportInfo::Regular { .. } => code.iter().any(|&(start, end)| start <= export.offset && export.offset < end),
+        _ => false,
+    }).map(|export| export.name).collect())
+}
+
+fn functions_only (opt: &Opt, info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    let names = match info.binary {
+        Binary::Elf(ref elf) => {
+            // like nm, fall back to the dynamic symbols when the binary is stripped
+            let (syms, strtab) = if !elf.syms.is_empty() { (&elf.syms, &elf.strtab) } else { (&elf.dynsyms, &elf.dynstrtab) };
+            syms.iter()
+                .filter(|sym| sym.st_type() == elf::sym::STT_FUNC && sym.st_shndx != elf::section_header::SHN_UNDEF as usize)
+                .filter_map(|sym| strtab.get(sym.st_name).ok().map(|name| name.to_owned()))
+                .collect::<Vec<_>>()
+        },
+        Binary::Mach(ref mach) => mach_functions(mach)?,
+        Binary::MachFat(ref arches) => {
+            let mut names = Vec::new();
+            for (_, _, mach) in arches {
+                let mach = mach.as_ref().map_err(|err| error::Error::Malformed(err.to_string()))?;
+                for name in mach_functions(mach)? {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+            names
+        },
+        Binary::Pe(ref pe) => {
+            let code = pe.sections.iter()
+                .filter(|section| section.characteristics & IMAGE_SCN_MEM_EXECUTE != 0)
+                .map(|section| (section.virtual_address as usize, section.virtual_address as usize + section.virtual_size as usize))
+                .collect::<Vec<_>>();
+            pe.exports.iter()
+                .filter(|export| export.reexport.is_none() && code.iter().any(|&(start, end)| start <= export.rva && export.rva < end))
+                .map(|export| export.name.to_owned())
+                .collect()
+        },
+        _ => return Err(error::Error::Malformed("--functions-only only supports ELF, Mach-o, and PE binaries".into())),
+    };
+    for name in names {
+        writeln!(out, "{}", symbol_name(opt, &name))?;
+    }
+    Ok(())
+}
+
+/// The file ranges of the sections of a binary, with their names, to tell which one a file offset is in
+fn file_sections (binary: &Binary) -> error::Result<Vec<(String, u64, u64)>> {
+    Ok(match *binary {
+        Binary::Elf(ref elf) => {
+            elf.section_headers.iter()
+                .filter(|shdr| shdr.sh_type != elf::section_header::SHT_NOBITS && shdr.sh_size != 0)
+                .map(|shdr| (strtab_get(&elf.shdr_strtab, shdr.sh_name).to_owned(), shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size)))
+                .collect()
+        },
+        Binary::Mach(ref mach) => {
+            let mut sections = Vec::new();
+            for section in mach.segments.sections()?.into_iter().flatten() {
+                // zerofill sections have no bytes in the file
+                if section.offset != 0 {
+                    let name = format!("{},{}", section.segname().unwrap_or(BAD_INDEX), section.name().unwrap_or(BAD_INDEX));
+                    sections.push((name, section.offset as u64, section.offset as u64 + section.data.len() as u64));
+                }
+            }
+            sections
+        },
+        Binary::Pe(ref pe) => {
+            pe.sections.iter()
+                .map(|section| (String::from_utf8_lossy(&section.name).trim_end_matches('\0').to_owned(),
+                                section.pointer_to_raw_data as u64, section.pointer_to_raw_data as u64 + section.size_of_raw_data as u64))
+                .collect()
+        },
+        _ => Vec::new(),
+    })
+}
+
+fn is_printable (byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' ' || byte == b'\t'
+}
+
+/// The runs of at least `min` printable ASCII characters in `bytes`, with their offsets
+fn ascii_strings (bytes: &[u8], min: usize) -> Vec<(usize, String)> {
+    let mut strings = Vec::new();
+    let mut start = 0;
+    for (i, &byte) in bytes.iter().chain(Some(&0)).enumerate() {
+        if !is_printable(byte) {
+            if i - start >= min {
+                strings.push((start, String::from_utf8_lossy(&bytes[start..i]).into_owned()));
+            }
+            start = i + 1;
+        }
+    }
+    strings
+}
+
+/// The runs of at least `min` printable ASCII characters encoded as UTF-16LE in `bytes`, at either alignment
+fn utf16_strings (bytes: &[u8], min: usize) -> Vec<(usize, String)> {
+    let mut strings = Vec::new();
+    for alignment in 0..2 {
+        let units = bytes.get(alignment..).unwrap_or(&[]).chunks(2);
+        let mut start = alignment;
+        let mut run = String::new();
+        for (i, unit) in units.chain(Some(&[0, 0][..])).enumerate() {
+            if unit.len() == 2 && unit[1] == 0 && is_printable(unit[0]) {
+                run.push(unit[0] as char);
+            } else {
+                if run.len() >= min {
+                    strings.push((start, run.clone()));
+                }
+                run.clear();
+                start = alignment + (i + 1) * 2;
+            }
+        }
+    }
+    strings.sort();
+    strings
+}
+
+fn strings (opt: &Opt, info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    let bytes = info.bytes;
+    let layout = Layout::new(opt, true);
+    let mut strings = ascii_strings(bytes, opt.min);
+    // the wide strings of Windows
+    if let Binary::Pe(_) = info.binary {
+        strings.extend(utf16_strings(bytes, opt.min));
+        strings.sort();
+    }
+    let sections = file_sections(&info.binary)?;
+    let base = opt.offset.map(|Number(base)| base).unwrap_or(0);
+    let width = name_width(opt, sections.iter().map(|(name, _, _)| name), 16);
+    let offset_width = layout.hex(bytes.len() as u64 + base, true).len();
+    for (offset, s) in strings {
+        let offset = offset as u64;
+        // the last, innermost, section containing it
+        let section = sections.iter().rev().find(|&&(_, start, end)| start <= offset && offset < end).map_or("", |(name, _, _)| name);
+        writeln!(out, "{:>offset_width$} {:<width$} {}", layout.off(offset + base), truncate(opt, section, width), string(opt, &s), offset_width = offset_width, width = width)?;
+    }
+    Ok(())
+}
+
+/// Exported symbols with the index of their version, and the versions
+type ElfExports<'a> = (Vec<(&'a str, u16)>, Vec<version::Defined<'a>>);
+
+/// The defined, visible global and weak dynamic symbols of `elf`, with the version each is in; the unversioned ones are in the base version
+fn elf_exports<'a> (elf: &'a elf::Elf, bytes: &[u8]) -> error::Result<ElfExports<'a>> {
+    use elf::section_header::{SHN_UNDEF, SHN_ABS, SHT_GNU_VERSYM, SHT_GNU_VERDEF};
+    use elf::sym::{STB_GLOBAL, STB_WEAK};
+    let version_section = |typ| elf.section_headers.iter().find(|shdr| shdr.sh_type == typ).and_then(|shdr| section_bytes(bytes, shdr).ok());
+    let versyms = version_section(SHT_GNU_VERSYM).map(|data| version::versym(data, elf.little_endian)).unwrap_or_default();
+    let defined = match version_section(SHT_GNU_VERDEF) {
+        Some(data) => version::verdef(data, elf.little_endian, &elf.dynstrtab)?,
+        None => Vec::new(),
+    };
+    let exports = elf.dynsyms.iter().enumerate()
+        .filter(|&(_, sym)| sym.st_shndx != SHN_UNDEF as usize && (sym.st_bind() == STB_GLOBAL || sym.st_bind() == STB_WEAK))
+        // hidden symbols can't be bound from outside, even if a broken link left them in .dynsym
+        .filter(|&(_, sym)| sym.st_other & 0x3 != STV_HIDDEN && sym.st_other & 0x3 != STV_INTERNAL)
+        .filter_map(|(i, sym)| {
+            let index = versyms.get(i).map_or(version::VER_NDX_GLOBAL, |versym| versym & version::VERSYM_VERSION);
+            elf.dynstrtab.get(sym.st_name).ok().filter(|name| !name.is_empty()).map(|name| (sym, name, index))
+        })
+        // ld defines an absolute symbol named after each version
+        .filter(|&(sym, name, _)| !(sym.st_shndx == SHN_ABS as usize && defined.iter().any(|version| version.name == name)))
+        .map(|(_, name, index)| (name, index))
+        .collect();
+    Ok((exports, defined))
+}
+
+/// Prints the exports of a binary as a list of raw names, or for ELF with `--version-script`, as a version script
+fn export_list (opt: &Opt, info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    match info.binary {
+        Binary::Elf(ref elf) => {
+            let (exports, defined) = elf_exports(elf, info.bytes)?;
+            if !opt.version_script {
+                for &(name, _) in &exports {
+                    writeln!(out, "{}", name)?;
+                }
+                return Ok(());
+            }
+            // the base version is the library's own name, the same as no version
+            let versions = defined.iter().filter(|version| version.flags & version::VER_FLG_BASE == 0).collect::<Vec<_>>();
+            let versioned = |index: u16| versions.iter().any(|version| version.index == index);
+            let unversioned = exports.iter().filter(|&&(_, index)| !versioned(index)).map(|&(name, _)| name).collect::<Vec<_>>();
+            if versions.is_empty() {
+                writeln!(out, "{{")?;
+                fmt_version_node(out, &unversioned, true)?;
+                writeln!(out, "}};")?;
+            }
+            for (i, version) in versions.iter().enumerate() {
+                let mut names = exports.iter().filter(|&&(_, index)| index == version.index).map(|&(name, _)| name).collect::<Vec<_>>();
+                // a version script can't mix named and anonymous versions, so the unversioned symbols join the first one
+                if i == 0 {
+                    names.extend(&unversioned);
+                }
+                writeln!(out, "{} {{", version.name)?;
+                fmt_version_node(out, &names, i == 0)?;
+                match version.parent {
+                    Some(parent) => writeln!(out, "}} {};", parent)?,
+                    None => writeln!(out, "}};")?,
+                }
+            }
+        },
+        Binary::Mach(ref mach) => {
+            for name in mach.exports()?.into_iter().map(|export| export.name) {
+                writeln!(out, "{}", name)?;
+            }
+        },
+        Binary::MachFat(_) => return Err(error::Error::Malformed("--export-list needs a single architecture; use --carve to find each one and --offset to select it".into())),
+        _ => return Err(error::Error::Malformed("--export-list and --version-script only support ELF and Mach-o binaries".into())),
+    }
+    Ok(())
+}
+
+/// The body of a version script node exporting `names`, hiding everything else if `local`
+fn fmt_version_node (out: &mut dyn Write, names: &[&str], local: bool) -> error::Result<()> {
+    if !names.is_empty() {
+        writeln!(out, "  global:")?;
+        for name in names {
+            writeln!(out, "    {};", name)?;
+        }
+    }
+    if local {
+        writeln!(out, "  local:")?;
+        writeln!(out, "    *;")?;
+    }
+    Ok(())
+}
+
+/// The defined symbols of an ELF or Mach-o binary, and their sizes.
+///
+/// Mach-o symbols have no size, so each one extends to the next symbol in its section, or the end of the section.
+fn sized_symbols (bytes: &[u8]) -> error::Result<Vec<(String, u64)>> {
+    match goblin::peek(&mut ::std::io::Cursor::new(bytes))? {
+        Hint::Elf(_) => {
+            use elf::sym::{STT_FILE, STT_SECTION};
+            let elf = parse_elf(bytes)?;
+            // like nm, fall back to the dynamic symbols when the binary is stripped
+            let (syms, strtab) = if !elf.syms.is_empty() { (&elf.syms, &elf.strtab) } else { (&elf.dynsyms, &elf.dynstrtab) };
+            Ok(syms.iter()
+                .filter(|sym| sym.st_shndx != elf::section_header::SHN_UNDEF as usize && sym.st_type() != STT_FILE && sym.st_type() != STT_SECTION)
+                .filter_map(|sym| strtab.get(sym.st_name).ok().filter(|name| !name.is_empty()).map(|name| (name.to_owned(), sym.st_size)))
+                .collect())
+        },
+        Hint::Mach(_) => {
+            let mach = parse_mach(bytes)?;
+            let sections = mach.segments.sections()?.into_iter().flatten().collect::<Vec<_>>();
+            let mut symbols = nlist::parse(bytes, &mach)?.into_iter()
+                .filter(|symbol| !symbol.is_stab() && symbol.n_type & nlist::N_TYPE == nlist::N_SECT && !symbol.name.is_empty())
+                .collect::<Vec<_>>();
+            symbols.sort_by_key(|symbol| (symbol.n_sect, symbol.n_value));
+            Ok(symbols.iter().enumerate().map(|(i, symbol)| {
+                // n_sect counts the sections of all segments from 1
+                let end = match symbols.get(i + 1) {
+                    Some(next) if next.n_sect == symbol.n_sect => next.n_value,
+                    _ => sections.get((symbol.n_sect as usize).wrapping_sub(1)).map_or(symbol.n_value, |section| section.addr + section.size),
+                };
+                (symbol.name.to_owned(), end.saturating_sub(symbol.n_value))
+            }).collect())
+        },
+        _ => Err(error::Error::Malformed("--diff-syms only supports ELF and Mach-o binaries".into())),
+    }
+}
+
+/// How the symbols of two binaries differ: each name with its total size in the old and new binary, if it is in it
+struct SymbolDiff<'a> {
+    changes: Vec<(String, Option<u64>, Option<u64>)>,
+    /// The total size of the symbols in the old and new binary
+    totals: (u64, u64),
+    opt: &'a Opt,
+}
+
+impl<'a> SymbolDiff<'a> {
+    fn new(old: Vec<(String, u64)>, new: Vec<(String, u64)>, opt: &'a Opt) -> Self {
+        use std::collections::BTreeMap;
+        // the alternate form of a Rust demangling leaves off the hashes
+        let name = |name: &str| match rustc_demangle::try_demangle(name) {
+            Ok(demangled) if opt.ignore_hash => format!("{:#}", demangled),
+            _ => symbol_name(opt, name),
+        };
+        let totals = (old.iter().map(|&(_, size)| size).sum(), new.iter().map(|&(_, size)| size).sum());
+        // symbols with the same name, like statics in different objects, are compared by their total size
+        let mut sizes: BTreeMap<String, (Option<u64>, Option<u64>)> = BTreeMap::new();
+        for (symbol, size) in old {
+            let entry = sizes.entry(name(&symbol)).or_insert((None, None));
+            entry.0 = Some(entry.0.unwrap_or(0) + size);
+        }
+        for (symbol, size) in new {
+            let entry = sizes.entry(name(&symbol)).or_insert((None, None));
+            entry.1 = Some(entry.1.unwrap_or(0) + size);
+        }
+        let mut changes = sizes.into_iter().filter(|&(_, (old, new))| old != new).map(|(name, (old, new))| (name, old, new)).collect::<Vec<_>>();
+        // stable, so equal changes stay in name order
+        changes.sort_by_key(|&(_, old, new)| ::std::cmp::Reverse((new.unwrap_or(0) as i64 - old.unwrap_or(0) as i64).abs()));
+        SymbolDiff { changes, totals, opt }
+    }
+}
+
+/// A size difference in hex with its sign
+fn delta_str (old: u64, new: u64) -> String {
+    if new >= old { format!("+{:#x}", new - old) } else { format!("-{:#x}", old - new) }
+}
+
+/// A size difference, red if it grew and green if it shrank
+fn delta (old: u64, new: u64) -> colored::ColoredString {
+    if new >= old { delta_str(old, new).red() } else { delta_str(old, new).green() }
+}
+
+impl<'a> ::std::fmt::Display for SymbolDiff<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let colors = &self.opt.theme;
+        let layout = Layout::new(self.opt, true);
+        let status = |old: Option<u64>, new: Option<u64>| match (old, new) {
+            (None, _) => "added",
+            (_, None) => "removed",
+            _ => "changed",
+        };
+        let size = |size: Option<u64>| size.map_or("-".to_owned(), |size| format!("{:#x}", size));
+        fmt_header(fmt, colors, "Symbol Diff", self.changes.len())?;
+        if self.opt.pretty {
+            let mut table = new_table(row![br->"Delta", b->"Symbol", br->"Old", br->"New", b->"Status"]);
+            for &(ref name, old, new) in &self.changes {
+                let delta_spec = if new.unwrap_or(0) >= old.unwrap_or(0) { "Frr" } else { "Fgr" };
+                table.add_row(Row::new(vec![
+                    Cell::new(&delta_str(old.unwrap_or(0), new.unwrap_or(0))).style_spec(delta_spec),
+                    string_cell(self.opt, name),
+                    Cell::new(&size(old)).style_spec("r"),
+                    Cell::new(&size(new)).style_spec("r"),
+                    Cell::new(status(old, new)),
+                ]));
+            }
+            fmt_table(fmt, self.opt, &table)?;
+        } else {
+            let width = name_width(self.opt, self.changes.iter().map(|(name, _, _)| name.as_str()), 0);
+            for &(ref name, old, new) in &self.changes {
+                write!(fmt, "{:>12} {:<width$} ", delta(old.unwrap_or(0), new.unwrap_or(0)), string(self.opt, name), width = width)?;
+                writeln!(fmt, "{} -> {} {}", layout.sz(old.unwrap_or(0)), layout.sz(new.unwrap_or(0)), status(old, new).dimmed())?;
+            }
+        }
+        writeln!(fmt)?;
+        let counts = [("added", false, true), ("removed", true, false), ("changed", true, true)].iter().map(|&(label, in_old, in_new)| {
+            let changes = self.changes.iter().filter(|&&(_, old, new)| old.is_some() == in_old && new.is_some() == in_new).collect::<Vec<_>>();
+            let old = changes.iter().map(|&&(_, old, _)| old.unwrap_or(0)).sum();
+            let new = changes.iter().map(|&&(_, _, new)| new.unwrap_or(0)).sum();
+            format!("{}: {} ({})", label, changes.len(), delta(old, new))
+        }).collect::<Vec<_>>();
+        writeln!(fmt, "{}", counts.join(" "))?;
+        writeln!(fmt, "total: {} -> {} ({})", layout.sz(self.totals.0), layout.sz(self.totals.1), delta(self.totals.0, self.totals.1))
+    }
+}
+
+/// Prints how the symbols of the input and the second binary differ
+fn diff_syms (opt: &Opt) -> error::Result<()> {
+    let other = opt.other.as_ref().ok_or_else(|| error::Error::Malformed("--diff-syms needs a second binary to compare with".into()))?;
+    let mut opt = opt.clone();
+    opt.demangle |= opt.ignore_hash;
+    let opt = &opt;
+    let old = sized_symbols(&read_input(opt)?)?;
+    let new = {
+        let mut bytes = Vec::new();
+        File::open(other)?.read_to_end(&mut bytes)?;
+        sized_symbols(&bytes)?
+    };
+    let mut out = output(opt)?;
+    write!(out, "{}", SymbolDiff::new(old, new, opt))?;
+    Ok(())
+}
+
+/// A value that differs between the old and the new binary, as `old -> new`
+fn changed<T: ::std::fmt::Display> (old: T, new: T) -> String {
+    format!("{} -> {}", old.to_string().red(), new.to_string().green())
+}
+
+/// The dynamic entries of `elf` but its libraries, with the strings of those that are offsets into the string table
+fn dyn_entries (elf: &elf::Elf) -> Vec<(u64, String)> {
+    elf.dynamic.as_ref().map_or(Vec::new(), |dynamic| {
+        dynamic.dyns.iter().filter(|dyn| dyn.d_tag != elf::dyn::DT_NULL && dyn.d_tag != elf::dyn::DT_NEEDED).map(|dyn| {
+            let value = match dyn_value(dyn.d_tag) {
+                Some(DynValue::Str) => elf.dynstrtab.get(dyn.d_val as usize).unwrap_or(BAD_INDEX).to_owned(),
+                _ => format!("{:#x}", dyn.d_val),
+            };
+            (dyn.d_tag, value)
+        }).collect()
+    })
+}
+
+/// How the headers of two ELF binaries differ, by part, leaving out the parts that don't
+fn elf_diff (layout: Layout, old: &elf::Elf, new: &elf::Elf) -> Vec<(&'static str, Vec<String>)> {
+    use elf::header;
+    use elf::program_header;
+    let mut parts = Vec::new();
+
+    let mut lines = Vec::new();
+    {
+        let mut field = |name: &str, old: String, new: String| if old != new { lines.push(format!("{}: {}", name, changed(old, new))) };
+        field("class", (if old.is_64 { "ELF64" } else { "ELF32" }).to_owned(), (if new.is_64 { "ELF64" } else { "ELF32" }).to_owned());
+        field("endianness", (if old.little_endian { "little" } else { "big" }).to_owned(), (if new.little_endian { "little" } else { "big" }).to_owned());
+        field("e_type", header::et_to_str(old.header.e_type).to_owned(), header::et_to_str(new.header.e_type).to_owned());
+        field("e_machine", header::machine_to_str(old.header.e_machine).to_owned(), header::machine_to_str(new.header.e_machine).to_owned());
+        field("e_entry", format!("{:#x}", old.header.e_entry), format!("{:#x}", new.header.e_entry));
+        field("e_flags", format!("{:#x}", old.header.e_flags), format!("{:#x}", new.header.e_flags));
+        field("e_phnum", old.header.e_phnum.to_string(), new.header.e_phnum.to_string());
+        field("e_shnum", old.header.e_shnum.to_string(), new.header.e_shnum.to_string());
+    }
+    parts.push(("Header", lines));
+
+    // segments are matched by their index and type
+    let mut lines = Vec::new();
+    for i in 0..::std::cmp::max(old.program_headers.len(), new.program_headers.len()) {
+        let typ = |phdr: &program_header::ProgramHeader| program_header::pt_to_str(phdr.p_type);
+        match (old.program_headers.get(i), new.program_headers.get(i)) {
+            (Some(old), Some(new)) if old.p_type == new.p_type => {
+                let fields = [
+                    ("p_offset", old.p_offset, new.p_offset),
+                    ("p_vaddr", old.p_vaddr, new.p_vaddr),
+                    ("p_filesz", old.p_filesz, new.p_filesz),
+                    ("p_memsz", old.p_memsz, new.p_memsz),
+                    ("p_flags", old.p_flags as u64, new.p_flags as u64),
+                    ("p_align", old.p_align, new.p_align),
+                ];
+                let fields = fields.iter().filter(|&&(_, old, new)| old != new)
+                    .map(|&(name, old, new)| format!("{}: {}", name, changed(format!("{:#x}", old), format!("{:#x}", new))))
+                    .collect::<Vec<_>>();
+                if !fields.is_empty() {
+                    lines.push(format!("{} {:<16} {}", layout.idx(i), typ(old), fields.join(" ")));
+                }
+            },
+            (old, new) => {
+                if let Some(old) = old {
+                    lines.push(format!("{} {}", layout.idx(i), format!("- {}", typ(old)).red()));
+                }
+                if let Some(new) = new {
+                    lines.push(format!("{} {}", layout.idx(i), format!("+ {}", typ(new)).green()));
+                }
+            },
+        }
+    }
+    parts.push(("ProgramHeaders", lines));
+
+    // sections are matched by name, in the order of the new binary, then the removed ones
+    let mut lines = Vec::new();
+    fn sections<'a> (elf: &'a elf::Elf) -> Vec<(&'a str, &'a elf::SectionHeader)> {
+        elf.section_headers.iter().filter(|shdr| shdr.sh_type != elf::section_header::SHT_NULL)
+            .map(|shdr| (elf.shdr_strtab.get(shdr.sh_name).unwrap_or(BAD_INDEX), shdr))
+            .collect()
+    }
+    let (old_sections, new_sections) = (sections(old), sections(new));
+    for &(name, new_shdr) in &new_sections {
+        match old_sections.iter().find(|&&(old_name, _)| old_name == name) {
+            Some(&(_, old_shdr)) => {
+                let mut fields = Vec::new();
+                if old_shdr.sh_type != new_shdr.sh_type {
+                    fields.push(format!("sh_type: {}", changed(elf::section_header::sht_to_str(old_shdr.sh_type), elf::section_header::sht_to_str(new_shdr.sh_type))));
+                }
+                for &(field, old, new) in &[("sh_addr", old_shdr.sh_addr, new_shdr.sh_addr), ("sh_size", old_shdr.sh_size, new_shdr.sh_size),
+                                             ("sh_flags", old_shdr.sh_flags, new_shdr.sh_flags), ("sh_addralign", old_shdr.sh_addralign, new_shdr.sh_addralign)] {
+                    if old != new {
+                        fields.push(format!("{}: {}", field, changed(format!("{:#x}", old), format!("{:#x}", new))));
+                    }
+                }
+                if !fields.is_empty() {
+                    lines.push(format!("{:>24} {}", name, fields.join(" ")));
+                }
+            },
+            None => lines.push(format!("{:>24} {} size: {}", name, "+ added".green(), layout.sz(new_shdr.sh_size))),
+        }
+    }
+    for &(name, old_shdr) in old_sections.iter().filter(|&&(name, _)| !new_sections.iter().any(|&(new_name, _)| new_name == name)) {
+        lines.push(format!("{:>24} {} size: {}", name, "- removed".red(), layout.sz(old_shdr.sh_size)));
+    }
+    parts.push(("Sections", lines));
+
+    // entries are compared by tag, since most repeat at most once
+    let mut lines = Vec::new();
+    let (old_dyns, new_dyns) = (dyn_entries(old), dyn_entries(new));
+    let mut tags = old_dyns.iter().chain(&new_dyns).map(|&(tag, _)| tag).collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        let values = |dyns: &[(u64, String)]| dyns.iter().filter(|&&(t, _)| t == tag).map(|(_, value)| value.clone()).collect::<Vec<_>>().join(", ");
+        let (old_values, new_values) = (values(&old_dyns), values(&new_dyns));
+        if old_values != new_values {
+            let or_none = |values: String| if values.is_empty() { "<none>".to_owned() } else { values };
+            lines.push(format!("{:>20} {}", tag_to_str(tag), changed(or_none(old_values), or_none(new_values))));
+        }
+    }
+    parts.push(("Dynamic", lines));
+
+    let mut lines = Vec::new();
+    for lib in new.libraries.iter().filter(|lib| !old.libraries.contains(lib)) {
+        lines.push(format!("{}", format!("+ {}", lib).green()));
+    }
+    for lib in old.libraries.iter().filter(|lib| !new.libraries.contains(lib)) {
+        lines.push(format!("{}", format!("- {}", lib).red()));
+    }
+    parts.push(("Libraries", lines));
+
+    parts.into_iter().filter(|(_, lines)| !lines.is_empty()).collect()
+}
+
+/// Prints how the headers of the input and the second binary differ; whether they do
+fn diff (opt: &Opt) -> error::Result<bool> {
+    let colors = &opt.theme;
+    let other = opt.other.as_ref().ok_or_else(|| error::Error::Malformed("--diff needs a second binary to compare with".into()))?;
+    let old_bytes = read_input(opt)?;
+    let new_bytes = {
+        let mut bytes = Vec::new();
+        File::open(other)?.read_to_end(&mut bytes)?;
+        bytes
+    };
+    let mut out = output(opt)?;
+    if old_bytes == new_bytes {
+        writeln!(out, "no differences")?;
+        return Ok(false);
+    }
+    for bytes in &[&old_bytes, &new_bytes] {
+        match goblin::peek(&mut ::std::io::Cursor::new(bytes))? {
+            Hint::Elf(_) => (),
+            _ => return Err(error::Error::Malformed("--diff only supports ELF binaries".into())),
+        }
+    }
+    let (old, new) = (parse_elf(&old_bytes)?, parse_elf(&new_bytes)?);
+    let parts = elf_diff(Layout::elf(opt, &new), &old, &new);
+    if parts.is_empty() {
+        writeln!(out, "no differences in the headers")?;
+    }
+    for &(name, ref lines) in &parts {
+        writeln!(out, "{}:\n", hdr_size(colors, name, lines.len()))?;
+        for line in lines {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(!parts.is_empty())
+}
+
+/// How much of each section --find-function scans, unless --max-scan-size says otherwise
+const DEFAULT_MAX_SCAN_SIZE: u64 = 64 * 1024 * 1024;
+
+/// An executable section, as scanned by --find-function
+struct CodeSection<'a> {
+    name: String,
+    offset: u64,
+    addr: u64,
+    data: &'a [u8],
+}
+
+/// The places a --find-function signature matched, with the named symbols of the binary by file offset, to
+/// find the nearest one to each match
+struct FoundFunctions<'a> {
+    signature: &'a Signature,
+    sections: Vec<CodeSection<'a>>,
+    symbols: Vec<(u64, String)>,
+    opt: &'a Opt,
+    layout: Layout,
+}
+
+impl<'a> ::std::fmt::Display for FoundFunctions<'a> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let opt = self.opt;
+        let colors = &opt.theme;
+        let layout = self.layout;
+        let limit = opt.max_scan_size.map(|Number(size)| size).unwrap_or(DEFAULT_MAX_SCAN_SIZE);
+        let matches = self.sections.iter().flat_map(|section| {
+            let scanned = &section.data[..::std::cmp::min(section.data.len() as u64, limit) as usize];
+            self.signature.find_all(scanned).into_iter().map(move |i| (section, i as u64))
+        }).collect::<Vec<_>>();
+        fmt_header(fmt, colors, &format!("Matches for {}", self.signature), matches.len())?;
+        for (section, i) in matches {
+            let offset = section.offset + i;
+            write!(fmt, "{:>16} {:<16} offset: {} ", layout.addrx(section.addr + i), section_name(opt, &section.name), layout.off(offset))?;
+            // the last symbol at or before the match, in the same section
+            let after = self.symbols.partition_point(|&(start, _)| start <= offset);
+            match after.checked_sub(1).map(|nearest| &self.symbols[nearest]) {
+                Some(&(start, ref name)) if start >= section.offset => {
+                    write!(fmt, "{}", string(opt, name))?;
+                    if offset != start {
+                        write!(fmt, "+{:#x}", offset - start)?;
+                    }
+                    writeln!(fmt)?;
+                },
+                _ => writeln!(fmt)?,
+            }
+        }
+        for section in self.sections.iter().filter(|section| section.data.len() as u64 > limit) {
+            writeln!(fmt, "{}", format!("only scanned the first {:#x} of the {:#x} bytes of {}, see --max-scan-size", limit, section.data.len(), section.name).dimmed())?;
+        }
+        writeln!(fmt)
+    }
+}
+
+fn find_function (opt: &Opt, signature: &Signature, info: &BinaryInfo, out: &mut dyn Write) -> error::Result<()> {
+    let bytes = info.bytes;
+    let (sections, mut symbols, layout) = match info.binary {
+        Binary::Elf(ref elf) => {
+            use elf::section_header::{SHF_EXECINSTR, SHT_NOBITS, SHN_UNDEF};
+            let shdrs = &elf.section_headers;
+            let mut sections = Vec::new();
+            for shdr in shdrs.iter().filter(|shdr| shdr.sh_flags as u32 & SHF_EXECINSTR != 0) {
+                sections.push(CodeSection {
+                    name: elf.shdr_strtab.get(shdr.sh_name).unwrap_or(BAD_INDEX).to_owned(),
+                    offset: shdr.sh_offset,
+                    addr: shdr.sh_addr,
+                    data: section_bytes(bytes, shdr)?,
+                });
+            }
+            let is_rel = elf.header.e_type == elf::header::ET_REL;
+            let xindex = xindex_table(elf, bytes, elf::section_header::SHT_SYMTAB);
+            let dyn_xindex = xindex_table(elf, bytes, elf::section_header::SHT_DYNSYM);
+            let syms = elf.syms.iter().enumerate().map(|(i, sym)| (sym, &elf.strtab, real_shndx(sym, i, &xindex)))
+                .chain(elf.dynsyms.iter().enumerate().map(|(i, sym)| (sym, &elf.dynstrtab, real_shndx(sym, i, &dyn_xindex))));
+            let mut symbols = Vec::new();
+            for (sym, strtab, shndx) in syms {
+                let name = strtab.get(sym.st_name).unwrap_or("");
+                if name.is_empty() || shndx == SHN_UNDEF as usize || sym.st_type() == elf::sym::STT_SECTION || sym.st_type() == elf::sym::STT_FILE {
+                    continue;
+                }
+                // relocatable objects have section relative symbols, everything else has addresses
+                let offset = match shdrs.get(shndx) {
+                    Some(shdr) if shdr.sh_type != SHT_NOBITS && is_rel => shdr.sh_offset.checked_add(sym.st_value),
+                    Some(shdr) if shdr.sh_type != SHT_NOBITS => sym.st_value.checked_sub(shdr.sh_addr).and_then(|delta| shdr.sh_offset.checked_add(delta)),
+                    _ => None,
+                };
+                if let Some(offset) = offset {
+                    symbols.push((offset, name.to_owned()));
+                }
+            }
+            (sections, symbols, Layout::elf(opt, elf))
+        },
+        Binary::Mach(ref mach) => {
+            use mach::constants::{S_ATTR_PURE_INSTRUCTIONS, S_ATTR_SOME_INSTRUCTIONS};
+            let all = mach.segments.sections()?.into_iter().flatten().collect::<Vec<_>>();
+            let sections = all.iter().filter(|section| section.flags & (S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS) != 0).map(|section| CodeSection {
+                name: format!("{},{}", section.segname().unwrap_or(BAD_INDEX), section.name().unwrap_or(BAD_INDEX)),
+                offset: section.offset as u64,
+                addr: section.addr,
+                data: section.data,
+            }).collect();
+            // n_sect counts the sections of every segment, from 1
+            let symbols = nlist::parse(bytes, mach)?.into_iter()
+                .filter(|symbol| !symbol.is_stab() && symbol.n_type & nlist::N_TYPE == nlist::N_SECT && !symbol.name.is_empty())
+                .filter_map(|symbol| {
+                    let section = all.get((symbol.n_sect as usize).checked_sub(1)?)?;
+                    Some((section.offset as u64 + symbol.n_value.checked_sub(section.addr)?, symbol.name.to_owned()))
+                }).collect();
+            (sections, symbols, Layout::new(opt, mach.is_64))
+        },
+        Binary::Pe(ref pe) => {
+            let mut sections = Vec::new();
+            for section in pe.sections.iter().filter(|section| section.characteristics & IMAGE_SCN_MEM_EXECUTE != 0) {
+                let start = section.pointer_to_raw_data as usize;
+                let data = bytes.get(start..start.saturating_add(section.size_of_raw_data as usize))
+                    .ok_or_else(|| error::Error::Malformed(format!("section at {:#x} of size {:#x} is past the end of the file", start, section.size_of_raw_data)))?;
+                sections.push(CodeSection {
+                    name: String::from_utf8_lossy(&section.name).trim_end_matches('\0').to_owned(),
+                    offset: start as u64,
+                    addr: section.virtual_address as u64,
+                    data,
+                });
+            }
+            let symbols = pe.exports.iter().filter(|export| export.reexport.is_none())
+                .filter_map(|export| clr::find_offset(export.rva as u32, &pe.sections).map(|offset| (offset as u64, export.name.to_owned())))
+                .collect();
+            (sections, symbols, Layout::new(opt, pe.is_64))
+        },
+        Binary::MachFat(_) => return Err(error::Error::Malformed("--find-function needs a single architecture; use --carve to find each one and --offset to select it".into())),
+        _ => return Err(error::Error::Malformed("--find-function only supports ELF, Mach-o, and PE binaries".into())),
+    };
+    symbols.sort();
+    write!(out, "{}", FoundFunctions { signature, sections, symbols, opt, layout })?;
+    Ok(())
+}
+
+/// The input file, from `--offset` on; everything else sees the file as if it started there
+fn read_input (opt: &Opt) -> error::Result<Vec<u8>> {
+    let mut fd = File::open(&opt.input)?;
+    let mut file = { let mut v = Vec::new(); fd.read_to_end(&mut v)?; v };
+    let base = opt.offset.map(|Number(offset)| offset).unwrap_or(0);
+    if base > file.len() as u64 {
+        return Err(error::Error::Malformed(format!("offset {:#x} is past the end of the file ({:#x})", base, file.len())));
+    }
+    file.drain(..base as usize);
+    Ok(file)
+}
+
+/// Where to print to: `--output`, or stdout
+fn output (opt: &Opt) -> error::Result<Box<dyn Write>> {
+    Ok(match opt.output_file() {
+        Some(output) => Box::new(File::create(output)?),
+        None => Box::new(::std::io::stdout()),
+    })
+}
+
+fn run (opt: Opt) -> error::Result<()> {
+    let bytes = read_input(&opt)?;
+    let mut out = output(&opt)?;
+    print(&opt, &bytes, &mut *out)
+}
+
+/// Whether `elf` is a position independent executable; shared libraries are ET_DYN too, but only an executable asks for
+/// an interpreter, or marks itself DF_1_PIE when it's static-pie and has none
+fn is_pie (elf: &elf::Elf) -> bool {
+    if elf.header.e_type != elf::header::ET_DYN {
+        return false;
+    }
+    let interp = elf.program_headers.iter().any(|phdr| phdr.p_type == elf::program_header::PT_INTERP);
+    let flags_1 = elf.dynamic.as_ref()
+        .and_then(|dynamic| dynamic.dyns.iter().find(|dyn| dyn.d_tag == elf::dyn::DT_FLAGS_1))
+        .map_or(0, |dyn| dyn.d_val);
+    interp || flags_1 & DF_1_PIE != 0
+}
+
+/// Prints whether the input is a PIE for `--pie-check`; whether it is
+fn pie_check (opt: &Opt) -> error::Result<bool> {
+    let bytes = read_input(opt)?;
+    let mut out = output(opt)?;
+    match goblin::peek(&mut ::std::io::Cursor::new(&bytes))? {
+        Hint::Elf(_) => (),
+        _ => return Err(error::Error::Malformed("--pie-check only supports ELF binaries".into())),
+    }
+    let elf = parse_elf(&bytes)?;
+    let pie = is_pie(&elf);
+    writeln!(out, "{}: {}", opt.input, if pie { "PIE".green().bold() } else { "NOT PIE".red().bold() })?;
+    Ok(pie)
+}
+
+/// Prints the file offset of each `--search` match, one per line and nothing else, for scripts; whether there were any
+fn offsets_only (opt: &Opt) -> error::Result<bool> {
+    let search = opt.search.as_ref().ok_or_else(|| error::Error::Malformed("--offsets-only needs --search".into()))?;
+    let bytes = read_input(opt)?;
+    let mut out = output(opt)?;
+    let base = opt.offset.map(|Number(base)| base).unwrap_or(0);
+    let matches = find_all(&bytes, search);
+    for &offset in &matches {
+        writeln!(out, "{:#x}", offset as u64 + base)?;
+    }
+    Ok(!matches.is_empty())
+}
+
+/// Prints `bytes`, the input file, to `out`
+fn print (opt: &Opt, bytes: &[u8], out: &mut dyn Write) -> error::Result<()> {
+    let info = if opt.raw {
+        let arch = opt.arch.ok_or_else(|| error::Error::Malformed("--raw needs --arch, there's no header to tell the architecture from".into()))?;
+        let base = opt.base.map(|Number(base)| base).unwrap_or(0);
+        parse_raw(bytes, arch, base)?
+    } else if opt.carve || opt.carve_extract.is_some() || opt.strings {
+        // these search any file, so one bingrep can't parse is searched as it is
+        parse_binary(bytes).unwrap_or(BinaryInfo { bytes, binary: Binary::Unparsed })
+    } else {
+        parse_binary(bytes)?
+    };
+    display_binary(&info, opt, out)
+}
+
+/// A binary parsed by `parse_binary`, for `display_binary` to print
+struct BinaryInfo<'a> {
+    /// The bytes it was parsed from, which the listings hexdump and look things up in
+    bytes: &'a [u8],
+    binary: Binary<'a>,
+}
+
+/// The headers of each kind of binary bingrep shows
+enum Binary<'a> {
+    Elf(elf::Elf<'a>),
+    Pe(pe::PE<'a>),
+    Mach(mach::MachO<'a>),
+    /// Each architecture with its bytes; one that doesn't parse doesn't hide the others
+    MachFat(Vec<(mach::fat::FatArch, &'a [u8], error::Result<mach::MachO<'a>>)>),
+    Archive(Vec<ar::Member<'a>>),
+    Wasm(wasm::Module<'a>),
+    Coff(coff::Coff<'a>),
+    /// goblin doesn't know bitcode, and there are no sections or symbols in it to show
+    Bitcode(bitcode::Bitcode),
+    /// A headerless code blob, disassembled as `--arch` from `--base`
+    Raw(Vec<disasm::Insn>, disasm::Arch, u64),
+    /// Bytes that aren't a binary bingrep can parse, which `--strings` and `--carve` still search
+    Unparsed,
+}
+
+/// Parses `bytes` as whichever kind of binary it is, without printing anything
+fn parse_binary<'a> (bytes: &'a [u8]) -> error::Result<BinaryInfo<'a>> {
+    if bitcode::is_bitcode(bytes) {
+        return Ok(BinaryInfo { bytes, binary: Binary::Bitcode(bitcode::parse(bytes)?) });
+    }
+    let binary = match goblin::peek(&mut ::std::io::Cursor::new(bytes))? {
+        Hint::Unknown(_) if bytes.starts_with(wasm::WASM_MAGIC) => Binary::Wasm(wasm::parse(bytes)?),
+        Hint::Unknown(_) if coff::is_coff(bytes) => Binary::Coff(coff::parse(bytes)?),
+        Hint::Unknown(magic) => return Err(error::Error::BadMagic(magic)),
+        Hint::Elf(_) => Binary::Elf(parse_elf(bytes)?),
+        Hint::PE => Binary::Pe(parse_pe(bytes)?),
+        Hint::MachFat(_) => Binary::MachFat(fat_arches(bytes)?.into_iter().map(|(arch, data)| (arch, data, parse_mach(data))).collect()),
+        Hint::Mach(_) => Binary::Mach(parse_mach(bytes)?),
+        Hint::Archive => Binary::Archive(ar::members(bytes)?),
+    };
+    Ok(BinaryInfo { bytes, binary })
+}
+
+/// Disassembles `bytes`, which have no header to parse, as code for `arch` loaded at `base`
+fn parse_raw<'a> (bytes: &'a [u8], arch: disasm::Arch, base: u64) -> error::Result<BinaryInfo<'a>> {
+    Ok(BinaryInfo { bytes, binary: Binary::Raw(disasm::disassemble(bytes, arch, base)?, arch, base) })
+}
+
+/// Prints `Name(total): KIND count, ...` for `--summary`, most common kind first
+fn fmt_counts<I: IntoIterator<Item = String>> (out: &mut dyn Write, colors: &theme::Theme, name: &str, kinds: I) -> error::Result<()> {
+    let mut counts = ::std::collections::BTreeMap::new();
+    for kind in kinds {
+        *counts.entry(kind).or_insert(0) += 1;
+    }
+    let total = counts.values().sum();
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_by(|&(ref a, a_count), &(ref b, b_count)| b_count.cmp(&a_count).then(a.cmp(b)));
+    if counts.is_empty() {
+        writeln!(out, "{}", hdr_size(colors, name, total))?;
+        return Ok(());
+    }
+    let counts = counts.iter().map(|&(ref kind, count)| format!("{} {}", kind, count)).collect::<Vec<_>>();
+    writeln!(out, "{}: {}", hdr_size(colors, name, total), counts.join(", "))?;
+    Ok(())
+}
+
+/// The name of a Mach-o section type, the low byte of its flags
+fn mach_section_type_to_str (flags: u32) -> &'static str {
+    use mach::constants::*;
+    match flags & SECTION_TYPE {
+        S_REGULAR => "REGULAR",
+        S_ZEROFILL => "ZEROFILL",
+        S_CSTRING_LITERALS => "CSTRING_LITERALS",
+        S_4BYTE_LITERALS => "4BYTE_LITERALS",
+        S_8BYTE_LITERALS => "8BYTE_LITERALS",
+        S_LITERAL_POINTERS => "LITERAL_POINTERS",
+        S_NON_LAZY_SYMBOL_POINTERS => "NON_LAZY_SYMBOL_POINTERS",
+        S_LAZY_SYMBOL_POINTERS => "LAZY_SYMBOL_POINTERS",
+        S_SYMBOL_STUBS => "SYMBOL_STUBS",
+        S_MOD_INIT_FUNC_POINTERS => "MOD_INIT_FUNC_POINTERS",
+        S_MOD_TERM_FUNC_POINTERS => "MOD_TERM_FUNC_POINTERS",
+        S_COALESCED => "COALESCED",
+        S_GB_ZEROFILL => "GB_ZEROFILL",
+        S_INTERPOSING => "INTERPOSING",
+        S_16BYTE_LITERALS => "16BYTE_LITERALS",
+        S_DTRACE_DOF => "DTRACE_DOF",
+        S_LAZY_DYLIB_SYMBOL_POINTERS => "LAZY_DYLIB_SYMBOL_POINTERS",
+        S_THREAD_LOCAL_REGULAR => "THREAD_LOCAL_REGULAR",
+        S_THREAD_LOCAL_ZEROFILL => "THREAD_LOCAL_ZEROFILL",
+        S_THREAD_LOCAL_VARIABLES => "THREAD_LOCAL_VARIABLES",
+        S_THREAD_LOCAL_VARIABLE_POINTERS => "THREAD_LOCAL_VARIABLE_POINTERS",
+        S_THREAD_LOCAL_INIT_FUNCTION_POINTERS => "THREAD_LOCAL_INIT_FUNCTION_POINTERS",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Prints only counts for `--summary`: sections, segments, and symbols by kind, relocations by type, and how many
+/// libraries, exports, and imports; nothing is formatted per item, so it's quick even for huge binaries
+fn summary (info: &BinaryInfo, colors: &theme::Theme, out: &mut dyn Write) -> error::Result<()> {
+    match info.binary {
+        Binary::Elf(ref elf) => {
+            use elf::section_header::{self, SHN_UNDEF};
+            use elf::sym::{self, STB_LOCAL};
+            let machine = elf.header.e_machine;
+            fmt_counts(out, colors, "Sections", elf.section_headers.iter().map(|shdr| section_header::sht_to_str(shdr.sh_type).trim_start_matches("SHT_").to_owned()))?;
+            fmt_counts(out, colors, "ProgramHeaders", elf.program_headers.iter().map(|phdr| pt_to_str(phdr.p_type).trim_start_matches("PT_").to_owned()))?;
+            let kind = |sym: &elf::Sym| format!("{} {}", sym::bind_to_str(sym.st_bind()), sym::type_to_str(sym.st_type()));
+            fmt_counts(out, colors, "Syms", elf.syms.iter().map(&kind))?;
+            fmt_counts(out, colors, "Dyn Syms", elf.dynsyms.iter().map(&kind))?;
+            let relocs = elf.dynrelas.iter().chain(elf.dynrels.iter()).chain(elf.pltrelocs.iter());
+            fmt_counts(out, colors, "Dynamic Relocations", relocs.map(|reloc| elf::reloc::r_to_str(reloc.r_type, machine).to_owned()))?;
+            let shdr_relocs = elf.shdr_relocs.iter().flat_map(|(_, relocs)| relocs.iter());
+            fmt_counts(out, colors, "Shdr Relocations", shdr_relocs.map(|reloc| elf::reloc::r_to_str(reloc.r_type, machine).to_owned()))?;
+            writeln!(out, "{}", hdr_size(colors, "Libraries", elf.libraries.len()))?;
+            let exports = elf_exports(elf, info.bytes).map(|(exports, _)| exports.len()).unwrap_or(0);
+            writeln!(out, "{}", hdr_size(colors, "Exports", exports))?;
+            let imports = elf.dynsyms.iter().filter(|sym| sym.st_shndx == SHN_UNDEF as usize && sym.st_bind() != STB_LOCAL && sym.st_name != 0).count();
+            writeln!(out, "{}", hdr_size(colors, "Imports", imports))?;
+        },
+        Binary::Mach(ref mach) => {
+            use mach::load_command;
+            let sections = mach.segments.sections()?.into_iter().flatten().collect::<Vec<_>>();
+            fmt_counts(out, colors, "Segments", mach.segments.iter().map(|segment| segment.name().unwrap_or(BAD_SEGMENT_NAME).to_owned()))?;
+            fmt_counts(out, colors, "Sections", sections.iter().map(|section| mach_section_type_to_str(section.flags).to_owned()))?;
+            fmt_counts(out, colors, "LoadCommands", mach.load_commands.iter().map(|lc| load_command::cmd_to_str(lc.command.cmd()).trim_start_matches("LC_").to_owned()))?;
+            let symbols = nlist::parse(info.bytes, mach)?;
+            let kind = |symbol: &nlist::Nlist| if symbol.is_stab() {
+                "STAB".to_owned()
+            } else {
+                format!("{} {}", if symbol.n_type & nlist::N_EXT != 0 { "EXT" } else { "LOCAL" }, nlist::n_type_to_str(symbol.n_type))
+            };
+            fmt_counts(out, colors, "Syms", symbols.iter().map(kind))?;
+            // the first library is the binary's own install name
+            writeln!(out, "{}", hdr_size(colors, "Libraries", mach.libs.len().saturating_sub(1)))?;
+            writeln!(out, "{}", hdr_size(colors, "Exports", mach.exports().map(|exports| exports.len()).unwrap_or(0)))?;
+            writeln!(out, "{}", hdr_size(colors, "Imports", mach.imports().map(|imports| imports.len()).unwrap_or(0)))?;
+        },
+        Binary::MachFat(_) => return Err(error::Error::Malformed("--summary needs a single architecture; use --carve to find each one and --offset to select it".into())),
+        _ => return Err(error::Error::Malformed("--summary only supports ELF and Mach-o binaries".into())),
+    }
+    Ok(())
+}
+
+/// Prints `info`, parsed from the input file, to `out` as the options ask
+fn display_binary (info: &BinaryInfo, opt: &Opt, out: &mut dyn Write) -> error::Result<()> {
+    // each binary is demangled afresh, e.g. when --watch reprints it after a rebuild
+    let opt = &Opt { demangled: Default::default(), ..opt.clone() };
+    let bytes = info.bytes;
+    if opt.carve || opt.carve_extract.is_some() {
+        return carve(opt, bytes, out);
+    }
+    if opt.csv_sections {
+        return csv_sections(info, out);
+    }
+    if opt.functions_only {
+        return functions_only(opt, info, out);
+    }
+    if opt.export_list || opt.version_script {
+        return export_list(opt, info, out);
+    }
+    if opt.strings {
+        return strings(opt, info, out);
+    }
+    if opt.emit_map {
+        return emit_map(opt, info, out);
+    }
+    if let Some(ref signature) = opt.find_function {
+        return find_function(opt, signature, info, out);
+    }
+    if opt.summary {
+        return summary(info, &opt.theme, out);
+    }
+    match info.binary {
+        Binary::Raw(ref insns, arch, base) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", insns)?;
+            } else {
+                writeln!(out, "{}", Raw { insns, arch, base, size: bytes.len(), opt: opt.clone() })?;
+            }
+        },
+        Binary::Unparsed => return Err(error::Error::Malformed("the input is not a binary bingrep can parse".into())),
+        Binary::Bitcode(ref bitcode) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", bitcode)?;
+            } else {
+                writeln!(out, "{}", Bitcode(bitcode, opt))?;
+            }
+        },
+        Binary::Wasm(ref module) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", module)?;
+            } else {
+                writeln!(out, "{}", Wasm { module, opt: opt.clone(), bytes })?;
+            }
+        },
+        Binary::Coff(ref coff) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", coff)?;
+            } else {
+                writeln!(out, "{}", Coff(coff, opt.clone()))?;
+            }
+        },
+        Binary::Elf(ref elf) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", elf)?;
+            } else if opt.anomalies {
+                writeln!(out, "{}", Anomalies { elf, size: bytes.len(), opt })?;
+            } else if opt.section_gaps {
+                write!(out, "{}", Gaps::elf(elf, bytes, opt))?;
+            } else if opt.alignment_waste {
+                write!(out, "{}", AlignmentWaste::elf(elf, bytes.len() as u64, opt))?;
+            } else if opt.dwarf {
+                write!(out, "{}", Dwarf::elf(elf, bytes, opt))?;
+            } else if opt.got {
+                write!(out, "{}", Got { elf, bytes, opt })?;
+            } else if let Some(ref name) = opt.extract {
+                let shdr = named_section(elf, name).ok_or_else(|| error::Error::Malformed(format!("there is no section named {}", name)))?;
+                out.write_all(&section_data(opt, elf, bytes, shdr)?)?;
+            } else {
+                let path = Path::new(&opt.input);
+                let overlay = Overlay::new(bytes, elf_extent(elf), Layout::elf(opt, elf));
+                let mut debug_bytes = Vec::new();
+                let debug_lookup = match debuglink(elf, bytes) {
+                    Some(ref link) if opt.follow_debuglink => Some(find_debug_file(path, link, &mut debug_bytes)?),
+                    _ => None,
+                };
+                let debug_info = match opt.debug_info {
+                    Some(ref debug_info) => {
+                        let id = build_id(elf, bytes).ok_or_else(|| error::Error::Malformed("the binary has no build-id to find its debug info with".into()))?;
+                        Some(find_build_id_file(debug_info, id, &mut debug_bytes)?)
+                    },
+                    None => None,
+                };
+                let debug_elf = match (&debug_lookup, &debug_info) {
+                    (_, &Some(_)) | (&Some(DebugLookup::Found(_)), _) => Some(parse_elf(&debug_bytes)?),
+                    _ => None,
+                };
+                let libraries = if opt.find_libraries { find_libraries(opt, elf, path) } else { elf.libraries.iter().map(|_| LibraryLookup::NotFound).collect() };
+                let import_providers = if opt.resolve_imports { Some(import_providers(&elf.libraries, &libraries)) } else { None };
+                writeln!(out, "{}", Elf {elf, opt: opt.clone(), bytes, libraries, import_providers, debug_lookup, debug_info, debug_elf, known_interpreters: known_interpreters(opt)?})?;
+                if let Some(overlay) = overlay {
+                    writeln!(out, "{}", overlay)?;
+                }
+            }
+        },
+        Binary::Pe(ref pe) => {
+            let layout = Layout::new(opt, pe.is_64);
+            if opt.section_gaps {
+                write!(out, "{}", Gaps::pe(pe, bytes, opt))?;
+                return Ok(());
+            }
+            writeln!(out, "pe: {:#?}", pe)?;
+            if !opt.debug {
+                if let Some(rich) = rich::parse(bytes, pe)? {
+                    writeln!(out, "{}", PeRich(rich, bytes, opt.clone(), layout))?;
+                }
+                let imports = pe_import::parse(bytes, pe)?;
+                if opt.shows(imports.len()) {
+                    writeln!(out, "{}", PeImports(imports, opt.clone(), layout))?;
+                }
+                if opt.shows(pe.exports.len()) {
+                    writeln!(out, "{}", PeExports(pe, bytes, opt.clone()))?;
+                }
+                if let Some(resources) = resource::parse(bytes, pe)? {
+                    writeln!(out, "{}", PeResources(resources, bytes, opt.clone(), layout))?;
+                }
+                if let Some(entries) = pe_debug::parse(bytes, pe)? {
+                    writeln!(out, "{}", PeDebug(entries, opt.clone(), layout))?;
+                }
+                if let Some(clr) = clr::parse(bytes, pe)? {
+                    writeln!(out, "{}", Clr(clr, opt.clone(), layout))?;
+                }
+            }
+            if let Some(overlay) = Overlay::new(bytes, pe_extent(pe), layout) {
+                writeln!(out, "{}", overlay)?;
+            }
+        },
+        Binary::MachFat(ref arches) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", mach::Mach::parse(bytes)?)?;
+            } else {
+                for &(_, data, ref binary) in arches {
+                    match *binary {
+                        Ok(ref binary) => {
+                            writeln!(out, "{}", MachO(binary, opt.clone(), data))?;
+                        },
+                        Err(ref err) => {
+                            writeln!(out, "{}", err)?;
+                        }
+                    }
+                }
+                let extent = arches.iter().fold(0, |extent, (arch, _, _)| ::std::cmp::max(extent, arch.offset as u64 + arch.size as u64));
+                if let Some(overlay) = Overlay::new(bytes, extent, Layout::new(opt, true)) {
+                    writeln!(out, "{}", overlay)?;
+                }
+            }
+        },
+        Binary::Mach(ref mach) => {
+            if opt.debug {
+                writeln!(out, "{:#?}", mach)?;
+            } else {
+                let overlay = Overlay::new(bytes, mach_extent(mach), Layout::new(opt, mach.is_64));
+                writeln!(out, "{}", MachO(mach, opt.clone(), bytes))?;
+                if let Some(overlay) = overlay {
+                    writeln!(out, "{}", overlay)?;
+                }
+            }
+        },
+        Binary::Archive(ref members) => {
+            if opt.debug {
+                let archive = archive::Archive::parse(bytes)?;
+                writeln!(out, "archive: {:#?}", &archive)?;
+            } else {
+                writeln!(out, "{}", Archive(members, opt.clone()))?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// How long to wait for a file being atomically replaced to reappear, and for a burst of write events to settle
+const WATCH_SETTLE_MS: u64 = 100;
+
+fn watch (opt: Opt) -> error::Result<()> {
+    use notify::{Watcher, RecursiveMode, EventKind};
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+    use std::thread::sleep;
+    let notify_err = |err: notify::Error| error::Error::IO(::std::io::Error::other(err.to_string()));
+
+    let path = Path::new(&opt.input).to_path_buf();
+    // watch the parent directory, since build tools often replace the file rather than write to it, which drops a watch on the file itself
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => Path::new(".").to_path_buf(),
+    };
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(&notify_err)?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive).map_err(&notify_err)?;
+    loop {
+        while !path.exists() {
+            sleep(Duration::from_millis(WATCH_SETTLE_MS));
+        }
+        if opt.output_file().is_none() {
+            print!("\x1b[2J\x1b[H");
+        }
+        if let Err(err) = run(opt.clone()) {
+            eprintln!("{:#}", err);
+        }
+        loop {
+            match rx.recv() {
+                Ok(Ok(event)) => {
+                    let ours = event.paths.iter().any(|changed| changed.file_name() == path.file_name());
+                    match event.kind {
+                        EventKind::Create(_) | EventKind::Modify(_) if ours => break,
+                        _ => (),
+                    }
+                },
+                Ok(Err(err)) => return Err(notify_err(err)),
+                Err(_) => return Ok(()),
+            }
+        }
+        sleep(Duration::from_millis(WATCH_SETTLE_MS));
+        while rx.try_recv().is_ok() {}
+    }
+}
+
+/// Exit codes, so that scripts can tell why bingrep failed; each one means a single thing, and 1 is left to clap,
+/// which exits with it when the command line is wrong
+const EXIT_IO_ERROR: i32 = 2;
+const EXIT_UNKNOWN_MAGIC: i32 = 3;
+/// `--offsets-only` found nothing
+const EXIT_NO_MATCH: i32 = 4;
+const EXIT_PARSE_ERROR: i32 = 5;
+/// `--diff` found differences
+const EXIT_DIFFERENT: i32 = 6;
+/// `--pie-check` found a binary that isn't position independent
+const EXIT_NOT_PIE: i32 = 7;
+
+fn exit_code (err: &error::Error) -> i32 {
+    match *err {
+        error::Error::IO(_) => EXIT_IO_ERROR,
+        error::Error::BadMagic(_) => EXIT_UNKNOWN_MAGIC,
+        _ => EXIT_PARSE_ERROR,
+    }
+}
+
+/// The options every fuzz input is printed with: the default output, and each mode that prints something else
+#[cfg(any(test, feature = "fuzzing"))]
+const FUZZ_ARGS: [&[&str]; 17] = [
+    &[],
+    &["--pretty"],
+    &["--debug"],
+    &["--got"],
+    &["--anomalies"],
+    &["--section-gaps"],
+    &["--alignment-waste"],
+    &["--dwarf"],
+    &["--bind-opcodes", "--rebase-opcodes"],
+    &["--csv-sections"],
+    &["--emit-map"],
+    &["--functions-only"],
+    &["--strings", "--min", "2"],
+    &["--find-function", "55 ?? 89"],
+    &["--carve"],
+    &["--search", "a", "--at", "0x40"],
+    &["--xrefs", "*", "--demangle"],
+];
+
+/// Prints `data` with each of the fuzz options, for the target `cargo fuzz run display` builds in fuzz/; an error is
+/// fine, a panic or an abort is a bug
+#[cfg(feature = "fuzzing")]
+pub fn fuzz (data: &[u8]) {
+    colored::control::set_override(false);
+    for args in FUZZ_ARGS.iter() {
+        let args = Some("bingrep").into_iter().chain(args.iter().cloned()).chain(Some("fuzz"));
+        let mut opt = Opt::new(Args::from_clap(Args::clap().get_matches_from(args)));
+        opt.find_libraries = false;
+        let _ = print(&opt, data, &mut ::std::io::sink());
+    }
+}
+
+/// Runs the mode the options ask for
+fn dispatch (opt: Opt) -> error::Result<()> {
+    if opt.watch && (opt.offsets_only || opt.diff || opt.pie_check || opt.diff_syms) {
+        return Err(error::Error::Malformed("--watch only re-prints the binary, it can't be combined with --offsets-only, --diff, --pie-check, or --diff-syms".into()));
+    }
+    if opt.offsets_only {
+        if !offsets_only(&opt)? {
+            ::std::process::exit(EXIT_NO_MATCH);
+        }
+        Ok(())
+    } else if opt.diff {
+        if diff(&opt)? {
+            ::std::process::exit(EXIT_DIFFERENT);
+        }
+        Ok(())
+    } else if opt.pie_check {
+        if !pie_check(&opt)? {
+            ::std::process::exit(EXIT_NOT_PIE);
+        }
+        Ok(())
+    } else if opt.diff_syms {
+        diff_syms(&opt)
+    } else if opt.watch {
+        watch(opt)
+    } else {
+        run(opt)
+    }
+}
+
+/// Runs bingrep with the command line of the process
+pub fn main () {
+    let mut opt = Opt::new(Args::from_args());
+    if opt.output_file().is_some() && !opt.color {
+        colored::control::set_override(false);
+    }
+    opt.theme.color_headers = !opt.no_color_headers;
+    match dispatch(opt) {
+        Ok(()) => (),
+        Err(err) => {
+            eprintln!("{:#}", err);
+            ::std::process::exit(exit_code(&err));
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Mach-o `nlist` symbol table entries, as pointed to by `LC_SYMTAB`.
+//!
+//! goblin keeps the fields of its `Nlist` private, so the entries are read here.
+
+use goblin::error;
+use goblin::mach;
+use goblin::mach::load_command::CommandVariant;
+use scroll::{self, Pread};
+
+/// Set for stabs debugging entries
+pub const N_STAB: u8 = 0xe0;
+pub const N_TYPE: u8 = 0x0e;
+pub const N_EXT: u8 = 0x01;
+
+pub const N_UNDF: u8 = 0x0;
+pub const N_ABS: u8 = 0x2;
+pub const N_SECT: u8 = 0xe;
+pub const N_PBUD: u8 = 0xc;
+pub const N_INDR: u8 = 0xa;
+
+// the stabs types, from <mach-o/stab.h>; the whole `n_type` of a stabs entry is its type
+pub const N_GSYM: u8 = 0x20;
+pub const N_FNAME: u8 = 0x22;
+pub const N_FUN: u8 = 0x24;
+pub const N_STSYM: u8 = 0x26;
+pub const N_LCSYM: u8 = 0x28;
+pub const N_BNSYM: u8 = 0x2e;
+pub const N_AST: u8 = 0x32;
+pub const N_OPT: u8 = 0x3c;
+pub const N_RSYM: u8 = 0x40;
+pub const N_SLINE: u8 = 0x44;
+pub const N_ENSYM: u8 = 0x4e;
+pub const N_SSYM: u8 = 0x60;
+pub const N_SO: u8 = 0x64;
+pub const N_OSO: u8 = 0x66;
+pub const N_LSYM: u8 = 0x80;
+pub const N_BINCL: u8 = 0x82;
+pub const N_SOL: u8 = 0x84;
+pub const N_PARAMS: u8 = 0x86;
+pub const N_VERSION: u8 = 0x88;
+pub const N_OLEVEL: u8 = 0x8a;
+pub const N_PSYM: u8 = 0xa0;
+pub const N_EINCL: u8 = 0xa2;
+pub const N_ENTRY: u8 = 0xa4;
+pub const N_LBRAC: u8 = 0xc0;
+pub const N_EXCL: u8 = 0xc2;
+pub const N_RBRAC: u8 = 0xe0;
+pub const N_BCOMM: u8 = 0xe2;
+pub const N_ECOMM: u8 = 0xe4;
+pub const N_ECOML: u8 = 0xe8;
+pub const N_LENG: u8 = 0xfe;
+
+pub fn stab_to_str(n_type: u8) -> &'static str {
+    match n_type {
+        N_GSYM => "N_GSYM",
+        N_FNAME => "N_FNAME",
+        N_FUN => "N_FUN",
+        N_STSYM => "N_STSYM",
+        N_LCSYM => "N_LCSYM",
+        N_BNSYM => "N_BNSYM",
+        N_AST => "N_AST",
+        N_OPT => "N_OPT",
+        N_RSYM => "N_RSYM",
+        N_SLINE => "N_SLINE",
+        N_ENSYM => "N_ENSYM",
+        N_SSYM => "N_SSYM",
+        N_SO => "N_SO",
+        N_OSO => "N_OSO",
+        N_LSYM => "N_LSYM",
+        N_BINCL => "N_BINCL",
+        N_SOL => "N_SOL",
+        N_PARAMS => "N_PARAMS",
+        N_VERSION => "N_VERSION",
+        N_OLEVEL => "N_OLEVEL",
+        N_PSYM => "N_PSYM",
+        N_EINCL => "N_EINCL",
+        N_ENTRY => "N_ENTRY",
+        N_LBRAC => "N_LBRAC",
+        N_EXCL => "N_EXCL",
+        N_RBRAC => "N_RBRAC",
+        N_BCOMM => "N_BCOMM",
+        N_ECOMM => "N_ECOMM",
+        N_ECOML => "N_ECOML",
+        N_LENG => "N_LENG",
+        _ => "UNKNOWN",
+    }
+}
+
+pub fn n_type_to_str(n_type: u8) -> &'static str {
+    match n_type & N_TYPE {
+        N_UNDF => "UNDF",
+        N_ABS => "ABS",
+        N_SECT => "SECT",
+        N_PBUD => "PBUD",
+        N_INDR => "INDR",
+        _ => "UNKNOWN",
+    }
+}
+
+#[derive(Debug)]
+pub struct Nlist<'a> {
+    pub name: &'a str,
+    pub n_type: u8,
+    pub n_sect: u8,
+    pub n_desc: u16,
+    pub n_value: u64,
+}
+
+impl<'a> Nlist<'a> {
+    pub fn is_stab(&self) -> bool {
+        self.n_type & N_STAB != 0
+    }
+
+    pub fn is_extern(&self) -> bool {
+        self.n_type & N_EXT != 0
+    }
+}
+
+/// Parses the symbol table of `mach`, whose bytes are `bytes`; binaries without an `LC_SYMTAB` have no symbols
+pub fn parse<'a>(bytes: &'a [u8], mach: &mach::MachO) -> error::Result<Vec<Nlist<'a>>> {
+    let symtab = mach.load_commands.iter().filter_map(|lc| match lc.command {
+        CommandVariant::Symtab(symtab) => Some(symtab),
+        _ => None,
+    }).next();
+    let symtab = match symtab {
+        Some(symtab) => symtab,
+        None => return Ok(Vec::new()),
+    };
+    let le = scroll::Endian::from(mach.little_endian);
+    let size = if mach.is_64 { 16 } else { 12 };
+    if (symtab.nsyms as usize).saturating_mul(size).saturating_add(symtab.symoff as usize) > bytes.len() {
+        return Err(error::Error::Malformed(format!("{:#x} symbols at {:#x} don't fit in the file", symtab.nsyms, symtab.symoff)));
+    }
+    let mut symbols = Vec::with_capacity(symtab.nsyms as usize);
+    for i in 0..symtab.nsyms as usize {
+        let offset = symtab.symoff as usize + i * size;
+        let n_strx = bytes.pread_with::<u32>(offset, le)? as usize;
+        symbols.push(Nlist {
+            name: bytes.pread(symtab.stroff as usize + n_strx).unwrap_or(""),
+            n_type: bytes.pread(offset + 4)?,
+            n_sect: bytes.pread(offset + 5)?,
+            n_desc: bytes.pread_with(offset + 6, le)?,
+            n_value: if mach.is_64 { bytes.pread_with(offset + 8, le)? } else { bytes.pread_with::<u32>(offset + 8, le)? as u64 },
+        });
+    }
+    Ok(symbols)
+}
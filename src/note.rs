@@ -0,0 +1,218 @@
+//! ELF notes, and the GNU program properties carried in `.note.gnu.property`.
+
+use goblin::error;
+use goblin::elf::header;
+use scroll::{self, Pread};
+
+/// The owner of GNU notes
+pub const NOTE_GNU: &str = "GNU";
+
+pub const NT_GNU_ABI_TAG: u32 = 1;
+pub const NT_GNU_BUILD_ID: u32 = 3;
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+pub const GNU_PROPERTY_1_NEEDED: u32 = 0xb0008000;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc0000000;
+pub const GNU_PROPERTY_AARCH64_FEATURE_PAUTH: u32 = 0xc0000001;
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+pub const GNU_PROPERTY_X86_ISA_1_NEEDED: u32 = 0xc0008002;
+pub const GNU_PROPERTY_X86_FEATURE_2_NEEDED: u32 = 0xc0008001;
+pub const GNU_PROPERTY_X86_ISA_1_USED: u32 = 0xc0010002;
+pub const GNU_PROPERTY_X86_FEATURE_2_USED: u32 = 0xc0010001;
+
+pub const GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS: u32 = 1 << 0;
+
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 1 << 0;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: u32 = 1 << 1;
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_GCS: u32 = 1 << 2;
+
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 1 << 0;
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 1 << 1;
+pub const GNU_PROPERTY_X86_FEATURE_1_LAM_U48: u32 = 1 << 2;
+pub const GNU_PROPERTY_X86_FEATURE_1_LAM_U57: u32 = 1 << 3;
+
+pub const GNU_PROPERTY_X86_ISA_1_BASELINE: u32 = 1 << 0;
+pub const GNU_PROPERTY_X86_ISA_1_V2: u32 = 1 << 1;
+pub const GNU_PROPERTY_X86_ISA_1_V3: u32 = 1 << 2;
+pub const GNU_PROPERTY_X86_ISA_1_V4: u32 = 1 << 3;
+
+pub const GNU_PROPERTY_X86_FEATURE_2_X86: u32 = 1 << 0;
+pub const GNU_PROPERTY_X86_FEATURE_2_X87: u32 = 1 << 1;
+pub const GNU_PROPERTY_X86_FEATURE_2_MMX: u32 = 1 << 2;
+pub const GNU_PROPERTY_X86_FEATURE_2_XMM: u32 = 1 << 3;
+pub const GNU_PROPERTY_X86_FEATURE_2_YMM: u32 = 1 << 4;
+pub const GNU_PROPERTY_X86_FEATURE_2_ZMM: u32 = 1 << 5;
+pub const GNU_PROPERTY_X86_FEATURE_2_FXSR: u32 = 1 << 6;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVE: u32 = 1 << 7;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVEOPT: u32 = 1 << 8;
+pub const GNU_PROPERTY_X86_FEATURE_2_XSAVEC: u32 = 1 << 9;
+pub const GNU_PROPERTY_X86_FEATURE_2_TMM: u32 = 1 << 10;
+pub const GNU_PROPERTY_X86_FEATURE_2_MASK: u32 = 1 << 11;
+
+#[derive(Debug)]
+pub struct Note<'a> {
+    pub n_type: u32,
+    /// The owner, e.g. `GNU`
+    pub name: &'a str,
+    pub desc: &'a [u8],
+}
+
+fn align(n: usize, alignment: usize) -> usize {
+    (n + alignment - 1) & !(alignment - 1)
+}
+
+/// Parses the notes in `bytes`, the contents of a SHT_NOTE section or PT_NOTE segment aligned to `alignment`
+pub fn parse<'a>(bytes: &'a [u8], alignment: u64, little_endian: bool) -> error::Result<Vec<Note<'a>>> {
+    let le = scroll::Endian::from(little_endian);
+    // notes are 4 byte aligned, except for 8 byte aligned ones like GNU properties on 64-bit
+    let alignment = if alignment == 8 { 8 } else { 4 };
+    let mut notes = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let namesz = bytes.pread_with::<u32>(offset, le)? as usize;
+        let descsz = bytes.pread_with::<u32>(offset + 4, le)? as usize;
+        let n_type = bytes.pread_with::<u32>(offset + 8, le)?;
+        let name_offset = offset + 12;
+        let desc_offset = name_offset.saturating_add(align(namesz, 4));
+        let name = bytes.get(name_offset..name_offset.saturating_add(namesz))
+            .ok_or_else(|| error::Error::Malformed(format!("note name at {:#x} of size {:#x} is out of bounds", name_offset, namesz)))?;
+        let desc = bytes.get(desc_offset..desc_offset.saturating_add(descsz))
+            .ok_or_else(|| error::Error::Malformed(format!("note desc at {:#x} of size {:#x} is out of bounds", desc_offset, descsz)))?;
+        // the name includes its NUL terminator
+        let name = ::std::str::from_utf8(name).unwrap_or("").trim_end_matches('\0');
+        notes.push(Note { n_type, name, desc });
+        offset = align(desc_offset + descsz, alignment);
+    }
+    Ok(notes)
+}
+
+pub const ELF_NOTE_OS_LINUX: u32 = 0;
+pub const ELF_NOTE_OS_GNU: u32 = 1;
+pub const ELF_NOTE_OS_SOLARIS2: u32 = 2;
+pub const ELF_NOTE_OS_FREEBSD: u32 = 3;
+
+/// The oldest kernel a binary runs on, from its `NT_GNU_ABI_TAG` note
+#[derive(Debug)]
+pub struct AbiTag {
+    pub os: u32,
+    /// The kernel version, e.g. `[3, 2, 0]`
+    pub version: [u32; 3],
+}
+
+/// Parses the desc of a `NT_GNU_ABI_TAG` note
+pub fn abi_tag(desc: &[u8], little_endian: bool) -> error::Result<AbiTag> {
+    let le = scroll::Endian::from(little_endian);
+    Ok(AbiTag {
+        os: desc.pread_with(0, le)?,
+        version: [desc.pread_with(4, le)?, desc.pread_with(8, le)?, desc.pread_with(12, le)?],
+    })
+}
+
+pub fn abi_os_to_str(os: u32) -> &'static str {
+    match os {
+        ELF_NOTE_OS_LINUX => "Linux",
+        ELF_NOTE_OS_GNU => "Hurd",
+        ELF_NOTE_OS_SOLARIS2 => "Solaris",
+        ELF_NOTE_OS_FREEBSD => "FreeBSD",
+        _ => "unknown",
+    }
+}
+
+#[derive(Debug)]
+pub struct Property<'a> {
+    pub pr_type: u32,
+    pub data: &'a [u8],
+    /// The data as an integer, if it is 4 or 8 bytes; bitmasks are always 4 bytes
+    pub value: Option<u64>,
+}
+
+/// Parses the properties in the desc of a `NT_GNU_PROPERTY_TYPE_0` note
+pub fn properties<'a>(desc: &'a [u8], is_64: bool, little_endian: bool) -> error::Result<Vec<Property<'a>>> {
+    let le = scroll::Endian::from(little_endian);
+    let alignment = if is_64 { 8 } else { 4 };
+    let mut properties = Vec::new();
+    let mut offset = 0;
+    while offset < desc.len() {
+        let pr_type = desc.pread_with::<u32>(offset, le)?;
+        let datasz = desc.pread_with::<u32>(offset + 4, le)? as usize;
+        let data = desc.get(offset + 8..(offset + 8).saturating_add(datasz))
+            .ok_or_else(|| error::Error::Malformed(format!("property {:#x} of size {:#x} is out of bounds", pr_type, datasz)))?;
+        let value = match data.len() {
+            4 => Some(data.pread_with::<u32>(0, le)? as u64),
+            8 => Some(data.pread_with::<u64>(0, le)?),
+            _ => None,
+        };
+        properties.push(Property { pr_type, data, value });
+        offset = align(offset + 8 + datasz, alignment);
+    }
+    Ok(properties)
+}
+
+pub fn is_x86(machine: u16) -> bool {
+    machine == header::EM_X86_64 || machine == header::EM_386
+}
+
+/// The name of a property type; processor specific types depend on the `machine`
+pub fn property_to_str(pr_type: u32, machine: u16) -> &'static str {
+    match pr_type {
+        GNU_PROPERTY_STACK_SIZE => "STACK_SIZE",
+        GNU_PROPERTY_NO_COPY_ON_PROTECTED => "NO_COPY_ON_PROTECTED",
+        GNU_PROPERTY_1_NEEDED => "1_NEEDED",
+        GNU_PROPERTY_AARCH64_FEATURE_1_AND if machine == header::EM_AARCH64 => "AARCH64_FEATURE_1_AND",
+        GNU_PROPERTY_AARCH64_FEATURE_PAUTH if machine == header::EM_AARCH64 => "AARCH64_FEATURE_PAUTH",
+        GNU_PROPERTY_X86_FEATURE_1_AND if is_x86(machine) => "X86_FEATURE_1_AND",
+        GNU_PROPERTY_X86_ISA_1_NEEDED if is_x86(machine) => "X86_ISA_1_NEEDED",
+        GNU_PROPERTY_X86_ISA_1_USED if is_x86(machine) => "X86_ISA_1_USED",
+        GNU_PROPERTY_X86_FEATURE_2_NEEDED if is_x86(machine) => "X86_FEATURE_2_NEEDED",
+        GNU_PROPERTY_X86_FEATURE_2_USED if is_x86(machine) => "X86_FEATURE_2_USED",
+        _ => "UNKNOWN",
+    }
+}
+
+/// The names of the bits of a bitmask property, or an empty slice if the property isn't a bitmask
+pub fn property_bits(pr_type: u32, machine: u16) -> &'static [(u32, &'static str)] {
+    const NEEDED_1: [(u32, &str); 1] = [
+        (GNU_PROPERTY_1_NEEDED_INDIRECT_EXTERN_ACCESS, "INDIRECT_EXTERN_ACCESS"),
+    ];
+    const AARCH64_FEATURE_1: [(u32, &str); 3] = [
+        (GNU_PROPERTY_AARCH64_FEATURE_1_BTI, "BTI"),
+        (GNU_PROPERTY_AARCH64_FEATURE_1_PAC, "PAC"),
+        (GNU_PROPERTY_AARCH64_FEATURE_1_GCS, "GCS"),
+    ];
+    const X86_FEATURE_1: [(u32, &str); 4] = [
+        (GNU_PROPERTY_X86_FEATURE_1_IBT, "IBT"),
+        (GNU_PROPERTY_X86_FEATURE_1_SHSTK, "SHSTK"),
+        (GNU_PROPERTY_X86_FEATURE_1_LAM_U48, "LAM_U48"),
+        (GNU_PROPERTY_X86_FEATURE_1_LAM_U57, "LAM_U57"),
+    ];
+    const X86_ISA_1: [(u32, &str); 4] = [
+        (GNU_PROPERTY_X86_ISA_1_BASELINE, "x86-64-baseline"),
+        (GNU_PROPERTY_X86_ISA_1_V2, "x86-64-v2"),
+        (GNU_PROPERTY_X86_ISA_1_V3, "x86-64-v3"),
+        (GNU_PROPERTY_X86_ISA_1_V4, "x86-64-v4"),
+    ];
+    const X86_FEATURE_2: [(u32, &str); 12] = [
+        (GNU_PROPERTY_X86_FEATURE_2_X86, "x86"),
+        (GNU_PROPERTY_X86_FEATURE_2_X87, "x87"),
+        (GNU_PROPERTY_X86_FEATURE_2_MMX, "MMX"),
+        (GNU_PROPERTY_X86_FEATURE_2_XMM, "XMM"),
+        (GNU_PROPERTY_X86_FEATURE_2_YMM, "YMM"),
+        (GNU_PROPERTY_X86_FEATURE_2_ZMM, "ZMM"),
+        (GNU_PROPERTY_X86_FEATURE_2_FXSR, "FXSR"),
+        (GNU_PROPERTY_X86_FEATURE_2_XSAVE, "XSAVE"),
+        (GNU_PROPERTY_X86_FEATURE_2_XSAVEOPT, "XSAVEOPT"),
+        (GNU_PROPERTY_X86_FEATURE_2_XSAVEC, "XSAVEC"),
+        (GNU_PROPERTY_X86_FEATURE_2_TMM, "TMM"),
+        (GNU_PROPERTY_X86_FEATURE_2_MASK, "MASK"),
+    ];
+    match pr_type {
+        GNU_PROPERTY_1_NEEDED => &NEEDED_1,
+        GNU_PROPERTY_AARCH64_FEATURE_1_AND if machine == header::EM_AARCH64 => &AARCH64_FEATURE_1,
+        GNU_PROPERTY_X86_FEATURE_1_AND if is_x86(machine) => &X86_FEATURE_1,
+        GNU_PROPERTY_X86_ISA_1_NEEDED | GNU_PROPERTY_X86_ISA_1_USED if is_x86(machine) => &X86_ISA_1,
+        GNU_PROPERTY_X86_FEATURE_2_NEEDED | GNU_PROPERTY_X86_FEATURE_2_USED if is_x86(machine) => &X86_FEATURE_2,
+        _ => &[],
+    }
+}
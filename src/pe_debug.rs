@@ -0,0 +1,213 @@
+//! The debug directory of PE binaries, which ties them to their PDBs.
+//!
+//! Each entry is an IMAGE_DEBUG_DIRECTORY; the CodeView, reproducible, and POGO entries have their data decoded.
+
+use goblin::error;
+use goblin::pe;
+use scroll::{self, Pread, Gread};
+use clr::find_offset;
+
+/// The size of an IMAGE_DEBUG_DIRECTORY
+pub const SIZEOF_DEBUG_DIRECTORY: usize = 28;
+
+pub const IMAGE_DEBUG_TYPE_UNKNOWN: u32 = 0;
+pub const IMAGE_DEBUG_TYPE_COFF: u32 = 1;
+pub const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+pub const IMAGE_DEBUG_TYPE_FPO: u32 = 3;
+pub const IMAGE_DEBUG_TYPE_MISC: u32 = 4;
+pub const IMAGE_DEBUG_TYPE_EXCEPTION: u32 = 5;
+pub const IMAGE_DEBUG_TYPE_FIXUP: u32 = 6;
+pub const IMAGE_DEBUG_TYPE_OMAP_TO_SRC: u32 = 7;
+pub const IMAGE_DEBUG_TYPE_OMAP_FROM_SRC: u32 = 8;
+pub const IMAGE_DEBUG_TYPE_BORLAND: u32 = 9;
+pub const IMAGE_DEBUG_TYPE_CLSID: u32 = 11;
+pub const IMAGE_DEBUG_TYPE_VC_FEATURE: u32 = 12;
+pub const IMAGE_DEBUG_TYPE_POGO: u32 = 13;
+pub const IMAGE_DEBUG_TYPE_ILTCG: u32 = 14;
+pub const IMAGE_DEBUG_TYPE_MPX: u32 = 15;
+pub const IMAGE_DEBUG_TYPE_REPRO: u32 = 16;
+pub const IMAGE_DEBUG_TYPE_EX_DLLCHARACTERISTICS: u32 = 20;
+
+pub fn type_to_str(typ: u32) -> &'static str {
+    match typ {
+        IMAGE_DEBUG_TYPE_UNKNOWN => "UNKNOWN",
+        IMAGE_DEBUG_TYPE_COFF => "COFF",
+        IMAGE_DEBUG_TYPE_CODEVIEW => "CODEVIEW",
+        IMAGE_DEBUG_TYPE_FPO => "FPO",
+        IMAGE_DEBUG_TYPE_MISC => "MISC",
+        IMAGE_DEBUG_TYPE_EXCEPTION => "EXCEPTION",
+        IMAGE_DEBUG_TYPE_FIXUP => "FIXUP",
+        IMAGE_DEBUG_TYPE_OMAP_TO_SRC => "OMAP_TO_SRC",
+        IMAGE_DEBUG_TYPE_OMAP_FROM_SRC => "OMAP_FROM_SRC",
+        IMAGE_DEBUG_TYPE_BORLAND => "BORLAND",
+        IMAGE_DEBUG_TYPE_CLSID => "CLSID",
+        IMAGE_DEBUG_TYPE_VC_FEATURE => "VC_FEATURE",
+        IMAGE_DEBUG_TYPE_POGO => "POGO",
+        IMAGE_DEBUG_TYPE_ILTCG => "ILTCG",
+        IMAGE_DEBUG_TYPE_MPX => "MPX",
+        IMAGE_DEBUG_TYPE_REPRO => "REPRO",
+        IMAGE_DEBUG_TYPE_EX_DLLCHARACTERISTICS => "EX_DLLCHARACTERISTICS",
+        _ => "UNKNOWN_TYPE",
+    }
+}
+
+/// The CodeView signature of PDB 7.0 files, `RSDS`
+pub const CV_SIGNATURE_RSDS: u32 = 0x53445352;
+/// The CodeView signature of PDB 2.0 files, `NB10`
+pub const CV_SIGNATURE_NB10: u32 = 0x3031424e;
+
+/// Where the PDB is, and what identifies the one that matches
+#[derive(Debug)]
+pub enum CodeView<'a> {
+    /// A PDB 7.0, matched by its GUID and age
+    Rsds { guid: [u8; 16], age: u32, path: &'a str },
+    /// A PDB 2.0, matched by its timestamp and age
+    Nb10 { offset: u32, timestamp: u32, age: u32, path: &'a str },
+}
+
+/// A section contribution recorded for profile guided optimization, e.g. `.text$mn`
+#[derive(Debug)]
+pub struct PogoEntry<'a> {
+    pub rva: u32,
+    pub size: u32,
+    pub name: &'a str,
+}
+
+#[derive(Debug)]
+pub struct Pogo<'a> {
+    /// `LTCG`, `PGI`, `PGO`, or `PGU`, by how the binary was built
+    pub signature: u32,
+    pub entries: Vec<PogoEntry<'a>>,
+}
+
+/// The decoded data of an entry
+#[derive(Debug)]
+pub enum Data<'a> {
+    CodeView(CodeView<'a>),
+    /// The hash that replaces the timestamps of a reproducible build, which may be empty
+    Repro(&'a [u8]),
+    Pogo(Pogo<'a>),
+    None,
+}
+
+#[derive(Debug)]
+pub struct Entry<'a> {
+    pub characteristics: u32,
+    pub time_date_stamp: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub typ: u32,
+    pub size_of_data: u32,
+    pub address_of_raw_data: u32,
+    pub pointer_to_raw_data: u32,
+    /// A bad entry's data shouldn't prevent showing the rest
+    pub data: error::Result<Data<'a>>,
+}
+
+/// Formats a GUID as Windows does, `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}`; the first three fields are little endian
+pub fn guid_to_string(guid: &[u8; 16]) -> String {
+    format!("{{{:02X}{:02X}{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+            guid[3], guid[2], guid[1], guid[0], guid[5], guid[4], guid[7], guid[6],
+            guid[8], guid[9], guid[10], guid[11], guid[12], guid[13], guid[14], guid[15])
+}
+
+fn parse_codeview<'a>(data: &'a [u8]) -> error::Result<CodeView<'a>> {
+    let signature = data.pread_with::<u32>(0, scroll::LE)?;
+    match signature {
+        CV_SIGNATURE_RSDS => {
+            let mut guid = [0; 16];
+            guid.copy_from_slice(data.get(4..20).ok_or_else(|| error::Error::Malformed("the RSDS CodeView record is too short for its GUID".into()))?);
+            Ok(CodeView::Rsds {
+                guid,
+                age: data.pread_with(20, scroll::LE)?,
+                path: data.pread::<&str>(24)?,
+            })
+        },
+        CV_SIGNATURE_NB10 => Ok(CodeView::Nb10 {
+            offset: data.pread_with(4, scroll::LE)?,
+            timestamp: data.pread_with(8, scroll::LE)?,
+            age: data.pread_with(12, scroll::LE)?,
+            path: data.pread::<&str>(16)?,
+        }),
+        _ => Err(error::Error::BadMagic(signature as u64)),
+    }
+}
+
+/// Rounds `n` up to the next multiple of 4
+fn align4(n: usize) -> usize {
+    (n + 3) & !3
+}
+
+fn parse_pogo<'a>(data: &'a [u8]) -> error::Result<Pogo<'a>> {
+    let mut offset = 0;
+    let signature = data.gread_with(&mut offset, scroll::LE)?;
+    let mut entries = Vec::new();
+    // each name is NUL terminated and padded to 4 bytes
+    while offset + 8 < data.len() {
+        let rva = data.gread_with(&mut offset, scroll::LE)?;
+        let size = data.gread_with(&mut offset, scroll::LE)?;
+        let name = data.pread::<&str>(offset)?;
+        offset += align4(name.len() + 1);
+        entries.push(PogoEntry { rva, size, name });
+    }
+    Ok(Pogo { signature, entries })
+}
+
+fn parse_data<'a>(bytes: &'a [u8], typ: u32, offset: u32, size: u32) -> error::Result<Data<'a>> {
+    if typ != IMAGE_DEBUG_TYPE_CODEVIEW && typ != IMAGE_DEBUG_TYPE_REPRO && typ != IMAGE_DEBUG_TYPE_POGO {
+        return Ok(Data::None);
+    }
+    let data = bytes.get(offset as usize..(offset as usize).saturating_add(size as usize))
+        .ok_or_else(|| error::Error::Malformed(format!("debug data at {:#x} of size {:#x} is past the end of the file", offset, size)))?;
+    Ok(match typ {
+        IMAGE_DEBUG_TYPE_CODEVIEW => Data::CodeView(parse_codeview(data)?),
+        // a length, then the hash
+        IMAGE_DEBUG_TYPE_REPRO if data.len() >= 4 => {
+            let length = data.pread_with::<u32>(0, scroll::LE)? as usize;
+            Data::Repro(&data[4..::std::cmp::min(data.len(), 4usize.saturating_add(length))])
+        },
+        IMAGE_DEBUG_TYPE_REPRO => Data::Repro(&[]),
+        _ => Data::Pogo(parse_pogo(data)?),
+    })
+}
+
+/// Parses the entries of the debug directory, if the PE has one
+pub fn parse<'a>(bytes: &'a [u8], pe: &pe::PE) -> error::Result<Option<Vec<Entry<'a>>>> {
+    let directory = match pe.header.optional_header {
+        Some(ref optional_header) => match *optional_header.data_directories.get_debug_table() {
+            Some(directory) if directory.size != 0 => directory,
+            _ => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+    let mut offset = find_offset(directory.virtual_address, &pe.sections)
+        .ok_or_else(|| error::Error::Malformed(format!("debug directory rva {:#x} is not in any section", directory.virtual_address)))?;
+    let count = directory.size as usize / SIZEOF_DEBUG_DIRECTORY;
+    if count.saturating_mul(SIZEOF_DEBUG_DIRECTORY) > bytes.len() {
+        return Err(error::Error::Malformed(format!("debug directory of size {:#x} can't fit in the file", directory.size)));
+    }
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let offset = &mut offset;
+        let characteristics = bytes.gread_with(offset, scroll::LE)?;
+        let time_date_stamp = bytes.gread_with(offset, scroll::LE)?;
+        let major_version = bytes.gread_with(offset, scroll::LE)?;
+        let minor_version = bytes.gread_with(offset, scroll::LE)?;
+        let typ = bytes.gread_with(offset, scroll::LE)?;
+        let size_of_data = bytes.gread_with(offset, scroll::LE)?;
+        let address_of_raw_data = bytes.gread_with(offset, scroll::LE)?;
+        let pointer_to_raw_data = bytes.gread_with(offset, scroll::LE)?;
+        entries.push(Entry {
+            characteristics,
+            time_date_stamp,
+            major_version,
+            minor_version,
+            typ,
+            size_of_data,
+            address_of_raw_data,
+            pointer_to_raw_data,
+            data: parse_data(bytes, typ, pointer_to_raw_data, size_of_data),
+        });
+    }
+    Ok(Some(entries))
+}
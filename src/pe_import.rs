@@ -0,0 +1,219 @@
+//! The PE import directory: per DLL, the import lookup table (ILT) and the import address table (IAT).
+//!
+//! goblin reads lookup entries as 32-bit words even in PE32+ binaries, which stops each DLL after its
+//! first import, and panics on RVAs outside of every section, so the directory is walked here instead.
+
+use goblin::error;
+use goblin::pe;
+use scroll::{self, Pread};
+
+use clr::find_offset;
+
+pub const IMPORT_BY_ORDINAL_32: u64 = 0x8000_0000;
+pub const IMPORT_BY_ORDINAL_64: u64 = 0x8000_0000_0000_0000;
+pub const SIZEOF_IMPORT_DIRECTORY_ENTRY: usize = 20;
+
+/// How an import is looked up in its DLL
+#[derive(Debug)]
+pub enum Lookup<'a> {
+    /// By name, with a hint into the DLL's export name table
+    Name { hint: u16, name: &'a str },
+    /// By ordinal only; the entry has no name
+    Ordinal(u16),
+}
+
+#[derive(Debug)]
+pub struct Import<'a> {
+    pub lookup: Lookup<'a>,
+    /// The RVA of this import's IAT slot, which the loader overwrites with the function's address
+    pub iat: u32,
+    /// The RVA of this import's ILT entry, if the DLL has an ILT
+    pub ilt: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct Dll<'a> {
+    pub name: &'a str,
+    pub ilt: u32,
+    pub iat: u32,
+    pub time_date_stamp: u32,
+    pub imports: Vec<Import<'a>>,
+}
+
+fn offset_of(rva: u32, pe: &pe::PE, what: &str) -> error::Result<usize> {
+    find_offset(rva, &pe.sections).ok_or_else(|| error::Error::Malformed(format!("{} rva {:#x} is not in any section", what, rva)))
+}
+
+fn parse_lookup<'a>(bytes: &'a [u8], pe: &pe::PE, entry: u64) -> error::Result<Lookup<'a>> {
+    let by_ordinal = if pe.is_64 { IMPORT_BY_ORDINAL_64 } else { IMPORT_BY_ORDINAL_32 };
+    if entry & by_ordinal != 0 {
+        Ok(Lookup::Ordinal(entry as u16))
+    } else {
+        let offset = offset_of(entry as u32 & 0x7fff_ffff, pe, "hint/name")?;
+        Ok(Lookup::Name {
+            hint: bytes.pread_with(offset, scroll::LE)?,
+            name: bytes.pread(offset + 2)?,
+        })
+    }
+}
+
+/// The names pefile gives ordinal imports from the DLLs it has export tables for, which `imphash` must match
+fn ordinal_name(dll: &str, ordinal: u16) -> Option<&'static str> {
+    match dll {
+        "ws2_32.dll" | "wsock32.dll" => match ordinal {
+            1 => Some("accept"),
+            2 => Some("bind"),
+            3 => Some("closesocket"),
+            4 => Some("connect"),
+            5 => Some("getpeername"),
+            6 => Some("getsockname"),
+            7 => Some("getsockopt"),
+            8 => Some("htonl"),
+            9 => Some("htons"),
+            10 => Some("ioctlsocket"),
+            11 => Some("inet_addr"),
+            12 => Some("inet_ntoa"),
+            13 => Some("listen"),
+            14 => Some("ntohl"),
+            15 => Some("ntohs"),
+            16 => Some("recv"),
+            17 => Some("recvfrom"),
+            18 => Some("select"),
+            19 => Some("send"),
+            20 => Some("sendto"),
+            21 => Some("setsockopt"),
+            22 => Some("shutdown"),
+            23 => Some("socket"),
+            51 => Some("gethostbyaddr"),
+            52 => Some("gethostbyname"),
+            53 => Some("getprotobyname"),
+            54 => Some("getprotobynumber"),
+            55 => Some("getservbyname"),
+            56 => Some("getservbyport"),
+            57 => Some("gethostname"),
+            101 => Some("WSAAsyncSelect"),
+            102 => Some("WSAAsyncGetHostByAddr"),
+            103 => Some("WSAAsyncGetHostByName"),
+            104 => Some("WSAAsyncGetProtoByNumber"),
+            105 => Some("WSAAsyncGetProtoByName"),
+            106 => Some("WSAAsyncGetServByPort"),
+            107 => Some("WSAAsyncGetServByName"),
+            108 => Some("WSACancelAsyncRequest"),
+            109 => Some("WSASetBlockingHook"),
+            110 => Some("WSAUnhookBlockingHook"),
+            111 => Some("WSAGetLastError"),
+            112 => Some("WSASetLastError"),
+            113 => Some("WSACancelBlockingCall"),
+            114 => Some("WSAIsBlocking"),
+            115 => Some("WSAStartup"),
+            116 => Some("WSACleanup"),
+            151 => Some("__WSAFDIsSet"),
+            500 => Some("WEP"),
+            _ => None,
+        },
+        "oleaut32.dll" => match ordinal {
+            2 => Some("SysAllocString"),
+            3 => Some("SysReAllocString"),
+            4 => Some("SysAllocStringLen"),
+            5 => Some("SysReAllocStringLen"),
+            6 => Some("SysFreeString"),
+            7 => Some("SysStringLen"),
+            8 => Some("VariantInit"),
+            9 => Some("VariantClear"),
+            10 => Some("VariantCopy"),
+            11 => Some("VariantCopyInd"),
+            12 => Some("VariantChangeType"),
+            13 => Some("VariantTimeToDosDateTime"),
+            14 => Some("DosDateTimeToVariantTime"),
+            15 => Some("SafeArrayCreate"),
+            16 => Some("SafeArrayDestroy"),
+            17 => Some("SafeArrayGetDim"),
+            18 => Some("SafeArrayGetElemsize"),
+            19 => Some("SafeArrayGetUBound"),
+            20 => Some("SafeArrayGetLBound"),
+            21 => Some("SafeArrayLock"),
+            22 => Some("SafeArrayUnlock"),
+            23 => Some("SafeArrayAccessData"),
+            24 => Some("SafeArrayUnaccessData"),
+            25 => Some("SafeArrayGetElement"),
+            26 => Some("SafeArrayPutElement"),
+            27 => Some("SafeArrayCopy"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The imphash of the imports, as computed by pefile: the MD5 of the comma separated, lowercased `dll.function`s
+/// in import order, where `dll` loses a `.dll`, `.ocx`, or `.sys` extension, and unknown ordinals are `ordN`
+pub fn imphash(dlls: &[Dll]) -> String {
+    let mut imports = Vec::new();
+    for dll in dlls {
+        let name = dll.name.to_lowercase();
+        let library = match name.rfind('.') {
+            Some(dot) if ["dll", "ocx", "sys"].contains(&&name[dot + 1..]) => &name[..dot],
+            _ => &name[..],
+        };
+        for import in &dll.imports {
+            let function = match import.lookup {
+                Lookup::Name { name, .. } => name.to_owned(),
+                Lookup::Ordinal(ordinal) => match ordinal_name(&name, ordinal) {
+                    Some(function) => function.to_owned(),
+                    None => format!("ord{}", ordinal),
+                },
+            };
+            if !function.is_empty() {
+                imports.push(format!("{}.{}", library, function.to_lowercase()));
+            }
+        }
+    }
+    format!("{:x}", ::md5::compute(imports.join(",").as_bytes()))
+}
+
+/// Parses the import directory, if the PE has one
+pub fn parse<'a>(bytes: &'a [u8], pe: &pe::PE) -> error::Result<Vec<Dll<'a>>> {
+    let directory = match pe.header.optional_header {
+        Some(ref optional_header) => match *optional_header.data_directories.get_import_table() {
+            Some(directory) => directory,
+            None => return Ok(Vec::new()),
+        },
+        None => return Ok(Vec::new()),
+    };
+    let entry_size = if pe.is_64 { 8 } else { 4 };
+    let mut offset = offset_of(directory.virtual_address, pe, "import directory")?;
+    let mut dlls = Vec::new();
+    loop {
+        // OriginalFirstThunk, TimeDateStamp, ForwarderChain, Name, FirstThunk
+        let ilt = bytes.pread_with::<u32>(offset, scroll::LE)?;
+        let time_date_stamp = bytes.pread_with::<u32>(offset + 4, scroll::LE)?;
+        let name = bytes.pread_with::<u32>(offset + 12, scroll::LE)?;
+        let iat = bytes.pread_with::<u32>(offset + 16, scroll::LE)?;
+        if ilt == 0 && name == 0 && iat == 0 {
+            break;
+        }
+        offset += SIZEOF_IMPORT_DIRECTORY_ENTRY;
+        let name = bytes.pread::<&str>(offset_of(name, pe, "DLL name")?)?;
+        // old linkers leave out the ILT, in which case the unbound IAT holds the same entries
+        let table = if ilt != 0 { ilt } else { iat };
+        let table_offset = offset_of(table, pe, "import lookup table")?;
+        let mut imports = Vec::new();
+        for i in 0.. {
+            let entry = if pe.is_64 {
+                bytes.pread_with::<u64>(table_offset + i * entry_size, scroll::LE)?
+            } else {
+                bytes.pread_with::<u32>(table_offset + i * entry_size, scroll::LE)? as u64
+            };
+            if entry == 0 {
+                break;
+            }
+            let slot = (i * entry_size) as u32;
+            imports.push(Import {
+                lookup: parse_lookup(bytes, pe, entry)?,
+                iat: iat + slot,
+                ilt: if ilt != 0 { Some(ilt + slot) } else { None },
+            });
+        }
+        dlls.push(Dll { name, ilt, iat, time_date_stamp, imports });
+    }
+    Ok(dlls)
+}
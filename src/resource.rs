@@ -0,0 +1,162 @@
+//! The PE resource directory (`.rsrc`): a tree of type, name or ID, and language, whose leaves are data entries.
+
+use goblin::error;
+use goblin::pe;
+use scroll::{self, Pread};
+
+use clr::find_offset;
+
+pub const RT_CURSOR: u32 = 1;
+pub const RT_BITMAP: u32 = 2;
+pub const RT_ICON: u32 = 3;
+pub const RT_MENU: u32 = 4;
+pub const RT_DIALOG: u32 = 5;
+pub const RT_STRING: u32 = 6;
+pub const RT_FONTDIR: u32 = 7;
+pub const RT_FONT: u32 = 8;
+pub const RT_ACCELERATOR: u32 = 9;
+pub const RT_RCDATA: u32 = 10;
+pub const RT_MESSAGETABLE: u32 = 11;
+pub const RT_GROUP_CURSOR: u32 = 12;
+pub const RT_GROUP_ICON: u32 = 14;
+pub const RT_VERSION: u32 = 16;
+pub const RT_DLGINCLUDE: u32 = 17;
+pub const RT_PLUGPLAY: u32 = 19;
+pub const RT_VXD: u32 = 20;
+pub const RT_ANICURSOR: u32 = 21;
+pub const RT_ANIICON: u32 = 22;
+pub const RT_HTML: u32 = 23;
+pub const RT_MANIFEST: u32 = 24;
+
+pub fn rt_to_str(typ: u32) -> &'static str {
+    match typ {
+        RT_CURSOR => "RT_CURSOR",
+        RT_BITMAP => "RT_BITMAP",
+        RT_ICON => "RT_ICON",
+        RT_MENU => "RT_MENU",
+        RT_DIALOG => "RT_DIALOG",
+        RT_STRING => "RT_STRING",
+        RT_FONTDIR => "RT_FONTDIR",
+        RT_FONT => "RT_FONT",
+        RT_ACCELERATOR => "RT_ACCELERATOR",
+        RT_RCDATA => "RT_RCDATA",
+        RT_MESSAGETABLE => "RT_MESSAGETABLE",
+        RT_GROUP_CURSOR => "RT_GROUP_CURSOR",
+        RT_GROUP_ICON => "RT_GROUP_ICON",
+        RT_VERSION => "RT_VERSION",
+        RT_DLGINCLUDE => "RT_DLGINCLUDE",
+        RT_PLUGPLAY => "RT_PLUGPLAY",
+        RT_VXD => "RT_VXD",
+        RT_ANICURSOR => "RT_ANICURSOR",
+        RT_ANIICON => "RT_ANIICON",
+        RT_HTML => "RT_HTML",
+        RT_MANIFEST => "RT_MANIFEST",
+        _ => "UNKNOWN_RT",
+    }
+}
+
+/// The high bit of an entry's name, set when it is an offset to a name string rather than an ID
+const NAME_IS_STRING: u32 = 0x8000_0000;
+/// The high bit of an entry's offset, set when it points to a subdirectory rather than a data entry
+const DATA_IS_DIRECTORY: u32 = 0x8000_0000;
+const SIZEOF_RESOURCE_DIRECTORY: usize = 16;
+const SIZEOF_RESOURCE_DIRECTORY_ENTRY: usize = 8;
+/// The tree is type, name, and language; anything deeper is malformed, or a loop
+const MAX_DEPTH: usize = 3;
+
+#[derive(Debug)]
+pub enum Id {
+    Name(String),
+    Id(u32),
+}
+
+#[derive(Debug)]
+pub struct Data {
+    pub rva: u32,
+    pub size: u32,
+    pub codepage: u32,
+    /// The file offset of the data, if its RVA is inside a section
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum Node {
+    Directory(Vec<Entry>),
+    Data(Data),
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub id: Id,
+    pub node: Node,
+}
+
+impl Entry {
+    /// The number of data entries at or below this entry
+    pub fn leaves(&self) -> usize {
+        match self.node {
+            Node::Directory(ref entries) => entries.iter().map(Entry::leaves).sum(),
+            Node::Data(_) => 1,
+        }
+    }
+}
+
+/// Reads the length prefixed UTF-16 name at `offset`
+fn parse_name(bytes: &[u8], offset: usize) -> error::Result<String> {
+    let len = bytes.pread_with::<u16>(offset, scroll::LE)? as usize;
+    let mut units = Vec::with_capacity(len);
+    for i in 0..len {
+        units.push(bytes.pread_with::<u16>(offset + 2 + i * 2, scroll::LE)?);
+    }
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// Parses the directory at `offset` into the resource section at `base`
+fn parse_directory(bytes: &[u8], pe: &pe::PE, base: usize, offset: usize, depth: usize) -> error::Result<Vec<Entry>> {
+    if depth == MAX_DEPTH {
+        return Err(error::Error::Malformed(format!("resource directory at {:#x} is nested more than {} deep", base + offset, MAX_DEPTH)));
+    }
+    let directory = base + offset;
+    let named = bytes.pread_with::<u16>(directory + 12, scroll::LE)? as usize;
+    let ids = bytes.pread_with::<u16>(directory + 14, scroll::LE)? as usize;
+    let mut entries = Vec::with_capacity(named + ids);
+    for i in 0..named + ids {
+        let entry = directory + SIZEOF_RESOURCE_DIRECTORY + i * SIZEOF_RESOURCE_DIRECTORY_ENTRY;
+        let name = bytes.pread_with::<u32>(entry, scroll::LE)?;
+        let data = bytes.pread_with::<u32>(entry + 4, scroll::LE)?;
+        let id = if name & NAME_IS_STRING != 0 {
+            Id::Name(parse_name(bytes, base + (name & !NAME_IS_STRING) as usize)?)
+        } else {
+            Id::Id(name)
+        };
+        let node = if data & DATA_IS_DIRECTORY != 0 {
+            Node::Directory(parse_directory(bytes, pe, base, (data & !DATA_IS_DIRECTORY) as usize, depth + 1)?)
+        } else {
+            // OffsetToData, Size, CodePage, Reserved
+            let leaf = base + data as usize;
+            let rva = bytes.pread_with::<u32>(leaf, scroll::LE)?;
+            Node::Data(Data {
+                rva,
+                size: bytes.pread_with(leaf + 4, scroll::LE)?,
+                codepage: bytes.pread_with(leaf + 8, scroll::LE)?,
+                offset: find_offset(rva, &pe.sections),
+            })
+        };
+        entries.push(Entry { id, node });
+    }
+    Ok(entries)
+}
+
+/// Parses the resource directory tree, if the PE has a resource table
+pub fn parse(bytes: &[u8], pe: &pe::PE) -> error::Result<Option<Vec<Entry>>> {
+    let directory = match pe.header.optional_header {
+        Some(ref optional_header) => match *optional_header.data_directories.get_resource_table() {
+            Some(directory) => directory,
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+    let base = find_offset(directory.virtual_address, &pe.sections)
+        .ok_or_else(|| error::Error::Malformed(format!("resource table rva {:#x} is not in any section", directory.virtual_address)))?;
+    Ok(Some(parse_directory(bytes, pe, base, 0, 0)?))
+}
@@ -0,0 +1,135 @@
+//! The undocumented "Rich" header the MSVC linker writes between the DOS stub and the PE header.
+//!
+//! It is a list of `@comp.id`s, the product and build of each tool that contributed objects to the link,
+//! with a use count each, XORed with a checksum of the DOS header and the list itself. The list starts
+//! with the (masked) `DanS` marker and ends with a plain `Rich` marker followed by the key.
+
+use goblin::error;
+use goblin::pe;
+use scroll::{self, Pread};
+
+/// "DanS", before masking
+const DANS: u32 = 0x536e_6144;
+/// "Rich"
+const RICH: u32 = 0x6863_6952;
+const SIZEOF_DOS_HEADER: usize = 0x40;
+/// `DanS` is followed by three masked zeros before the first entry
+const SIZEOF_PREAMBLE: usize = 16;
+
+#[derive(Debug)]
+pub struct Entry {
+    pub product: u16,
+    pub build: u16,
+    pub count: u32,
+}
+
+#[derive(Debug)]
+pub struct Rich {
+    /// The file offset of the masked `DanS`
+    pub offset: usize,
+    pub key: u32,
+    pub entries: Vec<Entry>,
+    /// The header unmasked, from `DanS` up to `Rich`
+    pub decoded: Vec<u8>,
+}
+
+impl Rich {
+    /// The key the linker would have computed for this DOS header and these entries; a mismatch means the
+    /// header was edited after linking
+    pub fn checksum(&self, bytes: &[u8]) -> u32 {
+        let mut checksum = self.offset as u32;
+        for (i, &byte) in bytes[..self.offset].iter().enumerate() {
+            // e_lfanew is left out, since it is only known after the Rich header is written
+            if (0x3c..0x40).contains(&i) {
+                continue;
+            }
+            checksum = checksum.wrapping_add((byte as u32).rotate_left(i as u32));
+        }
+        for entry in &self.entries {
+            let id = (entry.product as u32) << 16 | entry.build as u32;
+            checksum = checksum.wrapping_add(id.rotate_left(entry.count));
+        }
+        checksum
+    }
+
+    /// The richhash: the MD5 of the unmasked header, which is the same for every binary linked from the
+    /// same objects with the same tools, whatever the key
+    pub fn hash(&self) -> String {
+        format!("{:x}", ::md5::compute(&self.decoded))
+    }
+}
+
+/// The tool a product ID stands for, for the toolchains still in common use
+pub fn product_to_str(product: u16) -> &'static str {
+    match product {
+        0x0000 => "Unknown",
+        0x0001 => "Import0",
+        0x0002 => "Linker510",
+        0x0004 => "Linker600",
+        0x0006 => "Cvtres500",
+        0x000a => "Utc12_C",
+        0x000b => "Utc12_CPP",
+        0x000d => "VisualBasic60",
+        0x0093 => "Implib900",
+        0x009a => "Cvtres1000",
+        0x009b => "Export1000",
+        0x009c => "Implib1000",
+        0x009d => "Linker1000",
+        0x009e => "Masm1000",
+        0x00aa => "Utc1600_C",
+        0x00ab => "Utc1600_CPP",
+        0x00fd => "AliasObj1400",
+        0x00fe => "Cvtpgd1400",
+        0x00ff => "Cvtres1400",
+        0x0100 => "Export1400",
+        0x0101 => "Implib1400",
+        0x0102 => "Linker1400",
+        0x0103 => "Masm1400",
+        0x0104 => "Utc1900_C",
+        0x0105 => "Utc1900_CPP",
+        0x0106 => "Utc1900_CVTCIL_C",
+        0x0107 => "Utc1900_CVTCIL_CPP",
+        0x0108 => "Utc1900_LTCG_C",
+        0x0109 => "Utc1900_LTCG_CPP",
+        0x010a => "Utc1900_LTCG_MSIL",
+        0x010b => "Utc1900_POGO_I_C",
+        0x010c => "Utc1900_POGO_I_CPP",
+        0x010d => "Utc1900_POGO_O_C",
+        0x010e => "Utc1900_POGO_O_CPP",
+        _ => "unknown",
+    }
+}
+
+/// Finds and unmasks the Rich header, if the linker wrote one
+pub fn parse(bytes: &[u8], pe: &pe::PE) -> error::Result<Option<Rich>> {
+    let end = (pe.header.dos_header.pe_pointer as usize).min(bytes.len());
+    // the header is dword aligned, and ends at the last `Rich` before the PE header
+    let rich = (SIZEOF_DOS_HEADER..end.saturating_sub(7)).step_by(4).rev()
+        .find(|&offset| bytes.pread_with::<u32>(offset, scroll::LE).ok() == Some(RICH));
+    let rich = match rich {
+        Some(rich) => rich,
+        None => return Ok(None),
+    };
+    let key = bytes.pread_with::<u32>(rich + 4, scroll::LE)?;
+    // without `DanS` this is just a stray "Rich" in the stub
+    let offset = match (SIZEOF_DOS_HEADER..rich).step_by(4).rev()
+        .find(|&offset| bytes.pread_with::<u32>(offset, scroll::LE).ok() == Some(DANS ^ key)) {
+        Some(offset) => offset,
+        None => return Ok(None),
+    };
+    let mut decoded = Vec::with_capacity(rich - offset);
+    for dword in (offset..rich).step_by(4) {
+        let dword = bytes.pread_with::<u32>(dword, scroll::LE)? ^ key;
+        decoded.extend_from_slice(&dword.to_le_bytes());
+    }
+    let mut entries = Vec::new();
+    for entry in (SIZEOF_PREAMBLE..decoded.len().saturating_sub(7)).step_by(8) {
+        let id = decoded.pread_with::<u32>(entry, scroll::LE)?;
+        entries.push(Entry {
+            product: (id >> 16) as u16,
+            build: id as u16,
+            count: decoded.pread_with(entry + 4, scroll::LE)?,
+        });
+    }
+    Ok(Some(Rich { offset, key, entries, decoded }))
+}
@@ -0,0 +1,560 @@
+//! Binaries built byte by byte, printed the way `print` prints them, but into a buffer instead of a terminal
+
+use super::*;
+
+/// Little endian bytes, with words as wide as the class of the binary
+struct Bytes {
+    buf: Vec<u8>,
+    is_64: bool,
+}
+
+impl Bytes {
+    fn new (is_64: bool) -> Self {
+        Bytes { buf: Vec::new(), is_64 }
+    }
+    fn u8 (&mut self, n: u8) -> &mut Self {
+        self.buf.push(n);
+        self
+    }
+    fn u16 (&mut self, n: u16) -> &mut Self {
+        self.buf.extend_from_slice(&n.to_le_bytes());
+        self
+    }
+    fn u32 (&mut self, n: u32) -> &mut Self {
+        self.buf.extend_from_slice(&n.to_le_bytes());
+        self
+    }
+    fn u64 (&mut self, n: u64) -> &mut Self {
+        self.buf.extend_from_slice(&n.to_le_bytes());
+        self
+    }
+    fn word (&mut self, n: u64) -> &mut Self {
+        if self.is_64 { self.u64(n) } else { self.u32(n as u32) }
+    }
+    fn bytes (&mut self, bytes: &[u8]) -> &mut Self {
+        self.buf.extend_from_slice(bytes);
+        self
+    }
+    /// Zero pads to `offset`, which must not be behind
+    fn pad_to (&mut self, offset: usize) -> &mut Self {
+        assert!(self.buf.len() <= offset, "{:#x} is already past {:#x}", self.buf.len(), offset);
+        self.buf.resize(offset, 0);
+        self
+    }
+    fn align (&mut self, align: usize) -> &mut Self {
+        let len = self.buf.len();
+        self.pad_to(len.div_ceil(align) * align)
+    }
+}
+
+/// `name` as the fixed size, zero padded name of a Mach-o segment or section
+fn name16 (name: &[u8]) -> [u8; 16] {
+    let mut padded = [0; 16];
+    padded[..name.len()].copy_from_slice(name);
+    padded
+}
+
+struct Section<'a> {
+    name: &'a str,
+    sh_type: u32,
+    flags: u64,
+    /// Loaded sections are placed at the file offset of their address; the others follow them
+    addr: u64,
+    data: Vec<u8>,
+    link: u32,
+    info: u32,
+    entsize: u64,
+}
+
+/// Where the fixture executables are loaded
+const ELF64_BASE: u64 = 0x400000;
+const ELF32_BASE: u64 = 0x8048000;
+
+fn elf_base (is_64: bool) -> u64 {
+    if is_64 { ELF64_BASE } else { ELF32_BASE }
+}
+
+/// An x86 or x86-64 ELF of type `e_type` with `sections`, after the null section and before the `.shstrtab` it ends with;
+/// executables get a `PT_LOAD` of the whole file, and enter at their first loaded section
+fn elf_with (is_64: bool, e_type: u16, sections: &[Section]) -> Vec<u8> {
+    use elf::section_header::{SHF_ALLOC, SHT_STRTAB};
+    let (ehsize, phentsize, shentsize) = if is_64 { (64, 56, 64) } else { (52, 32, 40) };
+    let phnum = if e_type == elf::header::ET_EXEC { 1 } else { 0 };
+    let base = elf_base(is_64);
+    let mut shstrtab = vec![0];
+    let mut names = Vec::new();
+    for name in sections.iter().map(|section| section.name).chain(Some(".shstrtab")) {
+        names.push(shstrtab.len() as u32);
+        shstrtab.extend_from_slice(name.as_bytes());
+        shstrtab.push(0);
+    }
+    let mut body = Bytes::new(is_64);
+    body.pad_to(ehsize + phentsize * phnum);
+    let mut offsets = Vec::new();
+    for section in sections {
+        if section.flags & SHF_ALLOC as u64 != 0 && e_type == elf::header::ET_EXEC {
+            body.pad_to((section.addr - base) as usize);
+        } else {
+            body.align(8);
+        }
+        offsets.push(body.buf.len() as u64);
+        body.bytes(&section.data);
+    }
+    let shstrtab_offset = body.align(8).buf.len() as u64;
+    body.bytes(&shstrtab);
+    let shoff = body.align(8).buf.len() as u64;
+    body.pad_to(shoff as usize + shentsize);
+    let shstrtab = Section { name: ".shstrtab", sh_type: SHT_STRTAB, flags: 0, addr: 0, data: shstrtab, link: 0, info: 0, entsize: 0 };
+    for (i, section) in sections.iter().chain(Some(&shstrtab)).enumerate() {
+        let addr = if e_type == elf::header::ET_EXEC { section.addr } else { 0 };
+        let offset = offsets.get(i).cloned().unwrap_or(shstrtab_offset);
+        body.u32(names[i]).u32(section.sh_type).word(section.flags).word(addr).word(offset).word(section.data.len() as u64);
+        body.u32(section.link).u32(section.info).word(8).word(section.entsize);
+    }
+    let size = body.buf.len() as u64;
+
+    let mut out = Bytes::new(is_64);
+    out.bytes(b"\x7fELF").u8(if is_64 { 2 } else { 1 }).u8(1).u8(1).pad_to(16);
+    out.u16(e_type).u16(if is_64 { elf::header::EM_X86_64 } else { elf::header::EM_386 }).u32(1);
+    let entry = sections.iter().find(|section| section.flags & SHF_ALLOC as u64 != 0).map_or(0, |section| section.addr);
+    out.word(if e_type == elf::header::ET_EXEC { entry } else { 0 }).word(if phnum == 0 { 0 } else { ehsize as u64 }).word(shoff);
+    out.u32(0).u16(ehsize as u16).u16(phentsize as u16).u16(phnum as u16).u16(shentsize as u16).u16(sections.len() as u16 + 2).u16(sections.len() as u16 + 1);
+    if phnum != 0 {
+        let (flags, align) = (elf::program_header::PF_R | elf::program_header::PF_W | elf::program_header::PF_X, 0x1000_u32);
+        if is_64 {
+            out.u32(elf::program_header::PT_LOAD).u32(flags).u64(0).u64(base).u64(base).u64(size).u64(size).u64(align as u64);
+        } else {
+            out.u32(elf::program_header::PT_LOAD).u32(0).u32(base as u32).u32(base as u32).u32(size as u32).u32(size as u32).u32(flags).u32(align);
+        }
+    }
+    out.bytes(&body.buf[out.buf.len()..]);
+    out.buf
+}
+
+/// A symbol table and its string table, null symbol first; each symbol is its name, value, size, `st_info`, and section index
+fn elf_syms (is_64: bool, syms: &[(&str, u64, u64, u8, u16)]) -> (Vec<u8>, Vec<u8>) {
+    let mut symtab = Bytes::new(is_64);
+    let mut strtab = vec![0];
+    symtab.pad_to(if is_64 { 24 } else { 16 });
+    for &(name, value, size, info, shndx) in syms {
+        let st_name = strtab.len() as u32;
+        strtab.extend_from_slice(name.as_bytes());
+        strtab.push(0);
+        if is_64 {
+            symtab.u32(st_name).u8(info).u8(0).u16(shndx).u64(value).u64(size);
+        } else {
+            symtab.u32(st_name).u32(value as u32).u32(size as u32).u8(info).u8(0).u16(shndx);
+        }
+    }
+    (symtab.buf, strtab)
+}
+
+fn st_info (bind: u8, typ: u8) -> u8 {
+    bind << 4 | typ
+}
+
+/// A small executable: a `main` in `.text`, and a `counter` in `.data`
+fn elf (is_64: bool) -> Vec<u8> {
+    use elf::section_header::{SHT_PROGBITS, SHT_SYMTAB, SHT_STRTAB, SHF_ALLOC, SHF_EXECINSTR, SHF_WRITE};
+    use elf::sym::{STB_GLOBAL, STT_FUNC, STT_OBJECT};
+    let base = elf_base(is_64);
+    let code = if is_64 { vec![0x55, 0x48, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3] } else { vec![0x55, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3, 0x90] };
+    let (symtab, strtab) = elf_syms(is_64, &[
+        ("main", base + 0x100, code.len() as u64, st_info(STB_GLOBAL, STT_FUNC), 1),
+        ("counter", base + 0x200, 8, st_info(STB_GLOBAL, STT_OBJECT), 2),
+    ]);
+    elf_with(is_64, elf::header::ET_EXEC, &[
+        Section { name: ".text", sh_type: SHT_PROGBITS, flags: (SHF_ALLOC | SHF_EXECINSTR) as u64, addr: base + 0x100, data: code, link: 0, info: 0, entsize: 0 },
+        Section { name: ".data", sh_type: SHT_PROGBITS, flags: (SHF_ALLOC | SHF_WRITE) as u64, addr: base + 0x200, data: vec![0x2a, 0, 0, 0, 0, 0, 0, 0], link: 0, info: 0, entsize: 0 },
+        Section { name: ".symtab", sh_type: SHT_SYMTAB, flags: 0, addr: 0, data: symtab, link: 4, info: 1, entsize: if is_64 { 24 } else { 16 } },
+        Section { name: ".strtab", sh_type: SHT_STRTAB, flags: 0, addr: 0, data: strtab, link: 0, info: 0, entsize: 0 },
+    ])
+}
+
+/// A console executable for x86 or x64, with a `.text` and a `.data` section, no directories, and an overlay after them
+fn pe (is_64: bool) -> Vec<u8> {
+    let mut out = Bytes::new(is_64);
+    out.bytes(b"MZ").pad_to(0x3c).u32(0x40);
+    out.bytes(b"PE\0\0");
+    out.u16(if is_64 { 0x8664 } else { 0x14c }).u16(2).u32(0).u32(0).u32(0);
+    out.u16(if is_64 { 0xf0 } else { 0xe0 }).u16(if is_64 { 0x22 } else { 0x102 });
+    // the standard fields; PE32+ has no BaseOfData
+    out.u16(if is_64 { 0x20b } else { 0x10b }).u8(14).u8(0).u32(0x200).u32(0x200).u32(0).u32(0x1000).u32(0x1000);
+    if !is_64 {
+        out.u32(0x2000);
+    }
+    out.word(if is_64 { 0x140000000 } else { 0x400000 }).u32(0x1000).u32(0x200);
+    out.u16(6).u16(0).u16(0).u16(0).u16(6).u16(0).u32(0).u32(0x3000).u32(0x200).u32(0).u16(3).u16(if is_64 { 0x8160 } else { 0x8140 });
+    out.word(0x100000).word(0x1000).word(0x100000).word(0x1000).u32(0).u32(16);
+    out.pad_to(out.buf.len() + 16 * 8);
+    out.bytes(b".text\0\0\0").u32(0x10).u32(0x1000).u32(0x200).u32(0x200).u32(0).u32(0).u16(0).u16(0).u32(0x60000020);
+    out.bytes(b".data\0\0\0").u32(0x8).u32(0x2000).u32(0x200).u32(0x400).u32(0).u32(0).u16(0).u16(0).u32(0xc0000040);
+    out.pad_to(0x200);
+    if is_64 {
+        out.bytes(&[0x48, 0x83, 0xec, 0x28, 0x31, 0xc0, 0x48, 0x83, 0xc4, 0x28, 0xc3]);
+    } else {
+        out.bytes(&[0x55, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3]);
+    }
+    out.pad_to(0x400).u32(0x2a).pad_to(0x600);
+    out.bytes(b"appended payload");
+    out.buf
+}
+
+/// Where the `__TEXT` segment of the fixture Mach-o binaries is loaded
+fn mach_base (is_64: bool) -> u64 {
+    if is_64 { 0x100000000 } else { 0x1000 }
+}
+
+/// A Mach-o executable for x86 or x86-64, whose one segment is named `segname`, holding a `__text` section with `_main`,
+/// which is in the symbol table, and exported by the `LC_DYLD_INFO_ONLY` export trie, whose bind opcodes import
+/// `_printf` from libSystem
+fn mach_with (is_64: bool, segname: &[u8]) -> Vec<u8> {
+    let (header_size, segment_size, section_size, nlist_size) = if is_64 { (32, 72, 80, 16) } else { (28, 56, 68, 12) };
+    let dylib = b"/usr/lib/libSystem.B.dylib\0\0\0\0\0\0";
+    let commands = segment_size + section_size + 24 + 48 + 24 + dylib.len();
+    let code = if is_64 { vec![0x55, 0x48, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3] } else { vec![0x55, 0x89, 0xe5, 0x31, 0xc0, 0x5d, 0xc3, 0x90] };
+    let text = 0x180;
+    let symoff = text + code.len();
+    let stroff = symoff + nlist_size;
+    let strtab = b"\0_main\0\0";
+    let export_off = stroff + strtab.len();
+    // a root with the single edge `_main` to a terminal node: no flags, at offset 0x180 (0x80 0x03 in uleb128)
+    let trie = [0x00, 0x01, b'_', b'm', b'a', b'i', b'n', 0x00, 0x09, 0x03, 0x00, 0x80, 0x03, 0x00];
+    let bind_off = export_off + trie.len();
+    // from dylib 1, `_printf`, a pointer, in segment 0 at offset 0x180, bound, done
+    let bind = [0x11, 0x40, b'_', b'p', b'r', b'i', b'n', b't', b'f', 0x00, 0x51, 0x70, 0x80, 0x03, 0x90, 0x00];
+    let size = bind_off + bind.len();
+    let base = mach_base(is_64);
+
+    let mut out = Bytes::new(is_64);
+    out.u32(if is_64 { mach::header::MH_MAGIC_64 } else { mach::header::MH_MAGIC });
+    out.u32(if is_64 { 0x01000007 } else { 7 }).u32(3).u32(mach::header::MH_EXECUTE).u32(4).u32(commands as u32).u32(0x00200085);
+    if is_64 {
+        out.u32(0);
+    }
+    out.u32(if is_64 { mach::load_command::LC_SEGMENT_64 } else { mach::load_command::LC_SEGMENT }).u32((segment_size + section_size) as u32);
+    out.bytes(&name16(segname)).word(base).word(0x1000).word(0).word(size as u64).u32(5).u32(5).u32(1).u32(0);
+    out.bytes(&name16(b"__text")).bytes(&name16(segname)).word(base + text as u64).word(code.len() as u64);
+    out.u32(text as u32).u32(4).u32(0).u32(0).u32(0x80000400).u32(0).u32(0);
+    if is_64 {
+        out.u32(0);
+    }
+    out.u32(mach::load_command::LC_SYMTAB).u32(24).u32(symoff as u32).u32(1).u32(stroff as u32).u32(strtab.len() as u32);
+    out.u32(mach::load_command::LC_DYLD_INFO_ONLY).u32(48).u32(0).u32(0).u32(bind_off as u32).u32(bind.len() as u32).u32(0).u32(0).u32(0).u32(0).u32(export_off as u32).u32(trie.len() as u32);
+    out.u32(mach::load_command::LC_LOAD_DYLIB).u32((24 + dylib.len()) as u32).u32(24).u32(2).u32(0x10000).u32(0x10000).bytes(dylib);
+    assert_eq!(out.buf.len(), header_size + commands);
+    out.pad_to(text).bytes(&code);
+    out.u32(1).u8(0x0f).u8(1).u16(0).word(base + text as u64);
+    out.bytes(strtab).bytes(&trie).bytes(&bind);
+    out.buf
+}
+
+fn mach (is_64: bool) -> Vec<u8> {
+    mach_with(is_64, b"__TEXT")
+}
+
+/// A fat binary of the 32 and 64-bit fixtures, each aligned to a page
+fn mach_fat () -> Vec<u8> {
+    let arches = [(7, mach(false)), (0x01000007, mach(true))];
+    let mut out = Bytes::new(false);
+    out.bytes(&0xcafebabe_u32.to_be_bytes()).bytes(&(arches.len() as u32).to_be_bytes());
+    for (i, &(cputype, ref arch)) in arches.iter().enumerate() {
+        for &field in &[cputype, 3, 0x1000 * (i as u32 + 1), arch.len() as u32, 12] {
+            out.bytes(&field.to_be_bytes());
+        }
+    }
+    for (i, (_, arch)) in arches.iter().enumerate() {
+        out.pad_to(0x1000 * (i + 1)).bytes(arch);
+    }
+    out.buf
+}
+
+/// A module that exports `main`, a function of no arguments that returns nothing
+fn wasm () -> Vec<u8> {
+    let mut out = Bytes::new(false);
+    out.bytes(b"\0asm").u32(1);
+    out.bytes(&[1, 4, 1, 0x60, 0, 0]);
+    out.bytes(&[3, 2, 1, 0]);
+    out.bytes(&[7, 8, 1, 4, b'm', b'a', b'i', b'n', 0, 0]);
+    out.bytes(&[10, 4, 1, 2, 0, 0x0b]);
+    out.buf
+}
+
+/// The options `args` parse to, for an input that is never read
+fn opt (args: &[&str]) -> Opt {
+    let args = Some("bingrep").into_iter().chain(args.iter().cloned()).chain(Some("fixture"));
+    let mut opt = Opt::new(Args::from_clap(Args::clap().get_matches_from(args)));
+    // tables are colored when stdout is a terminal, which it may be while testing, but these print to a buffer
+    opt.output = Some("fixture.txt".into());
+    opt
+}
+
+/// colored is switched on and off for the whole process, so the tests that print take turns with it
+static COLOR: ::std::sync::Mutex<()> = ::std::sync::Mutex::new(());
+
+/// Runs `f` with colors on or off
+fn with_color<T, F: FnOnce() -> T> (color: bool, f: F) -> T {
+    let _turn = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    colored::control::set_override(color);
+    f()
+}
+
+/// What `print` prints for `bytes` with `args`, with colors if `color`
+fn render_with (color: bool, bytes: &[u8], args: &[&str]) -> error::Result<String> {
+    let opt = opt(args);
+    let mut out = Vec::new();
+    with_color(color, || print(&opt, bytes, &mut out))?;
+    Ok(String::from_utf8(out).expect("the output is UTF-8"))
+}
+
+/// What `print` prints for `bytes` with `args`, without colors
+fn render (bytes: &[u8], args: &[&str]) -> error::Result<String> {
+    render_with(false, bytes, args)
+}
+
+/// Checks `out` against the snapshot `name` in `src/snapshots`; with `BINGREP_BLESS` set, writes it instead
+fn snapshot (name: &str, out: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("snapshots").join(name);
+    if ::std::env::var_os("BINGREP_BLESS").is_some() {
+        ::std::fs::write(&path, out).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+        return;
+    }
+    let expected = ::std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    assert!(out == expected, "{} changed, run with BINGREP_BLESS=1 to update it if that's intended; it is now:\n{}", name, out);
+}
+
+/// A parsed binary as `display_binary` prints it
+struct Shown<'a, 'b: 'a>(&'a BinaryInfo<'b>, &'a Opt);
+
+impl<'a, 'b> ::std::fmt::Display for Shown<'a, 'b> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let mut out = Vec::new();
+        display_binary(self.0, self.1, &mut out).map_err(|_| ::std::fmt::Error)?;
+        fmt.write_str(&String::from_utf8_lossy(&out))
+    }
+}
+
+#[test]
+fn parses_each_kind_of_binary () {
+    let kind = |bytes: &[u8]| match parse_binary(bytes).expect("the fixture parses").binary {
+        Binary::Elf(ref elf) => format!("elf {}", elf.is_64),
+        Binary::Pe(ref pe) => format!("pe {}", pe.is_64),
+        Binary::Mach(ref mach) => format!("mach {}", mach.is_64),
+        Binary::MachFat(ref arches) => format!("fat {}", arches.iter().filter(|(_, _, mach)| mach.is_ok()).count()),
+        Binary::Wasm(_) => "wasm".into(),
+        _ => "other".into(),
+    };
+    assert_eq!(kind(&elf(false)), "elf false");
+    assert_eq!(kind(&elf(true)), "elf true");
+    assert_eq!(kind(&pe(false)), "pe false");
+    assert_eq!(kind(&pe(true)), "pe true");
+    assert_eq!(kind(&mach(false)), "mach false");
+    assert_eq!(kind(&mach(true)), "mach true");
+    assert_eq!(kind(&mach_fat()), "fat 2");
+    assert_eq!(kind(&wasm()), "wasm");
+}
+
+#[test]
+fn unknown_bytes_are_searched_but_not_displayed () {
+    let bytes = b"not a binary, but it has strings in it";
+    assert!(parse_binary(bytes).is_err());
+    assert!(render(bytes, &[]).is_err());
+    assert!(render(bytes, &["--strings"]).unwrap().contains("it has strings in it"));
+}
+
+#[test]
+fn every_mode_displays_without_a_terminal () {
+    let fixtures = [elf(false), elf(true), pe(false), pe(true), mach(false), mach(true), mach_fat()];
+    let modes: &[&[&str]] = &[&[], &["--debug"], &["--summary"], &["--csv-sections"], &["--functions-only"], &["--export-list"], &["--strings"], &["--carve"], &["--find-function", "55 ?? ??"]];
+    for bytes in fixtures.iter() {
+        for args in modes {
+            match render(bytes, args) {
+                Ok(out) => {
+                    // the other modes list what they find, which for some of the fixtures is nothing
+                    assert!(!out.is_empty() || !args.is_empty(), "nothing was printed");
+                    assert!(!out.contains('\x1b'), "{:?} printed colors", args);
+                },
+                // not every mode supports every format, but every format displays
+                Err(error::Error::Malformed(_)) if !args.is_empty() && *args != ["--debug"] => (),
+                Err(err) => panic!("{:?} failed: {}", args, err),
+            }
+        }
+    }
+}
+
+#[test]
+fn display_wraps_display_binary () {
+    let opt = opt(&[]);
+    let bytes = elf(true);
+    let info = parse_binary(&bytes).unwrap();
+    let shown = with_color(false, || Shown(&info, &opt).to_string());
+    assert_eq!(shown, render(&bytes, &[]).unwrap());
+}
+
+#[test]
+#[cfg(feature = "disasm")]
+fn raw_code_is_disassembled_from_its_base () {
+    let out = render(&[0x31, 0xc0, 0xc3], &["--raw", "--arch", "x86_64", "--base", "0x1000"]).unwrap();
+    assert!(out.contains("0x1000"), "{}", out);
+    assert!(out.contains("ret"), "{}", out);
+}
+
+#[test]
+fn snapshots_with_and_without_padded_addresses () {
+    let fixtures = [("elf32", elf(false)), ("elf64", elf(true)), ("pe32", pe(false)), ("pe64", pe(true)), ("mach32", mach(false)), ("mach64", mach(true))];
+    for (name, bytes) in fixtures.iter() {
+        snapshot(&format!("{}.txt", name), &render(bytes, &[]).unwrap());
+        snapshot(&format!("{}-padded.txt", name), &render(bytes, &["--pad-addresses"]).unwrap());
+    }
+}
+
+#[test]
+fn truncated_binaries_are_errors_not_panics () {
+    let fixtures = [elf(false), elf(true), pe(false), pe(true), mach(false), mach(true), mach_fat(), wasm()];
+    // prettytable 0.6 crashes on every table it prints, whatever the binary
+    for args in FUZZ_ARGS.iter().filter(|args| !args.contains(&"--pretty")) {
+        let opt = opt(args);
+        for bytes in fixtures.iter() {
+            for len in 0..bytes.len() {
+                let mut out = Vec::new();
+                let printed = with_color(false, || print(&opt, &bytes[..len], &mut out));
+                if args.is_empty() && printed.is_ok() && parse_binary(&bytes[..len]).is_ok() {
+                    assert!(!out.is_empty(), "the first {:#x} bytes printed nothing", len);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn symbol_tables_linking_past_the_last_section_are_errors () {
+    use elf::section_header::SHT_SYMTAB;
+    let (symtab, _) = elf_syms(true, &[]);
+    let bytes = elf_with(true, elf::header::ET_REL, &[
+        Section { name: ".symtab", sh_type: SHT_SYMTAB, flags: 0, addr: 0, data: symtab, link: 9, info: 1, entsize: 24 },
+    ]);
+    match render(&bytes, &[]) {
+        Err(error::Error::Malformed(msg)) => assert!(msg.contains("links to section 9"), "{}", msg),
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn sections_at_the_end_of_the_address_space_are_not_a_panic () {
+    use elf::section_header::{SHT_INIT_ARRAY, SHT_PROGBITS};
+    let top = u64::MAX - 7;
+    let got = Section { name: ".got", sh_type: SHT_PROGBITS, flags: 0, addr: top, data: vec![0; 16], link: 0, info: 0, entsize: 8 };
+    let init = Section { name: ".init_array", sh_type: SHT_INIT_ARRAY, flags: 0, addr: top, data: vec![0; 16], link: 0, info: 0, entsize: 8 };
+    let bytes = elf_with(true, elf::header::ET_EXEC, &[got, init]);
+    // the init array is in the default display; the last slot of each section is past the end of the address space
+    for args in &[&[][..], &["--got"], &["--at", "0x40"]] {
+        match render(&bytes, args) {
+            Ok(_) | Err(error::Error::Malformed(_)) => (),
+            Err(err) => panic!("{:?} failed: {}", args, err),
+        }
+    }
+}
+
+#[test]
+fn mach_symbol_tables_that_dont_parse_arent_called_stripped () {
+    let mut bytes = mach(true);
+    // the nsyms of the LC_SYMTAB, which follows the header, the segment command, and its section
+    bytes[32 + 72 + 80 + 12..][..4].copy_from_slice(&0xffff_u32.to_le_bytes());
+    let out = render(&bytes, &[]).unwrap();
+    assert!(out.contains("stripped: unknown (Malformed entity: 0xffff symbols"), "{}", out);
+}
+
+#[test]
+fn mach_sections_and_exports_use_the_theme_colors () {
+    for &(name, is_64) in &[("mach32-colored.txt", false), ("mach64-colored.txt", true)] {
+        let out = render_with(true, &mach(is_64), &[]).unwrap();
+        snapshot(name, &out);
+    }
+}
+
+/// An x86-64 object built with `-ffunction-sections`: `foo::bar()` in its own section calls `foo::baz()`, which is defined elsewhere
+fn cpp_object () -> Vec<u8> {
+    use elf::section_header::{SHT_PROGBITS, SHT_RELA, SHT_SYMTAB, SHT_STRTAB, SHF_ALLOC, SHF_EXECINSTR, SHF_INFO_LINK};
+    use elf::sym::{STB_GLOBAL, STT_FUNC, STT_NOTYPE};
+    let (symtab, strtab) = elf_syms(true, &[
+        ("_ZN3foo3barEv", 0, 6, st_info(STB_GLOBAL, STT_FUNC), 1),
+        ("_ZN3foo3bazEv", 0, 0, st_info(STB_GLOBAL, STT_NOTYPE), 0),
+    ]);
+    // the call's displacement, to foo::baz() through the PLT
+    let mut rela = Bytes::new(true);
+    rela.u64(1).u64(2 << 32 | elf::reloc::R_X86_64_PLT32 as u64).u64(-4_i64 as u64);
+    elf_with(true, elf::header::ET_REL, &[
+        Section { name: ".text._ZN3foo3barEv", sh_type: SHT_PROGBITS, flags: (SHF_ALLOC | SHF_EXECINSTR) as u64, addr: 0, data: vec![0xe8, 0, 0, 0, 0, 0xc3], link: 0, info: 0, entsize: 0 },
+        Section { name: ".rela.text._ZN3foo3barEv", sh_type: SHT_RELA, flags: SHF_INFO_LINK as u64, addr: 0, data: rela.buf, link: 3, info: 1, entsize: 24 },
+        Section { name: ".symtab", sh_type: SHT_SYMTAB, flags: 0, addr: 0, data: symtab, link: 4, info: 1, entsize: 24 },
+        Section { name: ".strtab", sh_type: SHT_STRTAB, flags: 0, addr: 0, data: strtab, link: 0, info: 0, entsize: 0 },
+    ])
+}
+
+#[test]
+fn relocation_targets_are_demangled () {
+    let out = render(&cpp_object(), &["--demangle"]).unwrap();
+    assert!(out.contains("  .text.foo::bar()(1)\n"), "{}", out);
+    assert!(out.contains(" X86_64_PLT32 foo::baz()+"), "{}", out);
+    let out = render(&cpp_object(), &[]).unwrap();
+    assert!(out.contains("  .text._ZN3foo3barEv(1)\n"), "{}", out);
+    assert!(out.contains(" X86_64_PLT32 _ZN3foo3bazEv+"), "{}", out);
+}
+
+#[test]
+fn bad_segment_names_are_shown_as_such () {
+    let bytes = mach_with(true, b"__T\xffXT");
+    for args in &[&[][..], &["--summary"]] {
+        let out = render(&bytes, args).unwrap();
+        assert!(out.contains(BAD_SEGMENT_NAME), "{:?}: {}", args, out);
+    }
+}
+
+/// An x86-64 object whose code makes `calls` calls to `distinct` template instantiations, as C++ objects do
+fn template_object (distinct: usize, calls: usize) -> Vec<u8> {
+    use elf::section_header::{SHT_PROGBITS, SHT_RELA, SHT_SYMTAB, SHT_STRTAB, SHF_ALLOC, SHF_EXECINSTR, SHF_INFO_LINK};
+    use elf::sym::{STB_GLOBAL, STT_NOTYPE};
+    let names = (0..distinct).map(|i| format!("_ZNK3foo6widgetILi{}EE5applyIN3bar5gizmoIiEEEEvRKT_", i)).collect::<Vec<_>>();
+    let syms = names.iter().map(|name| (name.as_str(), 0, 0, st_info(STB_GLOBAL, STT_NOTYPE), 0)).collect::<Vec<_>>();
+    let (symtab, strtab) = elf_syms(true, &syms);
+    let mut code = Vec::new();
+    let mut rela = Bytes::new(true);
+    for call in 0..calls {
+        rela.u64(code.len() as u64 + 1).u64(((call % distinct) as u64 + 1) << 32 | elf::reloc::R_X86_64_PLT32 as u64).u64(-4_i64 as u64);
+        code.extend_from_slice(&[0xe8, 0, 0, 0, 0]);
+    }
+    elf_with(true, elf::header::ET_REL, &[
+        Section { name: ".text", sh_type: SHT_PROGBITS, flags: (SHF_ALLOC | SHF_EXECINSTR) as u64, addr: 0, data: code, link: 0, info: 0, entsize: 0 },
+        Section { name: ".rela.text", sh_type: SHT_RELA, flags: SHF_INFO_LINK as u64, addr: 0, data: rela.buf, link: 3, info: 1, entsize: 24 },
+        Section { name: ".symtab", sh_type: SHT_SYMTAB, flags: 0, addr: 0, data: symtab, link: 4, info: 1, entsize: 24 },
+        Section { name: ".strtab", sh_type: SHT_STRTAB, flags: 0, addr: 0, data: strtab, link: 0, info: 0, entsize: 0 },
+    ])
+}
+
+/// A benchmark rather than a test, of demangling the relocations of a C++ object with and without the cache;
+/// run it with `cargo test --release demangle_cache -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn demangle_cache_benchmark () {
+    use std::time::Instant;
+    let bytes = template_object(200, 20000);
+    let elf = parse_elf(&bytes).unwrap();
+    // the names in the order the relocation listing asks for them
+    let names = elf.shdr_relocs.iter().flat_map(|(_, relocs)| relocs.iter())
+        .map(|reloc| strtab_get(&elf.strtab, elf.syms.get(reloc.r_sym).unwrap().st_name))
+        .collect::<Vec<_>>();
+    let start = Instant::now();
+    let uncached = names.iter().map(|name| demangle(name)).collect::<Vec<_>>();
+    let uncached_time = start.elapsed();
+    let opt = opt(&["--demangle"]);
+    let start = Instant::now();
+    let cached = names.iter().map(|name| symbol_name(&opt, name)).collect::<Vec<_>>();
+    let cached_time = start.elapsed();
+    assert_eq!(cached, uncached);
+    let start = Instant::now();
+    render(&bytes, &["--demangle"]).unwrap();
+    let listing_time = start.elapsed();
+    println!("{} names, {} of them distinct: {:?} uncached, {:?} cached; the whole --demangle listing takes {:?}",
+             names.len(), 200, uncached_time, cached_time, listing_time);
+    assert!(cached_time * 2 < uncached_time, "the cache saves nothing");
+}
@@ -0,0 +1,221 @@
+//! Color themes, mapping the semantic roles of the output (addresses, sizes, ...) to colors.
+//!
+//! A theme is either built in, or read from a file of `role = color` lines, e.g.:
+//!
+//! ```text
+//! # addresses in blue on light terminals
+//! addr = blue
+//! string = magenta
+//! ```
+//!
+//! Roles missing from a file keep their default color. The `BINGREP_COLORS` environment variable
+//! overrides roles of whichever theme is in use with the same pairs, comma separated, e.g.
+//! `BINGREP_COLORS=addr=bright_red,off=blue,sz=cyan`.
+
+use std::fs::File;
+use std::io::Read;
+use colored::{self, Colorize};
+use goblin::error;
+
+/// The environment variable whose `role=color` pairs override the theme
+pub const COLORS_VAR: &str = "BINGREP_COLORS";
+
+#[derive(Debug, Copy, Clone)]
+pub struct Theme {
+    pub addr: &'static str,
+    pub offset: &'static str,
+    pub size: &'static str,
+    /// Symbol, section, and library names
+    pub string: &'static str,
+    /// Section headers like `Syms(12)`
+    pub header: &'static str,
+    /// Indices alternate between this on `index_alt`, and `index_alt` on this
+    pub index: &'static str,
+    pub index_alt: &'static str,
+    /// Whether section headers are colored at all
+    pub color_headers: bool,
+}
+
+pub const DEFAULT: Theme = Theme {
+    addr: "red",
+    offset: "yellow",
+    size: "green",
+    string: "yellow",
+    header: "white",
+    index: "white",
+    index_alt: "black",
+    color_headers: true,
+};
+
+/// For terminals with a light background, where yellow and white are unreadable
+pub const LIGHT: Theme = Theme {
+    addr: "red",
+    offset: "magenta",
+    size: "green",
+    string: "blue",
+    header: "black",
+    index: "black",
+    index_alt: "white",
+    color_headers: true,
+};
+
+/// Approximates the solarized accents with the 8 basic colors
+pub const SOLARIZED: Theme = Theme {
+    addr: "blue",
+    offset: "yellow",
+    size: "cyan",
+    string: "magenta",
+    header: "green",
+    index: "white",
+    index_alt: "blue",
+    color_headers: true,
+};
+
+pub const THEMES: [(&str, Theme); 3] = [
+    ("default", DEFAULT),
+    ("light", LIGHT),
+    ("solarized", SOLARIZED),
+];
+
+/// The canonical name of `color`, which is what colored expects, or `bright_` and that name
+pub fn parse_color(color: &str) -> Option<&'static str> {
+    match color.trim().to_lowercase().replace(' ', "_").as_str() {
+        "black" => Some("black"),
+        "red" => Some("red"),
+        "green" => Some("green"),
+        "yellow" => Some("yellow"),
+        "blue" => Some("blue"),
+        "magenta" | "purple" => Some("magenta"),
+        "cyan" => Some("cyan"),
+        "white" => Some("white"),
+        "bright_black" => Some("bright_black"),
+        "bright_red" => Some("bright_red"),
+        "bright_green" => Some("bright_green"),
+        "bright_yellow" => Some("bright_yellow"),
+        "bright_blue" => Some("bright_blue"),
+        "bright_magenta" | "bright_purple" => Some("bright_magenta"),
+        "bright_cyan" => Some("bright_cyan"),
+        "bright_white" => Some("bright_white"),
+        _ => None,
+    }
+}
+
+/// `color` without its `bright_` prefix
+fn base(color: &str) -> &str {
+    color.trim_start_matches("bright_")
+}
+
+/// Colors `s` with `color`; colored has no bright colors, so they are drawn bold, which most terminals render bright
+pub fn paint<S: Colorize>(s: S, color: &str) -> colored::ColoredString {
+    if color.starts_with("bright_") { s.color(base(color)).bold() } else { s.color(color) }
+}
+
+/// Colors the background of `s` with `color`, dropping any brightness
+pub fn paint_bg<S: Colorize>(s: S, color: &str) -> colored::ColoredString {
+    s.on_color(base(color))
+}
+
+impl Theme {
+    /// A built-in theme by name
+    pub fn named(name: &str) -> Option<Theme> {
+        THEMES.iter().find(|&&(theme, _)| theme == name).map(|&(_, theme)| theme)
+    }
+
+    /// Sets `role` to `color`, failing if either is unknown
+    pub fn set_role(&mut self, role: &str, color: &str) -> Result<(), String> {
+        let color = parse_color(color).ok_or_else(|| format!("unknown color `{}`", color.trim()))?;
+        match role.trim() {
+            "addr" => self.addr = color,
+            "offset" | "off" => self.offset = color,
+            "size" | "sz" => self.size = color,
+            "string" | "str" => self.string = color,
+            "header" | "hdr" => self.header = color,
+            "index" | "idx" => self.index = color,
+            "index_alt" | "idx_alt" => self.index_alt = color,
+            role => return Err(format!("unknown role `{}`", role)),
+        }
+        Ok(())
+    }
+
+    /// Parses a theme file's `role = color` lines on top of the default theme
+    pub fn parse(contents: &str) -> error::Result<Theme> {
+        let mut theme = DEFAULT;
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let (role, color) = match (parts.next(), parts.next()) {
+                (Some(role), Some(color)) => (role, color),
+                _ => return Err(error::Error::Malformed(format!("theme line {}: expected `role = color`", i + 1))),
+            };
+            theme.set_role(role, color).map_err(|err| error::Error::Malformed(format!("theme line {}: {}", i + 1, err)))?;
+        }
+        Ok(theme)
+    }
+
+    /// Applies the comma separated `role=color` pairs of `BINGREP_COLORS`, as found in `colors`
+    pub fn override_with(&mut self, colors: &str) -> error::Result<()> {
+        for pair in colors.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let mut parts = pair.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(role), Some(color)) => self.set_role(role, color).map_err(|err| error::Error::Malformed(format!("{}: {}", COLORS_VAR, err)))?,
+                _ => return Err(error::Error::Malformed(format!("{}: expected `role=color`, got `{}`", COLORS_VAR, pair.trim()))),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The prettytable style spec letter closest to `color`; uppercase is the bright variant
+pub fn spec(color: &str) -> char {
+    let letter = match base(color) {
+        "black" => 'd',
+        "red" => 'r',
+        "green" => 'g',
+        "yellow" => 'y',
+        "blue" => 'b',
+        "magenta" => 'm',
+        "cyan" => 'c',
+        _ => 'w',
+    };
+    if color.starts_with("bright_") { letter.to_ascii_uppercase() } else { letter }
+}
+
+#[derive(Debug)]
+pub struct ParseThemeError(String);
+
+impl ::std::fmt::Display for ParseThemeError {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseThemeError {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A built-in theme by name, or else a theme file by path, with the overrides of `BINGREP_COLORS` applied
+impl ::std::str::FromStr for Theme {
+    type Err = ParseThemeError;
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let mut theme = match Theme::named(name) {
+            Some(theme) => theme,
+            None => {
+                let mut contents = String::new();
+                File::open(name).and_then(|mut file| file.read_to_string(&mut contents)).map_err(|err| {
+                    let names = THEMES.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+                    ParseThemeError(format!("`{}` is neither a built-in theme ({}) nor a readable theme file: {}", name, names.join(", "), err))
+                })?;
+                Theme::parse(&contents).map_err(|err| ParseThemeError(err.to_string()))?
+            },
+        };
+        if let Ok(colors) = ::std::env::var(COLORS_VAR) {
+            theme.override_with(&colors).map_err(|err| ParseThemeError(err.to_string()))?;
+        }
+        Ok(theme)
+    }
+}
@@ -0,0 +1,101 @@
+//! Mach-o thread states, as found in `LC_THREAD` and `LC_UNIXTHREAD`.
+//!
+//! goblin only reads the i386 state, so the command is parsed here from its raw bytes: after the
+//! `cmd` and `cmdsize`, it holds one or more `flavor`, `count`, and `count` words of register state.
+
+use goblin::error;
+use goblin::mach::constants::cputype::{CPU_TYPE_X86, CPU_TYPE_X86_64, CPU_TYPE_ARM, CPU_TYPE_ARM64};
+use scroll::{self, Pread};
+
+pub const X86_THREAD_STATE32: u32 = 1;
+pub const X86_THREAD_STATE64: u32 = 4;
+pub const ARM_THREAD_STATE: u32 = 1;
+pub const ARM_THREAD_STATE64: u32 = 6;
+
+const X86_REGISTERS32: [&str; 16] = [
+    "eax", "ebx", "ecx", "edx", "edi", "esi", "ebp", "esp",
+    "ss", "eflags", "eip", "cs", "ds", "es", "fs", "gs",
+];
+
+const X86_REGISTERS64: [&str; 21] = [
+    "rax", "rbx", "rcx", "rdx", "rdi", "rsi", "rbp", "rsp",
+    "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15",
+    "rip", "rflags", "cs", "fs", "gs",
+];
+
+const ARM_REGISTERS: [&str; 17] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12",
+    "sp", "lr", "pc", "cpsr",
+];
+
+const ARM_REGISTERS64: [&str; 34] = [
+    "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14",
+    "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27", "x28",
+    "fp", "lr", "sp", "pc", "cpsr",
+];
+
+#[derive(Debug)]
+pub struct ThreadState {
+    pub flavor: u32,
+    /// The registers, in the order the kernel lays them out; empty for flavors that aren't known
+    pub registers: Vec<(&'static str, u64)>,
+    /// The name of the program counter register
+    pub pc: &'static str,
+}
+
+pub fn flavor_to_str(cputype: u32, flavor: u32) -> &'static str {
+    match (cputype, flavor) {
+        (CPU_TYPE_X86, X86_THREAD_STATE32) => "x86_THREAD_STATE32",
+        (CPU_TYPE_X86_64, X86_THREAD_STATE64) => "x86_THREAD_STATE64",
+        (CPU_TYPE_ARM, ARM_THREAD_STATE) => "ARM_THREAD_STATE",
+        (CPU_TYPE_ARM64, ARM_THREAD_STATE64) => "ARM_THREAD_STATE64",
+        _ => "UNKNOWN_FLAVOR",
+    }
+}
+
+impl ThreadState {
+    /// The value of the program counter, i.e., the entry point
+    pub fn pc(&self) -> Option<u64> {
+        self.registers.iter().find(|&&(name, _)| name == self.pc).map(|&(_, value)| value)
+    }
+}
+
+/// Parses the thread states of the thread command at the start of `bytes`, for a `cputype` binary
+pub fn parse(bytes: &[u8], cputype: u32, little_endian: bool) -> error::Result<Vec<ThreadState>> {
+    let le = scroll::Endian::from(little_endian);
+    let cmdsize = bytes.pread_with::<u32>(4, le)? as usize;
+    let mut states = Vec::new();
+    let mut offset = 8;
+    while offset + 8 <= cmdsize {
+        let flavor = bytes.pread_with::<u32>(offset, le)?;
+        let count = bytes.pread_with::<u32>(offset + 4, le)? as usize;
+        offset += 8;
+        let state = bytes.get(offset..offset + count * 4)
+            .ok_or_else(|| error::Error::Malformed(format!("thread state of {} words runs past the end of the command", count)))?;
+        let (names, pc, wide): (&[&'static str], &'static str, bool) = match (cputype, flavor) {
+            (CPU_TYPE_X86, X86_THREAD_STATE32) => (&X86_REGISTERS32, "eip", false),
+            (CPU_TYPE_X86_64, X86_THREAD_STATE64) => (&X86_REGISTERS64, "rip", true),
+            (CPU_TYPE_ARM, ARM_THREAD_STATE) => (&ARM_REGISTERS, "pc", false),
+            (CPU_TYPE_ARM64, ARM_THREAD_STATE64) => (&ARM_REGISTERS64, "pc", true),
+            _ => (&[], "", false),
+        };
+        let mut registers = Vec::with_capacity(names.len());
+        for (i, &name) in names.iter().enumerate() {
+            // the arm64 cpsr is the only 32-bit register in its 64-bit state
+            let value = if wide && name != "cpsr" {
+                state.pread_with::<u64>(i * 8, le)
+            } else if wide {
+                state.pread_with::<u32>(i * 8, le).map(|value| value as u64)
+            } else {
+                state.pread_with::<u32>(i * 4, le).map(|value| value as u64)
+            };
+            match value {
+                Ok(value) => registers.push((name, value)),
+                Err(_) => break,
+            }
+        }
+        states.push(ThreadState { flavor, registers, pc });
+        offset += count * 4;
+    }
+    Ok(states)
+}
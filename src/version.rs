@@ -0,0 +1,116 @@
+//! GNU symbol versioning: the `.gnu.version` (versym) table, `.gnu.version_d` (verdef) definitions, and
+//! `.gnu.version_r` (verneed) requirements.
+
+use goblin::error;
+use goblin::strtab::Strtab;
+use scroll::{self, Pread};
+
+/// The versym index of a global, unversioned symbol
+pub const VER_NDX_GLOBAL: u16 = 1;
+/// The version index bits of a versym entry; the top bit marks hidden versions
+pub const VERSYM_VERSION: u16 = 0x7fff;
+
+/// The verdef flag of the version named after the library itself, which its unversioned symbols have
+pub const VER_FLG_BASE: u16 = 0x1;
+
+/// A version the binary defines
+#[derive(Debug)]
+pub struct Defined<'a> {
+    /// The version, e.g. `LIBFOO_1.0`
+    pub name: &'a str,
+    pub flags: u16,
+    /// The versym index that refers to this version
+    pub index: u16,
+    /// The version this one succeeds, if any
+    pub parent: Option<&'a str>,
+}
+
+/// A version one of the binary's DT_NEEDED libraries must provide
+#[derive(Debug)]
+pub struct Needed<'a> {
+    /// The library, e.g. `libc.so.6`
+    pub file: &'a str,
+    /// The version, e.g. `GLIBC_2.34`
+    pub name: &'a str,
+    /// The versym index that refers to this version
+    pub index: u16,
+}
+
+/// Parses the versym table in `bytes`, one entry per dynamic symbol
+pub fn versym(bytes: &[u8], little_endian: bool) -> Vec<u16> {
+    let le = scroll::Endian::from(little_endian);
+    (0..bytes.len() / 2).filter_map(|i| bytes.pread_with::<u16>(i * 2, le).ok()).collect()
+}
+
+/// Parses the verneed section `bytes`, whose names are in `strtab`
+pub fn verneed<'a>(bytes: &[u8], little_endian: bool, strtab: &Strtab<'a>) -> error::Result<Vec<Needed<'a>>> {
+    let le = scroll::Endian::from(little_endian);
+    let mut needed = Vec::new();
+    let mut offset = 0;
+    loop {
+        // vn_version, vn_cnt, vn_file, vn_aux, vn_next
+        let count = bytes.pread_with::<u16>(offset + 2, le)?;
+        let file = strtab.get(bytes.pread_with::<u32>(offset + 4, le)? as usize)?;
+        let mut aux = offset + bytes.pread_with::<u32>(offset + 8, le)? as usize;
+        for _ in 0..count {
+            // vna_hash, vna_flags, vna_other, vna_name, vna_next
+            needed.push(Needed {
+                file,
+                index: bytes.pread_with(aux + 6, le)?,
+                name: strtab.get(bytes.pread_with::<u32>(aux + 8, le)? as usize)?,
+            });
+            let next = bytes.pread_with::<u32>(aux + 12, le)? as usize;
+            if next == 0 {
+                break;
+            }
+            aux += next;
+        }
+        let next = bytes.pread_with::<u32>(offset + 12, le)? as usize;
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    Ok(needed)
+}
+
+/// Parses the verdef section `bytes`, whose names are in `strtab`
+pub fn verdef<'a>(bytes: &[u8], little_endian: bool, strtab: &Strtab<'a>) -> error::Result<Vec<Defined<'a>>> {
+    let le = scroll::Endian::from(little_endian);
+    let mut defined = Vec::new();
+    let mut offset = 0;
+    loop {
+        // vd_version, vd_flags, vd_ndx, vd_cnt, vd_hash, vd_aux, vd_next
+        let flags = bytes.pread_with::<u16>(offset + 2, le)?;
+        let index = bytes.pread_with::<u16>(offset + 4, le)?;
+        let count = bytes.pread_with::<u16>(offset + 6, le)?;
+        // the first verdaux names the version, and the second its parent; vda_name, vda_next
+        let aux = offset + bytes.pread_with::<u32>(offset + 12, le)? as usize;
+        let name = strtab.get(bytes.pread_with::<u32>(aux, le)? as usize)?;
+        let parent = match bytes.pread_with::<u32>(aux + 4, le)? as usize {
+            next if count > 1 && next != 0 => Some(strtab.get(bytes.pread_with::<u32>(aux + next, le)? as usize)?),
+            _ => None,
+        };
+        defined.push(Defined { name, flags, index, parent });
+        let next = bytes.pread_with::<u32>(offset + 16, le)? as usize;
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    Ok(defined)
+}
+
+/// The glibc release a `GLIBC_2.34` style version is from, e.g. `[2, 34]`; `GLIBC_PRIVATE` and other
+/// libraries' versions have none
+pub fn glibc(name: &str) -> Option<Vec<u32>> {
+    if !name.starts_with("GLIBC_") {
+        return None;
+    }
+    name["GLIBC_".len()..].split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Formats a version like `[2, 34]` as `2.34`
+pub fn glibc_to_string(version: &[u32]) -> String {
+    version.iter().map(|part| part.to_string()).collect::<Vec<_>>().join(".")
+}
@@ -0,0 +1,206 @@
+//! WebAssembly module support.
+//!
+//! goblin doesn't know about wasm, so modules are parsed with `wasmparser` and collected into
+//! a flat summary of the sections bingrep displays.
+
+use std::collections::HashMap;
+use goblin::error;
+use wasmparser::{self, Payload, KnownCustom, Name, Encoding};
+
+pub use wasmparser::{Import, Export, ExternalKind, TypeRef, SubType, CompositeInnerType, TableType, MemoryType};
+
+/// The magic at the start of every wasm module, `\0asm`
+pub const WASM_MAGIC: &[u8] = b"\0asm";
+
+/// A section as it appears in the file, with the range of its contents
+#[derive(Debug)]
+pub struct Section<'a> {
+    pub id: u8,
+    /// The section name; custom sections carry their own
+    pub name: &'a str,
+    pub offset: usize,
+    pub size: usize,
+}
+
+#[derive(Debug)]
+pub struct Custom<'a> {
+    pub name: &'a str,
+    /// The file offset of the section contents, after the name
+    pub offset: usize,
+    pub data: &'a [u8],
+}
+
+#[derive(Debug)]
+pub struct Module<'a> {
+    pub version: u16,
+    pub sections: Vec<Section<'a>>,
+    pub types: Vec<SubType>,
+    pub imports: Vec<Import<'a>>,
+    pub exports: Vec<Export<'a>>,
+    /// The type index of each function defined in the module
+    pub functions: Vec<u32>,
+    /// The tables defined in the module
+    pub tables: Vec<TableType>,
+    /// The memories defined in the module
+    pub memories: Vec<MemoryType>,
+    pub customs: Vec<Custom<'a>>,
+    /// Function names from the `name` custom section, by function index
+    pub names: HashMap<u32, &'a str>,
+}
+
+impl<'a> Module<'a> {
+    /// The number of imported functions, which come first in the function index space
+    pub fn imported_functions(&self) -> usize {
+        self.imports.iter().filter(|import| matches!(import.ty, TypeRef::Func(_))).count()
+    }
+    /// The number of imported tables, which come first in the table index space
+    pub fn imported_tables(&self) -> usize {
+        self.imports.iter().filter(|import| matches!(import.ty, TypeRef::Table(_))).count()
+    }
+    /// The number of imported memories, which come first in the memory index space
+    pub fn imported_memories(&self) -> usize {
+        self.imports.iter().filter(|import| matches!(import.ty, TypeRef::Memory(_))).count()
+    }
+    /// The signature of type `index`, e.g. `(i32, i32) -> (i64)`
+    pub fn signature(&self, index: u32) -> String {
+        match self.types.get(index as usize) {
+            Some(typ) => match typ.composite_type.inner {
+                CompositeInnerType::Func(ref func) => {
+                    let params = func.params().iter().map(|ty| ty.to_string()).collect::<Vec<_>>();
+                    let results = func.results().iter().map(|ty| ty.to_string()).collect::<Vec<_>>();
+                    format!("({}) -> ({})", params.join(", "), results.join(", "))
+                },
+                ref inner => format!("{:?}", inner),
+            },
+            None => format!("<bad type index {}>", index),
+        }
+    }
+}
+
+/// The element type and limits of a table, e.g. `funcref min: 1 max: 1`
+pub fn table_to_string(table: &TableType) -> String {
+    let max = table.maximum.map_or("none".to_owned(), |max| max.to_string());
+    let mut s = format!("{} min: {} max: {}", table.element_type, table.initial, max);
+    if table.table64 {
+        s.push_str(" table64");
+    }
+    if table.shared {
+        s.push_str(" shared");
+    }
+    s
+}
+
+/// The limits of a memory in pages, e.g. `min: 17 max: none`
+pub fn memory_to_string(memory: &MemoryType) -> String {
+    let max = memory.maximum.map_or("none".to_owned(), |max| max.to_string());
+    let mut s = format!("min: {} max: {}", memory.initial, max);
+    if memory.memory64 {
+        s.push_str(" memory64");
+    }
+    if memory.shared {
+        s.push_str(" shared");
+    }
+    s
+}
+
+pub fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+fn malformed(err: wasmparser::BinaryReaderError) -> error::Error {
+    error::Error::Malformed(format!("wasm: {}", err))
+}
+
+pub fn parse<'a>(bytes: &'a [u8]) -> error::Result<Module<'a>> {
+    let mut module = Module {
+        version: 0,
+        sections: Vec::new(),
+        types: Vec::new(),
+        imports: Vec::new(),
+        exports: Vec::new(),
+        functions: Vec::new(),
+        tables: Vec::new(),
+        memories: Vec::new(),
+        customs: Vec::new(),
+        names: HashMap::new(),
+    };
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        let payload = payload.map_err(malformed)?;
+        if let Some((id, range)) = payload.as_section() {
+            let name = match payload {
+                Payload::CustomSection(ref reader) => reader.name(),
+                _ => section_name(id),
+            };
+            module.sections.push(Section { id, name, offset: range.start, size: range.end - range.start });
+        }
+        match payload {
+            Payload::Version { num, encoding, .. } => {
+                if encoding != Encoding::Module {
+                    return Err(error::Error::Malformed("wasm components are not supported".into()));
+                }
+                module.version = num;
+            },
+            Payload::TypeSection(reader) => {
+                for group in reader {
+                    module.types.extend(group.map_err(malformed)?.into_types());
+                }
+            },
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    module.imports.push(import.map_err(malformed)?);
+                }
+            },
+            Payload::FunctionSection(reader) => {
+                for typ in reader {
+                    module.functions.push(typ.map_err(malformed)?);
+                }
+            },
+            Payload::TableSection(reader) => {
+                for table in reader {
+                    module.tables.push(table.map_err(malformed)?.ty);
+                }
+            },
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    module.memories.push(memory.map_err(malformed)?);
+                }
+            },
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    module.exports.push(export.map_err(malformed)?);
+                }
+            },
+            Payload::CustomSection(reader) => {
+                if let KnownCustom::Name(names) = reader.as_known() {
+                    // a malformed name section is only missing debug info, not worth failing over
+                    for name in names {
+                        if let Ok(Name::Function(map)) = name {
+                            for naming in map.into_iter().filter_map(|naming| naming.ok()) {
+                                module.names.insert(naming.index, naming.name);
+                            }
+                        }
+                    }
+                }
+                module.customs.push(Custom { name: reader.name(), offset: reader.data_offset(), data: reader.data() });
+            },
+            _ => (),
+        }
+    }
+    Ok(module)
+}